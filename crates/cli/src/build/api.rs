@@ -7,7 +7,7 @@
 //! necessary.
 
 use super::{
-    data::{self, AdditionalCategory, ItemAudit},
+    data::{self, AdditionalCategory, ClomonitorScore, ItemAudit},
     LandscapeData, LandscapeSettings,
 };
 use chrono::NaiveDate;
@@ -222,6 +222,9 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chat_channel: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clomonitor_report: Option<ClomonitorReport>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
 
@@ -267,6 +270,9 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub openssf_best_practices_url: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openssf_scorecard: Option<Scorecard>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oss: Option<bool>,
 
@@ -310,6 +316,7 @@ impl Item {
             blog_url: item.blog_url.clone(),
             category: item.category.clone(),
             chat_channel: item.chat_channel.clone(),
+            clomonitor_report: item.clomonitor_report.as_ref().map(Into::into),
             country: item.crunchbase_data.as_ref().and_then(|cb| cb.country.clone()),
             crunchbase_url: item.crunchbase_url.clone(),
             description: item.description().cloned(),
@@ -333,6 +340,7 @@ impl Item {
             maturity: item.maturity.clone(),
             name: item.name.clone(),
             openssf_best_practices_url: item.openssf_best_practices_url.clone(),
+            openssf_scorecard: item.openssf_scorecard.as_ref().map(Into::into),
             oss: item.oss,
             repositories: item.repositories.as_ref().map(|repos| repos.iter().map(Into::into).collect()),
             slack_url: item.slack_url.clone(),
@@ -348,14 +356,50 @@ impl Item {
     }
 }
 
+/// CLOMonitor report information that will be exposed from the API.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ClomonitorReport {
+    pub score: ClomonitorScore,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed_checks: Vec<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub passed_checks: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl From<&data::ClomonitorReport> for ClomonitorReport {
+    fn from(r: &data::ClomonitorReport) -> Self {
+        Self {
+            score: r.score.clone(),
+            failed_checks: r.failed_checks.clone(),
+            passed_checks: r.passed_checks.clone(),
+            rating: r.rating.clone(),
+            url: r.url.clone(),
+        }
+    }
+}
+
 /// Repository information that will be exposed from the API.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Repository {
     pub url: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub languages: Option<BTreeMap<String, i64>>,
 
@@ -367,13 +411,55 @@ impl From<&data::Repository> for Repository {
     fn from(r: &data::Repository) -> Self {
         Self {
             url: r.url.clone(),
+            archived: r.github_data.as_ref().and_then(|gh| gh.archived),
             branch: r.branch.clone(),
+            disabled: r.github_data.as_ref().and_then(|gh| gh.disabled),
             languages: r.github_data.as_ref().and_then(|gh| gh.languages.clone()),
             primary: r.primary,
         }
     }
 }
 
+/// OpenSSF Scorecard information that will be exposed from the API.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Scorecard {
+    pub score: f64,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<ScorecardCheck>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl From<&data::Scorecard> for Scorecard {
+    fn from(s: &data::Scorecard) -> Self {
+        Self {
+            score: s.score,
+            checks: s
+                .checks
+                .iter()
+                .map(|c| ScorecardCheck {
+                    name: c.name.clone(),
+                    score: c.score,
+                })
+                .collect(),
+            date: s.date.clone(),
+            url: s.url.clone(),
+        }
+    }
+}
+
+/// OpenSSF Scorecard check information that will be exposed from the API.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ScorecardCheck {
+    pub name: String,
+    pub score: i64,
+}
+
 /// Helper function to serialize the count value provided as a json string.
 fn count_to_json(count: usize) -> String {
     json!({"count": count}).to_string()
@@ -1,16 +1,133 @@
 //! This module defines the cache used to cache files across builds.
+//!
+//! Data collected from external services is stored per entry (one file for
+//! each key in the source's namespace directory), so that it can be persisted
+//! incrementally while it's being collected. All writes are atomic (data is
+//! written to a temporary file that is then renamed) and coordinated using a
+//! lock file, so that the cache can be shared safely by concurrent builds.
+//! Writes hold the lock in shared mode, so they don't block each other, while
+//! operations that span several files (like migrating legacy files or pruning
+//! the cache) hold it in exclusive mode.
 
-use anyhow::{bail, Result};
-use std::{fs, io::Write, path::PathBuf, time::SystemTime};
-use tracing::instrument;
+use super::{
+    clomonitor::CLOMONITOR_CACHE_TTL,
+    crunchbase::CRUNCHBASE_CACHE_TTL,
+    forges::{bitbucket::BITBUCKET_CACHE_TTL, gitea::GITEA_CACHE_TTL, gitlab::GITLAB_CACHE_TTL},
+    github::GITHUB_CACHE_TTL,
+    issues::{IssueKind, Issues},
+    scorecard::SCORECARD_CACHE_TTL,
+};
+use anyhow::{bail, format_err, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use fs2::FileExt;
+use futures::{
+    stream::{self, StreamExt},
+    Future,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::Write,
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+use tracing::{debug, instrument, warn};
+use walkdir::WalkDir;
 
 /// Path where the cache files will be written to inside the cache directory.
 const CACHE_PATH: &str = "landscape";
 
+/// Legacy cache files (all entries of a source in a single JSON file) and the
+/// namespaces their entries are migrated to.
+const LEGACY_CACHE_FILES: [(&str, &str); 2] = [("crunchbase.json", "crunchbase"), ("github.json", "github")];
+
+/// Name of the lock file used to coordinate the processes using the cache.
+const LOCK_FILE: &str = ".lock";
+
+/// Counter used to generate unique temporary files names.
+static TMP_FILES_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How long the data collected from each of the external services is valid.
+#[derive(Args, Clone)]
+pub struct CacheTtls {
+    /// How long the Bitbucket data in the cache is valid (in days).
+    #[arg(
+        long = "bitbucket-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = BITBUCKET_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub bitbucket: i64,
+
+    /// How long the CLOMonitor data in the cache is valid (in days).
+    #[arg(
+        long = "clomonitor-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = CLOMONITOR_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub clomonitor: i64,
+
+    /// How long the Crunchbase data in the cache is valid (in days).
+    #[arg(
+        long = "crunchbase-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = CRUNCHBASE_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub crunchbase: i64,
+
+    /// How long the Gitea/Forgejo data in the cache is valid (in days).
+    #[arg(
+        long = "gitea-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = GITEA_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub gitea: i64,
+
+    /// How long the GitHub data in the cache is valid (in days).
+    #[arg(
+        long = "github-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = GITHUB_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub github: i64,
+
+    /// How long the GitLab data in the cache is valid (in days).
+    #[arg(
+        long = "gitlab-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = GITLAB_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub gitlab: i64,
+
+    /// How long the OpenSSF Scorecard data in the cache is valid (in days).
+    #[arg(
+        long = "scorecard-cache-ttl",
+        value_name = "DAYS",
+        default_value_t = SCORECARD_CACHE_TTL,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub scorecard: i64,
+}
+
 /// Cache used to store data collected from external services.
 #[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_field_names)]
 pub(crate) struct Cache {
     cache_dir: PathBuf,
+    exclusive_lock_held: Arc<AtomicBool>,
+    stats: Arc<Mutex<BTreeMap<String, CacheStats>>>,
 }
 
 impl Cache {
@@ -27,7 +144,13 @@ impl Cache {
             if !cache_dir.exists() {
                 fs::create_dir_all(&cache_dir)?;
             }
-            return Ok(Self { cache_dir });
+            let cache = Self {
+                cache_dir,
+                exclusive_lock_held: Arc::new(AtomicBool::new(false)),
+                stats: Arc::new(Mutex::new(BTreeMap::new())),
+            };
+            cache.migrate_legacy_files();
+            return Ok(cache);
         }
 
         bail!(
@@ -35,11 +158,27 @@ impl Cache {
         );
     }
 
+    /// List the files available in the cache (paths relative to the cache
+    /// directory). Internal files, like the lock file, are not included.
+    pub(crate) fn list(&self) -> Result<Vec<String>> {
+        let mut files = vec![];
+        for entry in WalkDir::new(&self.cache_dir).min_depth(1) {
+            let entry = entry?;
+            if !entry.file_type().is_file() || entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = entry.path().strip_prefix(&self.cache_dir)?;
+            files.push(path.to_string_lossy().into_owned());
+        }
+        files.sort();
+        Ok(files)
+    }
+
     /// Read data from the cache file provided if available.
     #[instrument(skip(self), err)]
     pub(crate) fn read(&self, file_name: &str) -> Result<Option<(Option<SystemTime>, Vec<u8>)>> {
         // Check if the path exists
-        let path = self.cache_dir.join(file_name);
+        let path = self.path(file_name)?;
         if !path.exists() {
             return Ok(None);
         }
@@ -51,12 +190,606 @@ impl Cache {
         Ok(Some((modified_at, fs::read(&path)?)))
     }
 
-    /// Write provided data to cache file.
+    /// Read the entry with the key provided from the namespace given.
+    #[instrument(skip(self), err)]
+    pub(crate) fn read_entry<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Result<Option<T>> {
+        let Some((_, data)) = self.read(&entry_file_name(namespace, key))? else {
+            return Ok(None);
+        };
+        let entry: Entry<T> = serde_json::from_slice(&data)?;
+        Ok(Some(entry.value))
+    }
+
+    /// Read all the entries available in the namespace provided. Entries that
+    /// cannot be read are skipped (a warning is logged).
+    #[instrument(skip(self), err)]
+    pub(crate) fn read_entries<T: DeserializeOwned>(&self, namespace: &str) -> Result<BTreeMap<String, T>> {
+        let mut entries = BTreeMap::new();
+        let namespace_dir = self.cache_dir.join(namespace);
+        if !namespace_dir.exists() {
+            return Ok(entries);
+        }
+        for file in fs::read_dir(namespace_dir)? {
+            let file = file?;
+            if file.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = file.path();
+            let entry: Entry<T> = match serde_json::from_slice(&fs::read(&path)?) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    warn!(?path, ?err, "skipping invalid cache entry");
+                    continue;
+                }
+            };
+            entries.insert(entry.key, entry.value);
+        }
+        Ok(entries)
+    }
+
+    /// Record a cache hit for the source provided.
+    pub(crate) fn record_hit(&self, source: &str) {
+        let mut stats = self.stats.lock().expect("cache stats lock not to be poisoned");
+        stats.entry(source.to_string()).or_default().hits += 1;
+    }
+
+    /// Record a cache miss for the source provided.
+    pub(crate) fn record_miss(&self, source: &str) {
+        let mut stats = self.stats.lock().expect("cache stats lock not to be poisoned");
+        stats.entry(source.to_string()).or_default().misses += 1;
+    }
+
+    /// Remove the cache file provided.
+    #[instrument(skip(self), err)]
+    pub(crate) fn remove(&self, file_name: &str) -> Result<()> {
+        let path = self.path(file_name)?;
+        let _lock = self.lock_shared()?;
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    /// Remove the entry with the key provided from the namespace given.
+    pub(crate) fn remove_entry(&self, namespace: &str, key: &str) -> Result<()> {
+        self.remove(&entry_file_name(namespace, key))
+    }
+
+    /// Set the last modification time of the cache file provided.
+    #[instrument(skip(self), err)]
+    pub(crate) fn set_modified(&self, file_name: &str, modified_at: SystemTime) -> Result<()> {
+        let path = self.path(file_name)?;
+        let _lock = self.lock_shared()?;
+        let file = fs::File::options().write(true).open(path)?;
+        file.set_modified(modified_at)?;
+        Ok(())
+    }
+
+    /// Get the cache hits and misses recorded for each source.
+    pub(crate) fn stats(&self) -> BTreeMap<String, CacheStats> {
+        self.stats.lock().expect("cache stats lock not to be poisoned").clone()
+    }
+
+    /// Write provided data to cache file. The data is written to a temporary
+    /// file first, which is renamed once it's complete, so that readers never
+    /// see a partially written file.
     #[instrument(skip(self, data), err)]
     pub(crate) fn write(&self, file_name: &str, data: &[u8]) -> Result<()> {
-        let path = self.cache_dir.join(file_name);
-        let mut file = fs::File::create(path)?;
+        let path = self.path(file_name)?;
+        let Some(parent) = path.parent() else {
+            bail!("invalid cache file name: {file_name}");
+        };
+        fs::create_dir_all(parent)?;
+
+        // Write data to a temporary file in the same directory
+        let tmp_path = parent.join(format!(
+            ".{}.{}.{}.tmp",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            std::process::id(),
+            TMP_FILES_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut file = fs::File::create(&tmp_path)?;
         file.write_all(data)?;
+        file.sync_all()?;
+        drop(file);
+
+        // Move it to its final location (renames are atomic, so concurrent
+        // writers of the same entry won't corrupt it, the last one wins)
+        let _lock = self.lock_shared()?;
+        if let Err(err) = fs::rename(&tmp_path, &path) {
+            _ = fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Write the entry provided to the namespace given.
+    pub(crate) fn write_entry<T: Serialize>(&self, namespace: &str, key: &str, value: &T) -> Result<()> {
+        let entry = Entry {
+            key: key.to_string(),
+            value,
+        };
+        self.write(
+            &entry_file_name(namespace, key),
+            &serde_json::to_vec_pretty(&entry)?,
+        )
+    }
+
+    /// Acquire the cache lock in exclusive mode, so that no other process can
+    /// write to the cache until the guard returned is dropped. Operations that
+    /// read and write several files should hold it while they run.
+    pub(crate) fn lock(&self) -> Result<CacheLock> {
+        let file = self.open_lock_file()?;
+        FileExt::lock_exclusive(&file)?;
+        self.exclusive_lock_held.store(true, Ordering::SeqCst);
+        Ok(CacheLock {
+            _file: file,
+            exclusive_lock_held: Some(self.exclusive_lock_held.clone()),
+        })
+    }
+
+    /// Acquire the cache lock in shared mode. No lock is acquired if this
+    /// cache instance already holds it in exclusive mode.
+    fn lock_shared(&self) -> Result<Option<CacheLock>> {
+        if self.exclusive_lock_held.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
+        let file = self.open_lock_file()?;
+        FileExt::lock_shared(&file)?;
+        Ok(Some(CacheLock {
+            _file: file,
+            exclusive_lock_held: None,
+        }))
+    }
+
+    /// Open (creating it if needed) the lock file.
+    fn open_lock_file(&self) -> Result<fs::File> {
+        let file = fs::File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.cache_dir.join(LOCK_FILE))?;
+        Ok(file)
+    }
+
+    /// Migrate the entries in the legacy cache files (if any) to the per entry
+    /// storage. Legacy files are removed once they have been migrated.
+    fn migrate_legacy_files(&self) {
+        if !LEGACY_CACHE_FILES
+            .iter()
+            .any(|(legacy_file, _)| self.cache_dir.join(legacy_file).exists())
+        {
+            return;
+        }
+        let _lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(err) => {
+                warn!(?err, "error acquiring cache lock to migrate legacy cache files");
+                return;
+            }
+        };
+        for (legacy_file, namespace) in LEGACY_CACHE_FILES {
+            if let Err(err) = self.migrate_legacy_file(legacy_file, namespace) {
+                warn!(?err, legacy_file, "error migrating legacy cache file");
+            }
+        }
+    }
+
+    /// Migrate the entries in the legacy cache file provided.
+    fn migrate_legacy_file(&self, legacy_file: &str, namespace: &str) -> Result<()> {
+        let Some((_, data)) = self.read(legacy_file)? else {
+            return Ok(());
+        };
+        let entries: BTreeMap<String, serde_json::Value> = serde_json::from_slice(&data)?;
+        for (key, value) in &entries {
+            // Entries written by newer builds take precedence
+            if self.read(&entry_file_name(namespace, key))?.is_none() {
+                self.write_entry(namespace, key, value)?;
+            }
+        }
+        self.remove(legacy_file)?;
+        debug!(legacy_file, entries = entries.len(), "legacy cache file migrated");
         Ok(())
     }
+
+    /// Get the path of the cache file provided, making sure it's located
+    /// inside the cache directory.
+    fn path(&self, file_name: &str) -> Result<PathBuf> {
+        let relative_path = Path::new(file_name);
+        if !relative_path.components().all(|c| matches!(c, Component::Normal(_))) {
+            bail!("invalid cache file name: {file_name}");
+        }
+        Ok(self.cache_dir.join(relative_path))
+    }
+}
+
+/// Cache hits and misses recorded for a given source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Cache entry, as stored in the entry's file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry<T> {
+    key: String,
+    value: T,
+}
+
+/// Guard that releases the cache lock when dropped (the lock is released when
+/// the lock file is closed).
+pub(crate) struct CacheLock {
+    _file: fs::File,
+    exclusive_lock_held: Option<Arc<AtomicBool>>,
+}
+
+impl Drop for CacheLock {
+    fn drop(&mut self) {
+        if let Some(exclusive_lock_held) = &self.exclusive_lock_held {
+            exclusive_lock_held.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Data collected from an external service that is stored in the cache.
+pub(crate) trait CachedData: Clone + Serialize + DeserializeOwned + Send + Sync {
+    /// Get the time when the data was collected.
+    fn generated_at(&self) -> DateTime<Utc>;
+
+    /// Set the time when the data was collected (used to force its
+    /// expiration).
+    fn set_generated_at(&mut self, generated_at: DateTime<Utc>);
+
+    /// Set or clear the flag used to indicate that the data is stale.
+    fn set_stale(&mut self, stale: bool);
+}
+
+/// External service the data collected by a [CachedCollector] comes from.
+pub(crate) struct CachedSource<'a> {
+    /// Name used to identify the source in the cache stats and in the logs.
+    pub name: &'a str,
+
+    /// Namespace used to store the data collected in the cache.
+    pub namespace: &'a str,
+
+    /// Kind of the issues recorded when some data cannot be collected.
+    pub issue_kind: IssueKind,
+
+    /// How long the data in the cache is valid (in days).
+    pub ttl: i64,
+
+    /// Maximum percentage of the entries refreshed in a single build.
+    pub refresh_percentage: Option<u8>,
+
+    /// Only use the data available in the cache.
+    pub offline: bool,
+}
+
+/// Collector of data from an external service that reuses the data in the
+/// cache whenever possible. Expired entries are refreshed (up to the refresh
+/// percentage allowed), and the expired data is used, flagged as stale, when
+/// they cannot be.
+pub(crate) struct CachedCollector<'a, T> {
+    cache: &'a Cache,
+    issues: &'a Issues,
+    source: CachedSource<'a>,
+    cached_data: BTreeMap<String, T>,
+    entries_to_refresh: HashSet<String>,
+}
+
+impl<'a, T: CachedData> CachedCollector<'a, T> {
+    /// Create a new CachedCollector instance, reading the cached data of the
+    /// keys provided and selecting the expired entries that will be refreshed
+    /// in this build. Cached entries not accepted by `is_valid` are ignored.
+    pub(crate) fn new<'k>(
+        cache: &'a Cache,
+        issues: &'a Issues,
+        source: CachedSource<'a>,
+        keys: impl IntoIterator<Item = &'k String>,
+        is_valid: impl Fn(&String, &T) -> bool,
+    ) -> Self {
+        let mut cached_data = BTreeMap::new();
+        for key in keys {
+            match cache.read_entry(source.namespace, key) {
+                Ok(Some(cached_value)) if is_valid(key, &cached_value) => {
+                    cached_data.insert(key.clone(), cached_value);
+                }
+                Ok(_) => {}
+                Err(err) => warn!(?err, ?key, source = source.name, "error reading cached data"),
+            }
+        }
+        let entries_to_refresh = select_entries_to_refresh(
+            cached_data.iter().map(|(key, cached_value)| (key, cached_value.generated_at())),
+            source.ttl,
+            source.refresh_percentage,
+        );
+
+        Self {
+            cache,
+            issues,
+            source,
+            cached_data,
+            entries_to_refresh,
+        }
+    }
+
+    /// Check if the data of the key provided will be fetched from the source
+    /// in this build.
+    pub(crate) fn will_fetch(&self, key: &str) -> bool {
+        !self.source.offline && (!self.cached_data.contains_key(key) || self.entries_to_refresh.contains(key))
+    }
+
+    /// Collect the data of the keys provided (along with some context needed
+    /// to fetch it, like the repository branch), using the cached data when
+    /// available and fetching it from the source otherwise. Fresh data is
+    /// written to the cache as soon as it's fetched. The keys whose data could
+    /// not be collected (or refreshed) are recorded as issues.
+    pub(crate) async fn collect<K, F, Fut>(
+        &self,
+        keys: Vec<(String, K)>,
+        concurrency: usize,
+        fetch: F,
+    ) -> BTreeMap<String, T>
+    where
+        K: Send,
+        F: Fn(String, K) -> Fut + Sync,
+        Fut: Future<Output = Result<T>> + Send,
+    {
+        let source = &self.source;
+        let fetch = &fetch;
+        let results: Vec<(String, Result<T>, bool)> = stream::iter(keys)
+            .map(|(key, context)| async move {
+                let cached_value = self.cached_data.get(&key);
+
+                // Use cached data when available unless it has expired and it
+                // has been selected to be refreshed in this build (cached data
+                // is always used in offline mode). Expired entries deferred to
+                // next builds are not flagged as stale, as no refresh has been
+                // attempted yet
+                if let Some(cached_value) = cached_value {
+                    if source.offline || !self.entries_to_refresh.contains(&key) {
+                        self.cache.record_hit(source.name);
+                        return (key, Ok(stale_copy(cached_value, false)), false);
+                    }
+                }
+                self.cache.record_miss(source.name);
+
+                // Otherwise we fetch it from the source
+                let result = if source.offline {
+                    Err(format_err!("not available in cache (offline mode)"))
+                } else {
+                    fetch(key.clone(), context).await
+                };
+
+                // Persist fresh data right away, so that it's not lost if the
+                // collection is interrupted
+                if let Ok(value) = &result {
+                    if let Err(err) = self.cache.write_entry(source.namespace, &key, value) {
+                        warn!(?err, ?key, source = source.name, "error writing data to cache");
+                    }
+                }
+
+                // Fall back to the expired cached data if it could not be
+                // refreshed
+                match (result, cached_value) {
+                    (Err(err), Some(cached_value)) => {
+                        debug!(
+                            ?err,
+                            ?key,
+                            source = source.name,
+                            "error refreshing data, using stale cached data"
+                        );
+                        self.issues.add_stale_data(source.issue_kind, &key, &err);
+                        (key, Ok(stale_copy(cached_value, true)), true)
+                    }
+                    (result, _) => (key, result, false),
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        // Record the keys whose data could not be collected
+        let mut data = BTreeMap::new();
+        let mut stale_count = 0;
+        for (key, result, stale) in results {
+            match result {
+                Ok(value) => {
+                    if stale {
+                        stale_count += 1;
+                    }
+                    data.insert(key, value);
+                }
+                Err(err) => self.issues.add(source.issue_kind, &key, &err),
+            }
+        }
+
+        // Warn about the entries using stale data
+        if stale_count > 0 {
+            warn!(
+                "using stale cached {} data for {stale_count} entries (data expired and could not be refreshed)",
+                source.name
+            );
+        }
+
+        data
+    }
+}
+
+/// Return a copy of the cached data provided, setting the stale flag when the
+/// data could not be refreshed.
+fn stale_copy<T: CachedData>(cached_value: &T, stale: bool) -> T {
+    let mut value = cached_value.clone();
+    value.set_stale(stale);
+    value
+}
+
+/// Select the expired entries that will be refreshed in this build. When a
+/// refresh percentage is provided, only up to that percentage of the entries
+/// will be selected (oldest first), so that refreshing the cached data can be
+/// spread across several builds.
+fn select_entries_to_refresh<'a>(
+    entries: impl Iterator<Item = (&'a String, DateTime<Utc>)>,
+    ttl: i64,
+    refresh_percentage: Option<u8>,
+) -> HashSet<String> {
+    let mut entries: Vec<(&String, DateTime<Utc>)> = entries.collect();
+    let entries_count = entries.len();
+
+    // Select expired entries, oldest first
+    let expired_at = Utc::now() - chrono::Duration::days(ttl);
+    entries.retain(|(_, generated_at)| *generated_at <= expired_at);
+    entries.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+
+    // Limit the number of entries to refresh if needed
+    let expired_count = entries.len();
+    if let Some(refresh_percentage) = refresh_percentage {
+        entries.truncate((entries_count * usize::from(refresh_percentage)).div_ceil(100));
+        if entries.len() < expired_count {
+            debug!(
+                "refreshing {} of {expired_count} expired entries (the rest will be refreshed in next builds)",
+                entries.len()
+            );
+        }
+    }
+
+    entries.into_iter().map(|(key, _)| key.clone()).collect()
+}
+
+/// Get the name of the file used to store the entry provided.
+fn entry_file_name(namespace: &str, key: &str) -> String {
+    format!("{namespace}/{}.json", hex::encode(Sha256::digest(key.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use landscape2_core::data::Organization;
+    use tempfile::TempDir;
+
+    const TTL: i64 = 7;
+
+    #[tokio::test]
+    async fn collect_cached_data_refresh_failed() {
+        let (cache, _cache_dir) = setup_cache();
+        cache.write_entry("test", "expired", &sample_org(10)).unwrap();
+        cache.write_entry("test", "fresh", &sample_org(1)).unwrap();
+
+        let issues = Issues::new();
+        let collector: CachedCollector<Organization> =
+            CachedCollector::new(&cache, &issues, sample_source(None), &keys(), |_, _| true);
+        let data = collector
+            .collect(keys_with_context(), 1, |key, ()| async move {
+                match key.as_str() {
+                    "new" => Ok(sample_org(0)),
+                    _ => bail!("fetch error"),
+                }
+            })
+            .await;
+
+        assert_eq!(data["expired"].stale, Some(true));
+        assert_eq!(data["fresh"].stale, None);
+        assert_eq!(data["new"].stale, None);
+        let stale_data = issues.get_stale_data();
+        assert_eq!(stale_data.len(), 1);
+        assert_eq!(stale_data[0].id, "expired");
+        assert!(issues.get(IssueKind::Crunchbase).is_empty());
+    }
+
+    #[tokio::test]
+    async fn collect_cached_data_refresh_deferred() {
+        let (cache, _cache_dir) = setup_cache();
+        cache.write_entry("test", "expired", &sample_org(10)).unwrap();
+        cache.write_entry("test", "fresh", &sample_org(9)).unwrap();
+
+        // Only the oldest expired entry is selected to be refreshed
+        let issues = Issues::new();
+        let collector: CachedCollector<Organization> =
+            CachedCollector::new(&cache, &issues, sample_source(Some(50)), &keys(), |_, _| true);
+        assert!(collector.will_fetch("expired"));
+        assert!(!collector.will_fetch("fresh"));
+        let data = collector
+            .collect(keys_with_context(), 1, |key, ()| async move {
+                match key.as_str() {
+                    "new" => Ok(sample_org(0)),
+                    _ => bail!("fetch error"),
+                }
+            })
+            .await;
+
+        assert_eq!(data["expired"].stale, Some(true));
+        assert_eq!(data["fresh"].stale, None);
+    }
+
+    #[test]
+    fn lock_blocks_writes_from_other_processes() {
+        let (cache, cache_dir) = setup_cache();
+        let other_cache = Cache::new(&Some(cache_dir.path().to_path_buf())).unwrap();
+
+        // Writes from the cache instance holding the lock are not blocked
+        let lock = cache.lock().unwrap();
+        cache.write_entry("test", "key", &"value").unwrap();
+
+        // Writes from other instances wait until the lock is released
+        let (tx, rx) = std::sync::mpsc::channel();
+        let writer = std::thread::spawn(move || {
+            other_cache.write_entry("test", "key", &"other value").unwrap();
+            tx.send(()).unwrap();
+        });
+        let blocked = rx.recv_timeout(std::time::Duration::from_millis(250)).is_err();
+        drop(lock);
+        writer.join().unwrap();
+        let value: Option<String> = cache.read_entry("test", "key").unwrap();
+
+        assert!(blocked);
+        assert_eq!(value, Some("other value".to_string()));
+    }
+
+    #[test]
+    fn read_entries_skips_invalid_files() {
+        let (cache, _cache_dir) = setup_cache();
+        cache.write_entry("test", "key", &"value").unwrap();
+        cache.write("test/invalid.json", b"{").unwrap();
+
+        let entries: BTreeMap<String, String> = cache.read_entries("test").unwrap();
+
+        assert_eq!(
+            entries,
+            BTreeMap::from([("key".to_string(), "value".to_string())])
+        );
+    }
+
+    fn keys() -> Vec<String> {
+        vec!["expired".to_string(), "fresh".to_string(), "new".to_string()]
+    }
+
+    fn keys_with_context() -> Vec<(String, ())> {
+        keys().into_iter().map(|key| (key, ())).collect()
+    }
+
+    fn sample_org(days_ago: i64) -> Organization {
+        Organization {
+            generated_at: Utc::now() - chrono::Duration::days(days_ago),
+            ..Default::default()
+        }
+    }
+
+    fn sample_source(refresh_percentage: Option<u8>) -> CachedSource<'static> {
+        CachedSource {
+            name: "test",
+            namespace: "test",
+            issue_kind: IssueKind::Crunchbase,
+            ttl: TTL,
+            refresh_percentage,
+            offline: false,
+        }
+    }
+
+    /// Create a new cache in a temporary directory for testing purposes (the
+    /// directory is removed when the TempDir returned is dropped).
+    fn setup_cache() -> (Cache, TempDir) {
+        let cache_dir = TempDir::new().unwrap();
+        let cache = Cache::new(&Some(cache_dir.path().to_path_buf())).unwrap();
+        (cache, cache_dir)
+    }
 }
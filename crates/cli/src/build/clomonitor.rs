@@ -1,41 +1,67 @@
-//! This module provides the functionality to collect projects' reports
-//! summaries from CLOMonitor (https://clomonitor.io).
+//! This module provides the functionality to collect projects' reports and
+//! reports summaries from CLOMonitor (https://clomonitor.io by default, but a
+//! self-hosted instance can be configured in the landscape settings).
 
 use super::cache::Cache;
 use anyhow::{bail, Result};
 use chrono::{DateTime, Utc};
+use landscape2_core::{
+    data::{ClomonitorReport, ClomonitorScore},
+    settings::Clomonitor,
+};
 use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::debug;
 
-/// How long the CLOMonitor data in the cache is valid (in days).
-const CLOMONITOR_CACHE_TTL: i64 = 7;
+/// Name used to track the CLOMonitor data in the cache stats.
+const CLOMONITOR_CACHE_SOURCE: &str = "clomonitor";
 
-/// Foundations supported by CLOMonitor.
-const SUPPORTED_FOUNDATIONS: [&str; 2] = ["cncf", "lfaidata"];
+/// Prefix of the files used to cache the CLOMonitor reports summaries.
+pub(crate) const CLOMONITOR_CACHE_FILE_PREFIX: &str = "clomonitor_";
 
-/// Fetch project's report summary in SVG format from CLOMonitor.
+/// Default for how long the CLOMonitor data in the cache is valid (in days).
+pub(crate) const CLOMONITOR_CACHE_TTL: i64 = 7;
+
+/// Default CLOMonitor base url.
+const CLOMONITOR_URL: &str = "https://clomonitor.io";
+
+/// Return the base url of the CLOMonitor instance configured.
+fn base_url(clomonitor: &Clomonitor) -> &str {
+    clomonitor.url.as_deref().unwrap_or(CLOMONITOR_URL).trim_end_matches('/')
+}
+
+/// Fetch project's report summary in SVG format from CLOMonitor. In offline
+/// mode, only cached report summaries are used (regardless of their age).
 pub(crate) async fn fetch_report_summary(
     cache: &Cache,
     http_client: reqwest::Client,
-    foundation: &str,
+    clomonitor: &Clomonitor,
     project_name: &str,
+    ttl: i64,
+    offline: bool,
 ) -> Result<Option<Vec<u8>>> {
-    // Check if the foundation provided is supported by CLOMonitor
-    let foundation = foundation.to_lowercase();
-    if !SUPPORTED_FOUNDATIONS.contains(&foundation.as_str()) {
-        return Ok(None);
-    }
+    let foundation = &clomonitor.foundation;
 
     // Use cached report summary (if available and not expired)
-    let cache_file = format!("clomonitor_{foundation}_{project_name}.svg");
+    let cache_file = format!("{CLOMONITOR_CACHE_FILE_PREFIX}{foundation}_{project_name}.svg");
     if let Ok(Some((Some(modified_at), cached_report_summary))) = cache.read(&cache_file) {
         let modified_at: DateTime<Utc> = modified_at.into();
-        if Utc::now() - chrono::Duration::days(CLOMONITOR_CACHE_TTL) < modified_at {
+        if offline || Utc::now() - chrono::Duration::days(ttl) < modified_at {
+            cache.record_hit(CLOMONITOR_CACHE_SOURCE);
             return Ok(Some(cached_report_summary));
         }
     }
+    cache.record_miss(CLOMONITOR_CACHE_SOURCE);
+    if offline {
+        return Ok(None);
+    }
 
     // Fetch report summary from CLOMonitor
-    let url = format!("https://clomonitor.io/api/projects/{foundation}/{project_name}/report-summary");
+    let url = format!(
+        "{}/api/projects/{foundation}/{project_name}/report-summary",
+        base_url(clomonitor)
+    );
     let resp = http_client.get(url).send().await?;
     match resp.status() {
         StatusCode::OK => {
@@ -50,3 +76,359 @@ pub(crate) async fn fetch_report_summary(
         ),
     }
 }
+
+/// Fetch project's report from CLOMonitor. In offline mode, only cached
+/// reports are used (regardless of their age).
+pub(crate) async fn fetch_report(
+    cache: &Cache,
+    http_client: reqwest::Client,
+    clomonitor: &Clomonitor,
+    project_name: &str,
+    ttl: i64,
+    offline: bool,
+) -> Result<Option<ClomonitorReport>> {
+    let foundation = &clomonitor.foundation;
+
+    // Use cached report (if available and not expired)
+    let cache_file = format!("{CLOMONITOR_CACHE_FILE_PREFIX}{foundation}_{project_name}.json");
+    if let Ok(Some((Some(modified_at), cached_report))) = cache.read(&cache_file) {
+        let modified_at: DateTime<Utc> = modified_at.into();
+        if offline || Utc::now() - chrono::Duration::days(ttl) < modified_at {
+            // Cached reports that cannot be decoded are fetched again
+            match serde_json::from_slice(&cached_report) {
+                Ok(report) => {
+                    cache.record_hit(CLOMONITOR_CACHE_SOURCE);
+                    return Ok(Some(report));
+                }
+                Err(err) => debug!(?err, ?cache_file, "invalid cached clomonitor report, ignoring it"),
+            }
+        }
+    }
+    cache.record_miss(CLOMONITOR_CACHE_SOURCE);
+    if offline {
+        return Ok(None);
+    }
+
+    // Fetch project from CLOMonitor and prepare the report
+    let base_url = base_url(clomonitor);
+    let url = format!("{base_url}/api/projects/{foundation}/{project_name}");
+    let resp = http_client.get(url).send().await?;
+    match resp.status() {
+        StatusCode::OK => {
+            let project: CMProject = resp.json().await?;
+            let project_url = format!("{base_url}/projects/{foundation}/{project_name}");
+            let report = new_report_from(project, project_url);
+            cache.write(&cache_file, &serde_json::to_vec(&report)?)?;
+            Ok(Some(report))
+        }
+        StatusCode::NOT_FOUND => Ok(None),
+        _ => bail!(
+            "unexpected status code getting clomonitor project: {}",
+            resp.status()
+        ),
+    }
+}
+
+/// Create a new report from the CLOMonitor project provided. Checks are
+/// considered passed when they have passed (or have been exempted) in any of
+/// the project's repositories.
+fn new_report_from(project: CMProject, url: String) -> ClomonitorReport {
+    let mut passed_checks = BTreeSet::new();
+    let mut checks = BTreeSet::new();
+    for repo in project.repositories {
+        let Some(data) = repo.report.and_then(|report| report.data) else {
+            continue;
+        };
+        for (section, section_checks) in data {
+            for (check, result) in section_checks {
+                let Some(result) = result else {
+                    continue;
+                };
+                let check = format!("{section}/{check}");
+                if result.passed || result.exempt.unwrap_or_default() {
+                    passed_checks.insert(check.clone());
+                }
+                checks.insert(check);
+            }
+        }
+    }
+
+    let score = project.score.unwrap_or_default();
+    ClomonitorReport {
+        generated_at: Utc::now(),
+        score: ClomonitorScore {
+            global: score.global.unwrap_or_default(),
+            best_practices: score.best_practices,
+            documentation: score.documentation,
+            legal: score.legal,
+            license: score.license,
+            security: score.security,
+        },
+        failed_checks: checks.difference(&passed_checks).cloned().collect(),
+        passed_checks: passed_checks.into_iter().collect(),
+        rating: project.rating,
+        url: Some(url),
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CMProject {
+    rating: Option<String>,
+    #[serde(default)]
+    repositories: Vec<CMRepository>,
+    score: Option<CMScore>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CMScore {
+    best_practices: Option<f64>,
+    documentation: Option<f64>,
+    global: Option<f64>,
+    legal: Option<f64>,
+    license: Option<f64>,
+    security: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CMRepository {
+    report: Option<CMReport>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CMReport {
+    data: Option<BTreeMap<String, BTreeMap<String, Option<CMCheck>>>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CMCheck {
+    #[serde(default)]
+    passed: bool,
+    exempt: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FOUNDATION: &str = "foundation";
+    const PROJECT: &str = "project";
+
+    /// Create a new cache in a temporary directory for testing purposes (the
+    /// directory is removed when the TempDir returned is dropped).
+    fn setup_cache() -> (Cache, TempDir) {
+        let cache_dir = TempDir::new().unwrap();
+        let cache = Cache::new(&Some(cache_dir.path().to_path_buf())).unwrap();
+        (cache, cache_dir)
+    }
+
+    fn clomonitor_settings(url: String) -> Clomonitor {
+        Clomonitor {
+            foundation: FOUNDATION.to_string(),
+            url: Some(url),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_report_summary_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/projects/foundation/project/report-summary")
+            .with_status(200)
+            .with_body("<svg></svg>")
+            .expect(1)
+            .create_async()
+            .await;
+        let (cache, _cache_dir) = setup_cache();
+        let clomonitor = clomonitor_settings(server.url());
+
+        // The second call must be served from the cache
+        for _ in 0..2 {
+            let report_summary =
+                fetch_report_summary(&cache, reqwest::Client::new(), &clomonitor, PROJECT, 7, false)
+                    .await
+                    .unwrap();
+            assert_eq!(report_summary, Some(b"<svg></svg>".to_vec()));
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_report_summary_not_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/projects/foundation/project/report-summary")
+            .with_status(404)
+            .create_async()
+            .await;
+        let (cache, _cache_dir) = setup_cache();
+        let clomonitor = clomonitor_settings(server.url());
+
+        let report_summary =
+            fetch_report_summary(&cache, reqwest::Client::new(), &clomonitor, PROJECT, 7, false)
+                .await
+                .unwrap();
+        assert_eq!(report_summary, None);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_report_summary_offline() {
+        let (cache, _cache_dir) = setup_cache();
+        let clomonitor = clomonitor_settings("http://localhost:1".to_string());
+
+        let report_summary =
+            fetch_report_summary(&cache, reqwest::Client::new(), &clomonitor, PROJECT, 7, true)
+                .await
+                .unwrap();
+        assert_eq!(report_summary, None);
+    }
+
+    #[tokio::test]
+    async fn fetch_report_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/projects/foundation/project")
+            .with_status(200)
+            .with_body(r#"{"rating": "b", "score": {"global": 75.0}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let (cache, _cache_dir) = setup_cache();
+        let clomonitor = clomonitor_settings(format!("{}/", server.url()));
+
+        // The second call must be served from the cache
+        for _ in 0..2 {
+            let report = fetch_report(&cache, reqwest::Client::new(), &clomonitor, PROJECT, 7, false)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                report.score,
+                ClomonitorScore {
+                    global: 75.0,
+                    ..Default::default()
+                }
+            );
+            assert_eq!(report.rating, Some("b".to_string()));
+            assert_eq!(
+                report.url,
+                Some(format!("{}/projects/foundation/project", server.url()))
+            );
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_report_invalid_cached_report() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/projects/foundation/project")
+            .with_status(200)
+            .with_body(r#"{"rating": "b", "score": {"global": 75.0}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let (cache, _cache_dir) = setup_cache();
+        cache.write("clomonitor_foundation_project.json", b"invalid").unwrap();
+        let clomonitor = clomonitor_settings(server.url());
+
+        let report = fetch_report(&cache, reqwest::Client::new(), &clomonitor, PROJECT, 7, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.rating, Some("b".to_string()));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_report_unexpected_status() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/projects/foundation/project")
+            .with_status(500)
+            .create_async()
+            .await;
+        let (cache, _cache_dir) = setup_cache();
+        let clomonitor = clomonitor_settings(server.url());
+
+        let result = fetch_report(&cache, reqwest::Client::new(), &clomonitor, PROJECT, 7, false).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected status code getting clomonitor project: 500 Internal Server Error"
+        );
+    }
+
+    #[test]
+    fn new_report_from_project() {
+        let project: CMProject = serde_json::from_str(
+            r#"{
+                "name": "project",
+                "rating": "a",
+                "score": {
+                    "global": 85.5,
+                    "documentation": 90,
+                    "license": 100,
+                    "security": 70.5
+                },
+                "repositories": [
+                    {
+                        "name": "repo1",
+                        "report": {
+                            "data": {
+                                "documentation": {
+                                    "adopters": { "passed": true },
+                                    "roadmap": { "passed": false }
+                                },
+                                "security": {
+                                    "binary_artifacts": { "passed": false, "exempt": true },
+                                    "signed_releases": null
+                                }
+                            }
+                        }
+                    },
+                    {
+                        "name": "repo2",
+                        "report": {
+                            "data": {
+                                "documentation": {
+                                    "adopters": { "passed": false },
+                                    "roadmap": { "passed": false }
+                                },
+                                "license": {
+                                    "license_approved": { "passed": true }
+                                }
+                            }
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let url = "https://clomonitor.io/projects/cncf/project".to_string();
+        let report = new_report_from(project, url.clone());
+        assert_eq!(
+            report,
+            ClomonitorReport {
+                generated_at: report.generated_at,
+                score: ClomonitorScore {
+                    global: 85.5,
+                    best_practices: None,
+                    documentation: Some(90.0),
+                    legal: None,
+                    license: Some(100.0),
+                    security: Some(70.5),
+                },
+                failed_checks: vec!["documentation/roadmap".to_string()],
+                passed_checks: vec![
+                    "documentation/adopters".to_string(),
+                    "license/license_approved".to_string(),
+                    "security/binary_artifacts".to_string(),
+                ],
+                rating: Some("a".to_string()),
+                url: Some(url),
+            }
+        );
+    }
+}
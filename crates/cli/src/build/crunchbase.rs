@@ -2,12 +2,15 @@
 //! from Crunchbase for each of the landscape items (when applicable), as well
 //! as the functionality used to collect that information.
 
-use super::{cache::Cache, LandscapeData};
+use super::{
+    cache::{Cache, CachedCollector, CachedData, CachedSource},
+    issues::{IssueKind, Issues},
+    organizations::OrganizationsProvider,
+};
 use anyhow::{bail, format_err, Result};
 use async_trait::async_trait;
-use chrono::{Datelike, NaiveDate, Utc};
-use futures::stream::{self, StreamExt};
-use landscape2_core::data::{Acquisition, CrunchbaseData, FundingRound, Organization};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use landscape2_core::data::{Acquisition, CrunchbaseData, FundingRound, Organization, OrganizationRef};
 use lazy_static::lazy_static;
 use leaky_bucket::RateLimiter;
 #[cfg(test)]
@@ -15,14 +18,18 @@ use mockall::automock;
 use regex::Regex;
 use reqwest::{header, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, env, sync::Arc, time::Duration};
+use std::{env, sync::Arc, time::Duration};
 use tracing::{debug, instrument, warn};
 
-/// File used to cache data collected from Crunchbase.
-const CRUNCHBASE_CACHE_FILE: &str = "crunchbase.json";
+/// Name used to track the Crunchbase data in the cache stats.
+const CRUNCHBASE_CACHE_SOURCE: &str = "crunchbase";
 
-/// How long the Crunchbase data in the cache is valid (in days).
-const CRUNCHBASE_CACHE_TTL: i64 = 7;
+/// Namespace used to cache data collected from Crunchbase (one entry per
+/// organization).
+pub(crate) const CRUNCHBASE_CACHE_NAMESPACE: &str = "crunchbase";
+
+/// Default for how long the Crunchbase data in the cache is valid (in days).
+pub(crate) const CRUNCHBASE_CACHE_TTL: i64 = 7;
 
 /// Environment variable containing the Crunchbase API key.
 const CRUNCHBASE_API_KEY: &str = "CRUNCHBASE_API_KEY";
@@ -30,92 +37,100 @@ const CRUNCHBASE_API_KEY: &str = "CRUNCHBASE_API_KEY";
 /// Interval for the rate limiter used when sending requests to the CB API.
 const CRUNCHBASE_RATE_LIMITER_INTERVAL: Duration = Duration::from_millis(300);
 
-/// Collect Crunchbase data for each of the items orgs in the landscape,
+/// Organizations data provider backed by Crunchbase. Only organizations
+/// identified by their Crunchbase url are supported.
+pub(crate) struct CrunchbaseProvider<'a> {
+    pub cache: &'a Cache,
+    pub issues: &'a Issues,
+    pub ttl: i64,
+    pub refresh_percentage: Option<u8>,
+    pub offline: bool,
+}
+
+#[async_trait]
+impl OrganizationsProvider for CrunchbaseProvider<'_> {
+    /// [OrganizationsProvider::name]
+    fn name(&self) -> &'static str {
+        "crunchbase"
+    }
+
+    /// [OrganizationsProvider::collect]
+    async fn collect(&self, keys: &[String]) -> Result<CrunchbaseData> {
+        let urls = keys.iter().filter(|key| CRUNCHBASE_URL.is_match(key)).cloned().collect();
+        collect_crunchbase_data(
+            self.cache,
+            urls,
+            self.issues,
+            self.ttl,
+            self.refresh_percentage,
+            self.offline,
+        )
+        .await
+    }
+}
+
+/// Collect Crunchbase data for each of the organizations urls provided,
 /// reusing cached data whenever possible.
 #[instrument(skip_all, err)]
-pub(crate) async fn collect_crunchbase_data(
+async fn collect_crunchbase_data(
     cache: &Cache,
-    landscape_data: &LandscapeData,
+    mut urls: Vec<String>,
+    issues: &Issues,
+    ttl: i64,
+    refresh_percentage: Option<u8>,
+    offline: bool,
 ) -> Result<CrunchbaseData> {
-    debug!("collecting organizations information from crunchbase (this may take a while)");
-
-    // Read cached data (if available)
-    let mut cached_data: Option<CrunchbaseData> = None;
-    match cache.read(CRUNCHBASE_CACHE_FILE) {
-        Ok(Some((_, json_data))) => match serde_json::from_slice(&json_data) {
-            Ok(crunchbase_data) => cached_data = Some(crunchbase_data),
-            Err(err) => warn!("error parsing crunchbase cache file: {err:?}"),
-        },
-        Ok(None) => {}
-        Err(err) => warn!("error reading crunchbase cache file: {err:?}"),
+    if urls.is_empty() {
+        return Ok(CrunchbaseData::new());
     }
+    debug!("collecting organizations information from crunchbase (this may take a while)");
 
-    // Setup Crunchbase API client if an api key was provided
+    // Setup Crunchbase API client if an api key was provided (in offline mode
+    // only cached data is used)
     let api_key = match env::var(CRUNCHBASE_API_KEY) {
+        _ if offline => None,
         Ok(api_key) if !api_key.is_empty() => Some(api_key),
         Ok(_) | Err(_) => None,
     };
     let cb: Option<DynCB> = if let Some(api_key) = api_key {
         Some(Arc::new(CBApi::new(&api_key)?))
+    } else if offline {
+        debug!("offline mode enabled: only cached data will be used");
+        None
     } else {
         warn!("crunchbase api key not provided: no information will be collected from crunchbase");
         None
     };
 
-    // Collect items Crunchbase urls
-    let mut urls = vec![];
-    for item in &landscape_data.items {
-        if let Some(url) = &item.crunchbase_url {
-            urls.push(url);
-        }
-    }
+    // Prepare organizations Crunchbase urls
     urls.sort();
     urls.dedup();
 
+    // Read cached data (if available) and select the expired entries that
+    // will be refreshed in this build
+    let source = CachedSource {
+        name: CRUNCHBASE_CACHE_SOURCE,
+        namespace: CRUNCHBASE_CACHE_NAMESPACE,
+        issue_kind: IssueKind::Crunchbase,
+        ttl,
+        refresh_percentage,
+        offline,
+    };
+    let collector = CachedCollector::new(cache, issues, source, &urls, |_, _| true);
+
     // Collect information from Crunchbase, reusing cached data when available
     let limiter = RateLimiter::builder().initial(1).interval(CRUNCHBASE_RATE_LIMITER_INTERVAL).build();
-    let crunchbase_data: CrunchbaseData = stream::iter(urls)
-        .map(|url| async {
-            let url = url.clone();
-
-            // Use cached data when available if it hasn't expired yet
-            if let Some(cached_org) = cached_data.as_ref().and_then(|cache| {
-                cache.get(&url).and_then(|org| {
-                    if org.generated_at + chrono::Duration::days(CRUNCHBASE_CACHE_TTL) > Utc::now() {
-                        Some(org)
-                    } else {
-                        None
-                    }
-                })
-            }) {
-                (url, Ok(cached_org.clone()))
-            }
-            // Otherwise we pull it from Crunchbase if a key was provided
-            else if let Some(cb) = cb.clone() {
-                limiter.acquire_one().await;
-                (url.clone(), collect_organization_data(cb, &url).await)
-            } else {
-                (url.clone(), Err(format_err!("no api key provided")))
-            }
-        })
-        .buffer_unordered(1)
-        .collect::<BTreeMap<String, Result<Organization>>>()
-        .await
-        .into_iter()
-        .filter_map(|(url, result)| {
-            if let Ok(crunchbase_data) = result {
-                Some((url, crunchbase_data))
-            } else {
-                None
-            }
+    let (limiter, cb) = (&limiter, &cb);
+    let urls = urls.into_iter().map(|url| (url, ())).collect();
+    let crunchbase_data: CrunchbaseData = collector
+        .collect(urls, 1, |url, ()| async move {
+            let Some(cb) = cb.clone() else {
+                bail!("no api key provided");
+            };
+            limiter.acquire_one().await;
+            collect_organization_data(cb, &url).await
         })
-        .collect();
-
-    // Write data (in json format) to cache
-    cache.write(
-        CRUNCHBASE_CACHE_FILE,
-        &serde_json::to_vec_pretty(&crunchbase_data)?,
-    )?;
+        .await;
 
     debug!("done!");
     Ok(crunchbase_data)
@@ -173,9 +188,18 @@ async fn collect_organization_data(cb: DynCB, cb_url: &str) -> Result<Organizati
         None => (None, None),
     };
 
+    // Prepare parent organization
+    let parent = cb_org
+        .cards
+        .parent_organization
+        .and_then(|parents| parents.into_iter().next())
+        .and_then(|parent| parent.identifier)
+        .and_then(new_organization_ref_from);
+
     // Prepare organization instance using the information collected
     Ok(Organization {
         generated_at: Utc::now(),
+        acquired_by: cb_org.properties.acquirer_identifier.and_then(new_organization_ref_from),
         acquisitions,
         city: get_location_value(&cb_org.cards.headquarters_address, "city"),
         company_type: cb_org.properties.company_type,
@@ -190,13 +214,34 @@ async fn collect_organization_data(cb: DynCB, cb_url: &str) -> Result<Organizati
         name: cb_org.properties.name,
         num_employees_max,
         num_employees_min,
+        overridden_fields: None,
+        parent,
         region: get_location_value(&cb_org.cards.headquarters_address, "region"),
+        stale: None,
         stock_exchange: cb_org.properties.stock_exchange_symbol,
         ticker: cb_org.properties.stock_symbol.and_then(|v| v.value),
         twitter_url: cb_org.properties.twitter.and_then(|v| v.value),
+        ultimate_parent: None,
     })
 }
 
+impl CachedData for Organization {
+    /// [CachedData::generated_at]
+    fn generated_at(&self) -> DateTime<Utc> {
+        self.generated_at
+    }
+
+    /// [CachedData::set_generated_at]
+    fn set_generated_at(&mut self, generated_at: DateTime<Utc>) {
+        self.generated_at = generated_at;
+    }
+
+    /// [CachedData::set_stale]
+    fn set_stale(&mut self, stale: bool) {
+        self.stale = stale.then_some(true);
+    }
+}
+
 /// Crunchbase API base url.
 const CRUNCHBASE_API_URL: &str = "https://api.crunchbase.com/api/v4";
 
@@ -238,6 +283,7 @@ impl CB for CBApi {
         let cards = &[
             "acquiree_acquisitions",
             "headquarters_address",
+            "parent_organization",
             "raised_funding_rounds",
         ]
         .join(",");
@@ -253,6 +299,7 @@ impl CB for CBApi {
             "stock_exchange_symbol",
             "categories",
             "company_type",
+            "acquirer_identifier",
         ]
         .join(",");
         let url = format!(
@@ -275,6 +322,7 @@ struct CBOrganizationEntity {
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 struct CBOrganization {
+    acquirer_identifier: Option<CBOrganizationIdentifier>,
     categories: Option<Vec<CBEntityIdentifier>>,
     company_type: Option<String>,
     funding_total: Option<CBFundingTotal>,
@@ -307,22 +355,28 @@ struct CBValue {
 struct CBCards {
     acquiree_acquisitions: Option<Vec<CBAcquisition>>,
     headquarters_address: Option<Vec<CBAddress>>,
+    parent_organization: Option<Vec<CBParentOrganization>>,
     raised_funding_rounds: Option<Vec<CBFundingRound>>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 struct CBAcquisition {
-    acquiree_identifier: Option<CBAcquireeIdentifier>,
+    acquiree_identifier: Option<CBOrganizationIdentifier>,
     announced_on: Option<CBAcquisitionAnnouncedOn>,
     price: Option<CBAcquisitionPrice>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
-struct CBAcquireeIdentifier {
+struct CBOrganizationIdentifier {
     permalink: Option<String>,
     value: Option<String>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CBParentOrganization {
+    identifier: Option<CBOrganizationIdentifier>,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 struct CBAcquisitionAnnouncedOn {
     value: Option<NaiveDate>,
@@ -393,6 +447,18 @@ fn new_acquisition_from(cba: CBAcquisition) -> Acquisition {
     }
 }
 
+/// Create a new OrganizationRef instance from the Crunchbase organization
+/// identifier provided.
+fn new_organization_ref_from(identifier: CBOrganizationIdentifier) -> Option<OrganizationRef> {
+    Some(OrganizationRef {
+        name: identifier.value?,
+        crunchbase_url: identifier
+            .permalink
+            .map(|permalink| format!("https://www.crunchbase.com/organization/{permalink}")),
+        organization_id: None,
+    })
+}
+
 /// Create a new FundingRound instance from the Crunchbase data provided.
 fn new_funding_round_from(cbfr: CBFundingRound) -> FundingRound {
     FundingRound {
@@ -52,6 +52,12 @@ struct Item {
     github_start_commit_link: Option<String>,
     github_contributors_count: Option<usize>,
     github_contributors_link: Option<String>,
+    github_forks: Option<i64>,
+    github_watchers: Option<i64>,
+    github_open_issues: Option<i64>,
+    github_open_pull_requests: Option<i64>,
+    github_pull_requests_median_merge_time: Option<i64>,
+    github_releases_last_year: Option<usize>,
     accepted: Option<String>,
     incubation: Option<String>,
     graduated: Option<String>,
@@ -64,9 +70,11 @@ struct Item {
     chat_channel: Option<String>,
     last_audit_date: Option<String>,
     last_audit_url: Option<String>,
+    crunchbase_ultimate_parent: Option<String>,
 }
 
 impl From<&data::Item> for Item {
+    #[allow(clippy::too_many_lines)]
     fn from(di: &data::Item) -> Self {
         // Helper closure to format dates
         let fmt_date = |date: NaiveDate| date.format(DATE_FORMAT).to_string();
@@ -108,6 +116,7 @@ impl From<&data::Item> for Item {
             item.crunchbase_region.clone_from(&organization.region);
             item.crunchbase_ticker.clone_from(&organization.ticker);
             item.crunchbase_twitter.clone_from(&organization.twitter_url);
+            item.crunchbase_ultimate_parent = organization.ultimate_parent.as_ref().map(|p| p.name.clone());
             item.funding = organization.funding;
             item.organization.clone_from(&organization.name);
         }
@@ -137,9 +146,15 @@ impl From<&data::Item> for Item {
                 item.github_contributors_count = Some(gh_data.contributors.count);
                 item.github_contributors_link = Some(gh_data.contributors.url.clone());
                 item.github_description = Some(gh_data.description.clone());
+                item.github_forks = gh_data.forks;
                 item.github_latest_commit_link = Some(gh_data.latest_commit.url.clone());
+                item.github_open_issues = gh_data.open_issues;
+                item.github_open_pull_requests = gh_data.open_pull_requests;
+                item.github_pull_requests_median_merge_time = gh_data.pull_requests_median_merge_time;
+                item.github_releases_last_year = gh_data.releases_last_year;
                 item.github_repo = Some(gh_data.url.clone());
                 item.github_stars = Some(gh_data.stars);
+                item.github_watchers = gh_data.watchers;
 
                 if let Some(commit) = &gh_data.first_commit {
                     item.github_start_commit_link = Some(commit.url.clone());
@@ -0,0 +1,312 @@
+//! This module defines the functionality used to collect information from
+//! Bitbucket Cloud.
+
+use super::{
+    parse_repo_url_pattern, participation_stats, participation_stats_since, Forge, Project,
+    CONTRIBUTORS_MAX_COMMITS, PARTICIPATION_STATS_MAX_COMMITS,
+};
+use crate::build::github::parse_api_url;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use landscape2_core::data::{Commit, Release};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use regex::Regex;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use tracing::instrument;
+
+/// Namespace used to cache data collected from Bitbucket (one entry per
+/// repository).
+pub(crate) const BITBUCKET_CACHE_NAMESPACE: &str = "bitbucket";
+
+/// Default for how long the Bitbucket data in the cache is valid (in days).
+pub(crate) const BITBUCKET_CACHE_TTL: i64 = 7;
+
+/// Environment variable containing a comma separated list of Bitbucket
+/// access tokens.
+pub(super) const BITBUCKET_TOKENS: &str = "BITBUCKET_TOKENS";
+
+/// Default Bitbucket API base url.
+const BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Default Bitbucket repository url regular expression.
+const BITBUCKET_REPO_URL_PATTERN: &str = r"^https://bitbucket\.org/(?P<project>[^/]+/[^/]+?)/?$";
+
+/// Maximum number of items per page (maximum allowed by Bitbucket).
+const BITBUCKET_PAGE_SIZE: usize = 100;
+
+/// Bitbucket collector options.
+#[derive(Args, Clone)]
+pub struct BitbucketOptions {
+    /// Bitbucket API base url.
+    #[arg(
+        id = "bitbucket_api_url",
+        long = "bitbucket-api-url",
+        value_name = "URL",
+        default_value = BITBUCKET_API_URL,
+        value_parser = parse_api_url
+    )]
+    pub api_url: String,
+
+    /// Regular expression used to identify the Bitbucket repositories urls. It
+    /// must include the project named group (workspace/repo).
+    #[arg(
+        id = "bitbucket_repo_url_pattern",
+        long = "bitbucket-repo-url-pattern",
+        value_name = "REGEX",
+        default_value = BITBUCKET_REPO_URL_PATTERN,
+        value_parser = parse_repo_url_pattern
+    )]
+    pub repo_url_pattern: Regex,
+}
+
+/// Forge implementation backed by the Bitbucket Cloud API.
+pub(super) struct BitbucketApi {
+    api_url: String,
+    http_client: reqwest::Client,
+}
+
+impl BitbucketApi {
+    /// Create a new BitbucketApi instance.
+    pub(super) fn new(token: &str, api_url: &str) -> Result<Self> {
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}"))?,
+        );
+        let http_client =
+            reqwest::Client::builder().user_agent(user_agent).default_headers(headers).build()?;
+
+        Ok(Self {
+            api_url: api_url.to_string(),
+            http_client,
+        })
+    }
+
+    /// Get the commits of the reference provided (page of results requested,
+    /// newest first). The url of the next page is returned when available.
+    async fn get_commits(&self, url: &str) -> Result<(Vec<BitbucketCommit>, Option<String>)> {
+        let page: BitbucketPage<BitbucketCommit> =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok((page.values, page.next))
+    }
+
+    /// Get the url of the first page of commits of the reference provided.
+    fn commits_url(&self, project: &str, ref_: &str, page_size: usize) -> String {
+        format!(
+            "{}/repositories/{project}/commits/{}?pagelen={page_size}",
+            self.api_url,
+            utf8_percent_encode(ref_, NON_ALPHANUMERIC)
+        )
+    }
+}
+
+#[async_trait]
+impl Forge for BitbucketApi {
+    /// [Forge::get_contributors_count]
+    ///
+    /// Bitbucket does not provide the number of contributors of a repository,
+    /// so it is approximated by counting the distinct authors of the most
+    /// recent commits.
+    #[instrument(skip(self), err)]
+    async fn get_contributors_count(&self, project: &str, ref_: &str) -> Result<usize> {
+        let mut authors = HashSet::new();
+        let mut next_url = Some(self.commits_url(project, ref_, BITBUCKET_PAGE_SIZE));
+        let mut commits_count = 0;
+        while let Some(url) = next_url {
+            let (commits, next) = self.get_commits(&url).await?;
+            commits_count += commits.len();
+            authors.extend(commits.into_iter().map(|commit| commit.author.raw));
+            if commits_count >= CONTRIBUTORS_MAX_COMMITS {
+                break;
+            }
+            next_url = next;
+        }
+        Ok(authors.len())
+    }
+
+    /// [Forge::get_first_commit]
+    ///
+    /// Bitbucket only allows listing the commits starting from the most recent
+    /// one, so the first commit is not collected.
+    async fn get_first_commit(&self, _project: &str, _ref: &str) -> Result<Option<Commit>> {
+        Ok(None)
+    }
+
+    /// [Forge::get_languages]
+    ///
+    /// Bitbucket does not provide the languages used in a repository.
+    async fn get_languages(&self, _project: &str) -> Result<Option<BTreeMap<String, i64>>> {
+        Ok(None)
+    }
+
+    /// [Forge::get_latest_commit]
+    #[instrument(skip(self), err)]
+    async fn get_latest_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>> {
+        let (commits, _) = self.get_commits(&self.commits_url(project, ref_, 1)).await?;
+        Ok(commits.into_iter().next().map(Commit::from))
+    }
+
+    /// [Forge::get_latest_release]
+    ///
+    /// Bitbucket does not support releases.
+    async fn get_latest_release(&self, _project: &str) -> Result<Option<Release>> {
+        Ok(None)
+    }
+
+    /// [Forge::get_participation_stats]
+    #[instrument(skip(self), err)]
+    async fn get_participation_stats(&self, project: &str, ref_: &str) -> Result<Vec<i64>> {
+        // Get all commits of the last year (commits are returned newest first)
+        let now = Utc::now();
+        let since = participation_stats_since(now);
+        let mut commits_dates = vec![];
+        let mut next_url = Some(self.commits_url(project, ref_, BITBUCKET_PAGE_SIZE));
+        let mut commits_count = 0;
+        while let Some(url) = next_url {
+            let (commits, next) = self.get_commits(&url).await?;
+            let page_commits_count = commits.len();
+            commits_count += page_commits_count;
+            let page_commits_dates: Vec<DateTime<Utc>> =
+                commits.into_iter().map(|commit| commit.date).filter(|date| *date >= since).collect();
+
+            // Stop when the page includes commits older than a year
+            let done = page_commits_dates.len() < page_commits_count;
+            commits_dates.extend(page_commits_dates);
+            if done || commits_count >= PARTICIPATION_STATS_MAX_COMMITS {
+                break;
+            }
+            next_url = next;
+        }
+
+        Ok(participation_stats(now, commits_dates.into_iter()))
+    }
+
+    /// [Forge::get_project]
+    #[instrument(skip(self), err)]
+    async fn get_project(&self, project: &str) -> Result<Project> {
+        // Repository
+        let url = format!("{}/repositories/{project}", self.api_url);
+        let repo: BitbucketRepository =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+
+        // Watchers (Bitbucket does not support stars, watchers are used instead)
+        let url = format!("{}/repositories/{project}/watchers?pagelen=1", self.api_url);
+        let watchers: BitbucketPage<serde_json::Value> =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+
+        Ok(Project {
+            contributors_url: format!("{}/commits", repo.links.html.href),
+            default_branch: repo.mainbranch.map(|branch| branch.name),
+            description: Some(repo.description).filter(|description| !description.is_empty()),
+            stars: watchers.size.unwrap_or_default(),
+            url: repo.links.html.href,
+            ..Default::default()
+        })
+    }
+}
+
+/// Page of results returned by the Bitbucket API.
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketPage<T> {
+    next: Option<String>,
+    size: Option<i64>,
+    values: Vec<T>,
+}
+
+/// Repository information returned by the Bitbucket API.
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketRepository {
+    #[serde(default)]
+    description: String,
+    links: BitbucketLinks,
+    mainbranch: Option<BitbucketBranch>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketBranch {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketLinks {
+    html: BitbucketLink,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketLink {
+    href: String,
+}
+
+/// Commit information returned by the Bitbucket API.
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketCommit {
+    author: BitbucketCommitAuthor,
+    date: DateTime<Utc>,
+    links: BitbucketLinks,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketCommitAuthor {
+    raw: String,
+}
+
+impl From<BitbucketCommit> for Commit {
+    fn from(value: BitbucketCommit) -> Self {
+        Commit {
+            ts: Some(value.date),
+            url: value.links.html.href,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commits_url_encodes_ref() {
+        let bitbucket = BitbucketApi::new("token", BITBUCKET_API_URL).unwrap();
+        assert_eq!(
+            bitbucket.commits_url("workspace/repo", "feature/a b", 10),
+            "https://api.bitbucket.org/2.0/repositories/workspace/repo/commits/feature%2Fa%20b?pagelen=10"
+        );
+    }
+
+    #[test]
+    fn commits_page_from_bitbucket_response() {
+        let page: BitbucketPage<BitbucketCommit> = serde_json::from_str(
+            r#"{
+                "pagelen": 1,
+                "next": "https://api.bitbucket.org/2.0/repositories/workspace/repo/commits/main?page=2",
+                "values": [
+                    {
+                        "hash": "abc",
+                        "date": "2024-01-02T01:04:05+00:00",
+                        "author": { "raw": "Author <author@example.com>" },
+                        "links": {
+                            "html": { "href": "https://bitbucket.org/workspace/repo/commits/abc" }
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            page.next.as_deref(),
+            Some("https://api.bitbucket.org/2.0/repositories/workspace/repo/commits/main?page=2")
+        );
+        assert_eq!(page.values[0].author.raw, "Author <author@example.com>");
+        assert_eq!(
+            Commit::from(page.values[0].clone()),
+            Commit {
+                ts: Some(DateTime::parse_from_rfc3339("2024-01-02T01:04:05Z").unwrap().into()),
+                url: "https://bitbucket.org/workspace/repo/commits/abc".to_string(),
+            }
+        );
+    }
+}
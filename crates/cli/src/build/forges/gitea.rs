@@ -0,0 +1,379 @@
+//! This module defines the functionality used to collect information from
+//! Gitea and Forgejo instances (i.e. Codeberg).
+
+use super::{
+    header_value, parse_repo_url_pattern, participation_stats, participation_stats_since, Forge, Project,
+    CONTRIBUTORS_MAX_COMMITS, PARTICIPATION_STATS_MAX_COMMITS,
+};
+use crate::build::github::parse_api_url;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use landscape2_core::data::{Commit, Release};
+use regex::Regex;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use tracing::instrument;
+
+/// Namespace used to cache data collected from Gitea (one entry per
+/// repository).
+pub(crate) const GITEA_CACHE_NAMESPACE: &str = "gitea";
+
+/// Default for how long the Gitea data in the cache is valid (in days).
+pub(crate) const GITEA_CACHE_TTL: i64 = 7;
+
+/// Environment variable containing a comma separated list of Gitea tokens.
+pub(super) const GITEA_TOKENS: &str = "GITEA_TOKENS";
+
+/// Default Gitea API base url.
+const GITEA_API_URL: &str = "https://codeberg.org/api/v1";
+
+/// Default Gitea repository url regular expression.
+const GITEA_REPO_URL_PATTERN: &str = r"^https://codeberg\.org/(?P<project>[^/]+/[^/]+?)/?$";
+
+/// Maximum number of items per page (default maximum allowed by Gitea).
+const GITEA_PAGE_SIZE: usize = 50;
+
+/// Gitea/Forgejo collector options.
+#[derive(Args, Clone)]
+pub struct GiteaOptions {
+    /// Gitea/Forgejo API base url. It can be used to collect data from a
+    /// self-hosted instance (i.e. https://gitea.example.com/api/v1).
+    #[arg(
+        id = "gitea_api_url",
+        long = "gitea-api-url",
+        value_name = "URL",
+        default_value = GITEA_API_URL,
+        value_parser = parse_api_url
+    )]
+    pub api_url: String,
+
+    /// Regular expression used to identify the Gitea/Forgejo repositories
+    /// urls. It must include the project named group (owner/repo).
+    #[arg(
+        id = "gitea_repo_url_pattern",
+        long = "gitea-repo-url-pattern",
+        value_name = "REGEX",
+        default_value = GITEA_REPO_URL_PATTERN,
+        value_parser = parse_repo_url_pattern
+    )]
+    pub repo_url_pattern: Regex,
+}
+
+/// Forge implementation backed by the Gitea API (also supported by Forgejo).
+pub(super) struct GiteaApi {
+    api_url: String,
+    http_client: reqwest::Client,
+}
+
+impl GiteaApi {
+    /// Create a new GiteaApi instance.
+    pub(super) fn new(token: &str, api_url: &str) -> Result<Self> {
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("token {token}"))?,
+        );
+        let http_client =
+            reqwest::Client::builder().user_agent(user_agent).default_headers(headers).build()?;
+
+        Ok(Self {
+            api_url: api_url.to_string(),
+            http_client,
+        })
+    }
+
+    /// Get the commits of the reference provided (page of results requested).
+    async fn get_commits(
+        &self,
+        project: &str,
+        ref_: &str,
+        query: &[(&str, &str)],
+    ) -> Result<(Vec<GiteaCommit>, HeaderMap)> {
+        let url = format!("{}/repos/{project}/commits", self.api_url);
+        let response = self
+            .http_client
+            .get(url)
+            .query(&[
+                ("sha", ref_),
+                ("stat", "false"),
+                ("verification", "false"),
+                ("files", "false"),
+            ])
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?;
+        let headers = response.headers().clone();
+        Ok((response.json().await?, headers))
+    }
+}
+
+#[async_trait]
+impl Forge for GiteaApi {
+    /// [Forge::get_contributors_count]
+    ///
+    /// Gitea does not provide the number of contributors of a repository, so
+    /// it is approximated by counting the distinct authors of the most recent
+    /// commits.
+    #[instrument(skip(self), err)]
+    async fn get_contributors_count(&self, project: &str, ref_: &str) -> Result<usize> {
+        let mut authors = HashSet::new();
+        let limit = GITEA_PAGE_SIZE.to_string();
+        for page in 1..=CONTRIBUTORS_MAX_COMMITS.div_ceil(GITEA_PAGE_SIZE) {
+            let page = page.to_string();
+            let (commits, headers) =
+                self.get_commits(project, ref_, &[("limit", &limit), ("page", &page)]).await?;
+            authors.extend(commits.into_iter().map(|commit| commit.commit.author.email));
+            if header_value::<bool>(&headers, "x-hasmore") != Some(true) {
+                break;
+            }
+        }
+        Ok(authors.len())
+    }
+
+    /// [Forge::get_first_commit]
+    #[instrument(skip(self), err)]
+    async fn get_first_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>> {
+        // Get total number of commits
+        let (_, headers) = self.get_commits(project, ref_, &[("limit", "1")]).await?;
+        let Some(total) = header_value::<usize>(&headers, "x-total-count") else {
+            return Ok(None);
+        };
+
+        // Get first project commit and return it if found
+        let page = total.to_string();
+        let (mut commits, _) = self.get_commits(project, ref_, &[("limit", "1"), ("page", &page)]).await?;
+        Ok(commits.pop().map(Commit::from))
+    }
+
+    /// [Forge::get_languages]
+    #[instrument(skip(self), err)]
+    async fn get_languages(&self, project: &str) -> Result<Option<BTreeMap<String, i64>>> {
+        let url = format!("{}/repos/{project}/languages", self.api_url);
+        let languages = self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok(Some(languages))
+    }
+
+    /// [Forge::get_latest_commit]
+    #[instrument(skip(self), err)]
+    async fn get_latest_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>> {
+        let (mut commits, _) = self.get_commits(project, ref_, &[("limit", "1")]).await?;
+        Ok(commits.pop().map(Commit::from))
+    }
+
+    /// [Forge::get_latest_release]
+    #[instrument(skip(self), err)]
+    async fn get_latest_release(&self, project: &str) -> Result<Option<Release>> {
+        let url = format!(
+            "{}/repos/{project}/releases?draft=false&pre-release=false&limit=1",
+            self.api_url
+        );
+        let mut releases: Vec<GiteaRelease> =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok(releases.pop().map(|release| Release {
+            ts: release.published_at,
+            url: release.html_url,
+        }))
+    }
+
+    /// [Forge::get_participation_stats]
+    #[instrument(skip(self), err)]
+    async fn get_participation_stats(&self, project: &str, ref_: &str) -> Result<Vec<i64>> {
+        // Get all commits of the last year
+        let now = Utc::now();
+        let since = participation_stats_since(now).to_rfc3339();
+        let limit = GITEA_PAGE_SIZE.to_string();
+        let mut commits = vec![];
+        for page in 1..=PARTICIPATION_STATS_MAX_COMMITS.div_ceil(GITEA_PAGE_SIZE) {
+            let page = page.to_string();
+            let query = [("since", since.as_str()), ("limit", &limit), ("page", &page)];
+            let (page_commits, headers) = self.get_commits(project, ref_, &query).await?;
+            commits.extend(page_commits);
+            if header_value::<bool>(&headers, "x-hasmore") != Some(true) {
+                break;
+            }
+        }
+
+        Ok(participation_stats(
+            now,
+            commits.iter().map(|commit| commit.commit.author.date),
+        ))
+    }
+
+    /// [Forge::get_project]
+    #[instrument(skip(self), err)]
+    async fn get_project(&self, project: &str) -> Result<Project> {
+        let url = format!("{}/repos/{project}", self.api_url);
+        let repo: GiteaRepository =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok(repo.into())
+    }
+}
+
+/// Repository information returned by the Gitea API.
+#[derive(Debug, Clone, Deserialize)]
+struct GiteaRepository {
+    #[serde(default)]
+    archived: bool,
+    default_branch: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    empty: bool,
+    forks_count: i64,
+    html_url: String,
+    #[serde(default)]
+    licenses: Vec<String>,
+    open_issues_count: i64,
+    open_pr_counter: i64,
+    stars_count: i64,
+    #[serde(default)]
+    topics: Vec<String>,
+    watchers_count: i64,
+}
+
+impl From<GiteaRepository> for Project {
+    fn from(value: GiteaRepository) -> Self {
+        Project {
+            archived: value.archived,
+            contributors_url: format!("{}/activity/contributors", value.html_url),
+            default_branch: value.default_branch.filter(|_| !value.empty),
+            description: value.description.filter(|description| !description.is_empty()),
+            forks: Some(value.forks_count),
+            license: value.licenses.into_iter().next(),
+            open_issues: Some(value.open_issues_count),
+            open_pull_requests: Some(value.open_pr_counter),
+            stars: value.stars_count,
+            topics: value.topics,
+            url: value.html_url,
+            watchers: Some(value.watchers_count),
+        }
+    }
+}
+
+/// Commit information returned by the Gitea API.
+#[derive(Debug, Clone, Deserialize)]
+struct GiteaCommit {
+    commit: GiteaCommitDetails,
+    html_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GiteaCommitDetails {
+    author: GiteaCommitAuthor,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GiteaCommitAuthor {
+    date: DateTime<Utc>,
+    email: String,
+}
+
+impl From<GiteaCommit> for Commit {
+    fn from(value: GiteaCommit) -> Self {
+        Commit {
+            ts: Some(value.commit.author.date),
+            url: value.html_url,
+        }
+    }
+}
+
+/// Release information returned by the Gitea API.
+#[derive(Debug, Clone, Deserialize)]
+struct GiteaRelease {
+    html_url: String,
+    published_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_from_gitea_repository() {
+        let repo: GiteaRepository = serde_json::from_str(
+            r#"{
+                "archived": true,
+                "default_branch": "main",
+                "description": "description",
+                "empty": false,
+                "forks_count": 5,
+                "html_url": "https://codeberg.org/owner/repo",
+                "licenses": ["MIT"],
+                "open_issues_count": 8,
+                "open_pr_counter": 2,
+                "stars_count": 100,
+                "topics": ["topic1"],
+                "watchers_count": 3
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Project::from(repo),
+            Project {
+                archived: true,
+                contributors_url: "https://codeberg.org/owner/repo/activity/contributors".to_string(),
+                default_branch: Some("main".to_string()),
+                description: Some("description".to_string()),
+                forks: Some(5),
+                license: Some("MIT".to_string()),
+                open_issues: Some(8),
+                open_pull_requests: Some(2),
+                stars: 100,
+                topics: vec!["topic1".to_string()],
+                url: "https://codeberg.org/owner/repo".to_string(),
+                watchers: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn project_from_empty_gitea_repository() {
+        let repo: GiteaRepository = serde_json::from_str(
+            r#"{
+                "default_branch": "main",
+                "description": "",
+                "empty": true,
+                "forks_count": 0,
+                "html_url": "https://codeberg.org/owner/repo",
+                "open_issues_count": 0,
+                "open_pr_counter": 0,
+                "stars_count": 0,
+                "watchers_count": 0
+            }"#,
+        )
+        .unwrap();
+        let project = Project::from(repo);
+        assert_eq!(project.default_branch, None);
+        assert_eq!(project.description, None);
+        assert_eq!(project.license, None);
+    }
+
+    #[test]
+    fn commit_from_gitea_commit() {
+        let commit: GiteaCommit = serde_json::from_str(
+            r#"{
+                "sha": "abc",
+                "html_url": "https://codeberg.org/owner/repo/commit/abc",
+                "commit": {
+                    "author": {
+                        "name": "author",
+                        "email": "author@example.com",
+                        "date": "2024-01-02T02:04:05+01:00"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Commit::from(commit),
+            Commit {
+                ts: Some(DateTime::parse_from_rfc3339("2024-01-02T01:04:05Z").unwrap().into()),
+                url: "https://codeberg.org/owner/repo/commit/abc".to_string(),
+            }
+        );
+    }
+}
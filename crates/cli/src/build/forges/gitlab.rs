@@ -0,0 +1,347 @@
+//! This module defines the functionality used to collect information from
+//! GitLab (gitlab.com or self-hosted instances).
+
+use super::{
+    header_value, parse_repo_url_pattern, participation_stats, participation_stats_since, Forge, Project,
+    PARTICIPATION_STATS_MAX_COMMITS,
+};
+use crate::build::github::parse_api_url;
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use landscape2_core::data::{Commit, Release};
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::{instrument, warn};
+
+/// Namespace used to cache data collected from GitLab (one entry per
+/// repository).
+pub(crate) const GITLAB_CACHE_NAMESPACE: &str = "gitlab";
+
+/// Default for how long the GitLab data in the cache is valid (in days).
+pub(crate) const GITLAB_CACHE_TTL: i64 = 7;
+
+/// Environment variable containing a comma separated list of GitLab tokens.
+pub(super) const GITLAB_TOKENS: &str = "GITLAB_TOKENS";
+
+/// Default GitLab API base url.
+const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Default GitLab repository url regular expression.
+const GITLAB_REPO_URL_PATTERN: &str = r"^https://gitlab\.com/(?P<project>[^/]+(?:/[^/]+)+?)/?$";
+
+/// Maximum number of items per page (maximum allowed by GitLab).
+const GITLAB_PAGE_SIZE: usize = 100;
+
+/// GitLab collector options.
+#[derive(Args, Clone)]
+pub struct GitlabOptions {
+    /// GitLab API base url. It can be used to collect data from a self-hosted
+    /// GitLab instance (i.e. https://gitlab.example.com/api/v4).
+    #[arg(
+        id = "gitlab_api_url",
+        long = "gitlab-api-url",
+        value_name = "URL",
+        default_value = GITLAB_API_URL,
+        value_parser = parse_api_url
+    )]
+    pub api_url: String,
+
+    /// Regular expression used to identify the GitLab repositories urls. It
+    /// must include the project named group (full path of the project).
+    #[arg(
+        id = "gitlab_repo_url_pattern",
+        long = "gitlab-repo-url-pattern",
+        value_name = "REGEX",
+        default_value = GITLAB_REPO_URL_PATTERN,
+        value_parser = parse_repo_url_pattern
+    )]
+    pub repo_url_pattern: Regex,
+}
+
+/// Forge implementation backed by the GitLab API.
+pub(super) struct GitlabApi {
+    api_url: String,
+    http_client: reqwest::Client,
+}
+
+impl GitlabApi {
+    /// Create a new GitlabApi instance.
+    pub(super) fn new(token: &str, api_url: &str) -> Result<Self> {
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let mut headers = HeaderMap::new();
+        headers.insert("PRIVATE-TOKEN", HeaderValue::from_str(token)?);
+        let http_client =
+            reqwest::Client::builder().user_agent(user_agent).default_headers(headers).build()?;
+
+        Ok(Self {
+            api_url: api_url.to_string(),
+            http_client,
+        })
+    }
+
+    /// Get the url of the project endpoint provided.
+    fn project_url(&self, project: &str, endpoint: &str) -> String {
+        format!(
+            "{}/projects/{}{endpoint}",
+            self.api_url,
+            encode_project_path(project)
+        )
+    }
+
+    /// Get the commits of the reference provided (page of results requested).
+    async fn get_commits(
+        &self,
+        project: &str,
+        query: &[(&str, &str)],
+    ) -> Result<(Vec<GitlabCommit>, HeaderMap)> {
+        let url = self.project_url(project, "/repository/commits");
+        let response = self.http_client.get(url).query(query).send().await?.error_for_status()?;
+        let headers = response.headers().clone();
+        Ok((response.json().await?, headers))
+    }
+}
+
+#[async_trait]
+impl Forge for GitlabApi {
+    /// [Forge::get_contributors_count]
+    #[instrument(skip(self), err)]
+    async fn get_contributors_count(&self, project: &str, _ref: &str) -> Result<usize> {
+        let url = self.project_url(project, "/repository/contributors?per_page=1");
+        let response = self.http_client.get(url).send().await?.error_for_status()?;
+        header_value(response.headers(), "x-total")
+            .ok_or_else(|| format_err!("x-total header not found in contributors response"))
+    }
+
+    /// [Forge::get_first_commit]
+    #[instrument(skip(self), err)]
+    async fn get_first_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>> {
+        // Get last commits page (GitLab does not include the total number of
+        // pages for very large results sets)
+        let query = [("ref_name", ref_), ("per_page", "1")];
+        let (_, headers) = self.get_commits(project, &query).await?;
+        let Some(last_page) = header_value::<usize>(&headers, "x-total-pages") else {
+            warn!(
+                project,
+                "total number of commits pages not available, first commit not collected"
+            );
+            return Ok(None);
+        };
+
+        // Get first project commit and return it if found
+        let last_page = last_page.to_string();
+        let query = [("ref_name", ref_), ("per_page", "1"), ("page", &last_page)];
+        let (mut commits, _) = self.get_commits(project, &query).await?;
+        Ok(commits.pop().map(Commit::from))
+    }
+
+    /// [Forge::get_languages]
+    ///
+    /// GitLab only provides the percentage of each language, which is returned
+    /// in hundredths of a percent so that they can be used as relative weights.
+    #[allow(clippy::cast_possible_truncation)]
+    #[instrument(skip(self), err)]
+    async fn get_languages(&self, project: &str) -> Result<Option<BTreeMap<String, i64>>> {
+        let url = self.project_url(project, "/languages");
+        let languages: BTreeMap<String, f64> =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok(Some(
+            languages
+                .into_iter()
+                .map(|(name, percentage)| (name, (percentage * 100.0).round() as i64))
+                .collect(),
+        ))
+    }
+
+    /// [Forge::get_latest_commit]
+    #[instrument(skip(self), err)]
+    async fn get_latest_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>> {
+        let query = [("ref_name", ref_), ("per_page", "1")];
+        let (mut commits, _) = self.get_commits(project, &query).await?;
+        Ok(commits.pop().map(Commit::from))
+    }
+
+    /// [Forge::get_latest_release]
+    #[instrument(skip(self), err)]
+    async fn get_latest_release(&self, project: &str) -> Result<Option<Release>> {
+        let url = self.project_url(project, "/releases?per_page=1");
+        let mut releases: Vec<GitlabRelease> =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok(releases.pop().map(|release| Release {
+            ts: release.released_at,
+            url: release.links.self_,
+        }))
+    }
+
+    /// [Forge::get_participation_stats]
+    #[instrument(skip(self), err)]
+    async fn get_participation_stats(&self, project: &str, ref_: &str) -> Result<Vec<i64>> {
+        // Get all commits of the last year
+        let now = Utc::now();
+        let since = participation_stats_since(now).to_rfc3339();
+        let per_page = GITLAB_PAGE_SIZE.to_string();
+        let mut commits = vec![];
+        let mut page = "1".to_string();
+        for _ in 0..PARTICIPATION_STATS_MAX_COMMITS.div_ceil(GITLAB_PAGE_SIZE) {
+            let query = [
+                ("ref_name", ref_),
+                ("since", &since),
+                ("per_page", &per_page),
+                ("page", &page),
+            ];
+            let (page_commits, headers) = self.get_commits(project, &query).await?;
+            commits.extend(page_commits);
+            match header_value::<String>(&headers, "x-next-page") {
+                Some(next_page) if !next_page.is_empty() => page = next_page,
+                _ => break,
+            }
+        }
+
+        Ok(participation_stats(
+            now,
+            commits.iter().filter_map(|commit| commit.authored_date),
+        ))
+    }
+
+    /// [Forge::get_project]
+    #[instrument(skip(self), err)]
+    async fn get_project(&self, project: &str) -> Result<Project> {
+        let url = self.project_url(project, "?license=true");
+        let project: GitlabProject =
+            self.http_client.get(url).send().await?.error_for_status()?.json().await?;
+        Ok(project.into())
+    }
+}
+
+/// Project information returned by the GitLab API.
+#[derive(Debug, Clone, Deserialize)]
+struct GitlabProject {
+    #[serde(default)]
+    archived: bool,
+    default_branch: Option<String>,
+    description: Option<String>,
+    forks_count: i64,
+    license: Option<GitlabProjectLicense>,
+    // Not included when the issues are disabled
+    open_issues_count: Option<i64>,
+    star_count: i64,
+    #[serde(default)]
+    topics: Vec<String>,
+    web_url: String,
+}
+
+impl From<GitlabProject> for Project {
+    fn from(value: GitlabProject) -> Self {
+        let contributors_url = match &value.default_branch {
+            Some(default_branch) => format!("{}/-/graphs/{default_branch}", value.web_url),
+            None => value.web_url.clone(),
+        };
+        Project {
+            archived: value.archived,
+            contributors_url,
+            default_branch: value.default_branch,
+            description: value.description,
+            forks: Some(value.forks_count),
+            license: value.license.map(|license| license.name),
+            open_issues: value.open_issues_count,
+            open_pull_requests: None,
+            stars: value.star_count,
+            topics: value.topics,
+            url: value.web_url,
+            watchers: None,
+        }
+    }
+}
+
+/// Project license information returned by the GitLab API.
+#[derive(Debug, Clone, Deserialize)]
+struct GitlabProjectLicense {
+    name: String,
+}
+
+/// Commit information returned by the GitLab API.
+#[derive(Debug, Clone, Deserialize)]
+struct GitlabCommit {
+    authored_date: Option<DateTime<Utc>>,
+    web_url: String,
+}
+
+impl From<GitlabCommit> for Commit {
+    fn from(value: GitlabCommit) -> Self {
+        Commit {
+            ts: value.authored_date,
+            url: value.web_url,
+        }
+    }
+}
+
+/// Release information returned by the GitLab API.
+#[derive(Debug, Clone, Deserialize)]
+struct GitlabRelease {
+    released_at: Option<DateTime<Utc>>,
+    #[serde(rename = "_links")]
+    links: GitlabReleaseLinks,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GitlabReleaseLinks {
+    #[serde(rename = "self")]
+    self_: String,
+}
+
+/// Encode the project path provided so that it can be used as the project id
+/// in the GitLab API urls.
+fn encode_project_path(project: &str) -> String {
+    project.replace('/', "%2F")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_project_path_escapes_slashes() {
+        assert_eq!(
+            encode_project_path("group/subgroup/project"),
+            "group%2Fsubgroup%2Fproject"
+        );
+    }
+
+    #[test]
+    fn project_from_gitlab_project() {
+        let gitlab_project: GitlabProject = serde_json::from_str(
+            r#"{
+                "archived": false,
+                "default_branch": "main",
+                "description": "description",
+                "forks_count": 5,
+                "license": { "key": "apache-2.0", "name": "Apache License 2.0" },
+                "open_issues_count": 8,
+                "star_count": 100,
+                "topics": ["topic1"],
+                "web_url": "https://gitlab.com/group/project"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Project::from(gitlab_project),
+            Project {
+                archived: false,
+                contributors_url: "https://gitlab.com/group/project/-/graphs/main".to_string(),
+                default_branch: Some("main".to_string()),
+                description: Some("description".to_string()),
+                forks: Some(5),
+                license: Some("Apache License 2.0".to_string()),
+                open_issues: Some(8),
+                open_pull_requests: None,
+                stars: 100,
+                topics: vec!["topic1".to_string()],
+                url: "https://gitlab.com/group/project".to_string(),
+                watchers: None,
+            }
+        );
+    }
+}
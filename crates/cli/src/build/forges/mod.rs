@@ -0,0 +1,597 @@
+//! This module defines the functionality used to collect information from
+//! code forges other than GitHub (GitLab, Gitea/Forgejo and Bitbucket) for
+//! each of the landscape items repositories hosted on them (when applicable).
+//! The forge used for each repository is selected using its url, and the
+//! information collected is represented using the same types used for the
+//! data collected from GitHub.
+
+use self::{
+    bitbucket::{BitbucketApi, BitbucketOptions, BITBUCKET_CACHE_NAMESPACE, BITBUCKET_TOKENS},
+    gitea::{GiteaApi, GiteaOptions, GITEA_CACHE_NAMESPACE, GITEA_TOKENS},
+    gitlab::{GitlabApi, GitlabOptions, GITLAB_CACHE_NAMESPACE, GITLAB_TOKENS},
+};
+use super::{
+    cache::{Cache, CacheTtls, CachedCollector, CachedSource},
+    github::{add_repository_url, branch_matches, parse_url_pattern},
+    issues::{IssueKind, Issues},
+    LandscapeData,
+};
+use anyhow::{bail, format_err, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use deadpool::unmanaged::{Object, Pool};
+use futures::future;
+use landscape2_core::data::{Commit, Contributors, GithubData, Release, RepositoryGithubData};
+#[cfg(test)]
+use mockall::automock;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use std::{collections::BTreeMap, env, fmt};
+use tracing::{debug, instrument, warn};
+
+pub(crate) mod bitbucket;
+pub(crate) mod gitea;
+pub(crate) mod gitlab;
+
+/// Number of weeks included in the participation stats.
+const PARTICIPATION_STATS_WEEKS: i64 = 52;
+
+/// Maximum number of commits processed to count the contributors of a
+/// repository, in forges that do not provide this information directly.
+const CONTRIBUTORS_MAX_COMMITS: usize = 1000;
+
+/// Maximum number of commits processed to build the participation stats of a
+/// repository (the stats of very active repositories will be incomplete).
+const PARTICIPATION_STATS_MAX_COMMITS: usize = 10_000;
+
+/// Forges supported (in the order used to select the forge of a repository).
+const FORGES: [ForgeKind; 3] = [ForgeKind::Gitlab, ForgeKind::Gitea, ForgeKind::Bitbucket];
+
+/// Forges collectors options.
+#[derive(Args, Clone)]
+pub struct ForgesOptions {
+    /// Bitbucket collector options.
+    #[command(flatten)]
+    pub bitbucket: BitbucketOptions,
+
+    /// Gitea/Forgejo collector options.
+    #[command(flatten)]
+    pub gitea: GiteaOptions,
+
+    /// GitLab collector options.
+    #[command(flatten)]
+    pub gitlab: GitlabOptions,
+}
+
+impl ForgesOptions {
+    /// Get the API base url and the repository url pattern of the forge
+    /// provided.
+    fn get(&self, forge: ForgeKind) -> (&str, &Regex) {
+        match forge {
+            ForgeKind::Bitbucket => (&self.bitbucket.api_url, &self.bitbucket.repo_url_pattern),
+            ForgeKind::Gitea => (&self.gitea.api_url, &self.gitea.repo_url_pattern),
+            ForgeKind::Gitlab => (&self.gitlab.api_url, &self.gitlab.repo_url_pattern),
+        }
+    }
+
+    /// Select the forge of the repository url provided (if any).
+    fn select_forge(&self, repo_url: &str) -> Option<ForgeKind> {
+        FORGES.into_iter().find(|forge| self.get(*forge).1.is_match(repo_url))
+    }
+}
+
+/// Forge kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ForgeKind {
+    Bitbucket,
+    Gitea,
+    Gitlab,
+}
+
+impl ForgeKind {
+    /// Namespace used to cache the data collected from this forge.
+    fn cache_namespace(self) -> &'static str {
+        match self {
+            ForgeKind::Bitbucket => BITBUCKET_CACHE_NAMESPACE,
+            ForgeKind::Gitea => GITEA_CACHE_NAMESPACE,
+            ForgeKind::Gitlab => GITLAB_CACHE_NAMESPACE,
+        }
+    }
+
+    /// How long the data collected from this forge is valid in the cache.
+    fn cache_ttl(self, cache_ttls: &CacheTtls) -> i64 {
+        match self {
+            ForgeKind::Bitbucket => cache_ttls.bitbucket,
+            ForgeKind::Gitea => cache_ttls.gitea,
+            ForgeKind::Gitlab => cache_ttls.gitlab,
+        }
+    }
+
+    /// Kind of the issues found collecting data from this forge.
+    fn issue_kind(self) -> IssueKind {
+        match self {
+            ForgeKind::Bitbucket => IssueKind::Bitbucket,
+            ForgeKind::Gitea => IssueKind::Gitea,
+            ForgeKind::Gitlab => IssueKind::Gitlab,
+        }
+    }
+
+    /// Create a new Forge instance for this forge kind.
+    fn new_forge(self, token: &str, api_url: &str) -> Result<DynForge> {
+        let forge: DynForge = match self {
+            ForgeKind::Bitbucket => Box::new(BitbucketApi::new(token, api_url)?),
+            ForgeKind::Gitea => Box::new(GiteaApi::new(token, api_url)?),
+            ForgeKind::Gitlab => Box::new(GitlabApi::new(token, api_url)?),
+        };
+        Ok(forge)
+    }
+
+    /// Environment variable containing the tokens for this forge.
+    fn tokens_env_var(self) -> &'static str {
+        match self {
+            ForgeKind::Bitbucket => BITBUCKET_TOKENS,
+            ForgeKind::Gitea => GITEA_TOKENS,
+            ForgeKind::Gitlab => GITLAB_TOKENS,
+        }
+    }
+}
+
+impl fmt::Display for ForgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForgeKind::Bitbucket => write!(f, "bitbucket"),
+            ForgeKind::Gitea => write!(f, "gitea"),
+            ForgeKind::Gitlab => write!(f, "gitlab"),
+        }
+    }
+}
+
+/// Collect data from the corresponding forge for each of the items
+/// repositories not hosted on GitHub, reusing cached data whenever possible.
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_forges_data(
+    cache: &Cache,
+    landscape_data: &LandscapeData,
+    issues: &Issues,
+    options: &ForgesOptions,
+    cache_ttls: &CacheTtls,
+    refresh_percentage: Option<u8>,
+    offline: bool,
+) -> Result<GithubData> {
+    // Collect urls of the repositories to process (and the branch to use for
+    // each of them, when set), grouped by forge
+    let mut urls: BTreeMap<ForgeKind, BTreeMap<&String, Option<&String>>> = BTreeMap::new();
+    for item in &landscape_data.items {
+        if let Some(repositories) = &item.repositories {
+            for repo in repositories {
+                if let Some(forge) = options.select_forge(&repo.url) {
+                    add_repository_url(urls.entry(forge).or_default(), repo);
+                }
+            }
+        }
+    }
+
+    // Collect data from each of the forges concurrently
+    let forges_data = future::try_join_all(urls.into_iter().map(|(forge, urls)| {
+        collect_forge_data(
+            cache,
+            issues,
+            options,
+            forge,
+            urls,
+            forge.cache_ttl(cache_ttls),
+            refresh_percentage,
+            offline,
+        )
+    }))
+    .await?;
+
+    Ok(forges_data.into_iter().flatten().collect())
+}
+
+/// Collect data from the forge provided for the repositories urls provided,
+/// reusing cached data whenever possible.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip(cache, issues, options, urls), err)]
+async fn collect_forge_data(
+    cache: &Cache,
+    issues: &Issues,
+    options: &ForgesOptions,
+    forge: ForgeKind,
+    urls: BTreeMap<&String, Option<&String>>,
+    ttl: i64,
+    refresh_percentage: Option<u8>,
+    offline: bool,
+) -> Result<GithubData> {
+    debug!("collecting repositories information from {forge} (this may take a while)");
+    let (api_url, repo_url_pattern) = options.get(forge);
+    let cache_source = forge.to_string();
+
+    // Setup forge API clients pool if any tokens have been provided (in
+    // offline mode only cached data is used)
+    let tokens: Option<Vec<String>> = match env::var(forge.tokens_env_var()) {
+        _ if offline => None,
+        Ok(tokens) if !tokens.is_empty() => Some(tokens.split(',').map(ToString::to_string).collect()),
+        Ok(_) | Err(_) => None,
+    };
+    let forge_pool: Option<Pool<DynForge>> = if let Some(tokens) = &tokens {
+        let mut forge_clients: Vec<DynForge> = vec![];
+        for token in tokens {
+            forge_clients.push(forge.new_forge(token, api_url)?);
+        }
+        Some(Pool::from(forge_clients))
+    } else if offline {
+        debug!("offline mode enabled: only cached data will be used");
+        None
+    } else {
+        warn!("{forge} tokens not provided: no information will be collected from {forge}");
+        None
+    };
+
+    // Read cached data (if available) and select the expired entries that
+    // will be refreshed in this build
+    let source = CachedSource {
+        name: &cache_source,
+        namespace: forge.cache_namespace(),
+        issue_kind: forge.issue_kind(),
+        ttl,
+        refresh_percentage,
+        offline,
+    };
+    let collector = CachedCollector::new(cache, issues, source, urls.keys().copied(), |url, cached_repo| {
+        let valid = branch_matches(cached_repo, urls[url]);
+        if !valid {
+            debug!(?url, "branch changed, ignoring cached repository data");
+        }
+        valid
+    });
+
+    // Collect repositories information from the forge, reusing cached data
+    // when available
+    let concurrency = tokens.map_or(1, |tokens| tokens.len());
+    let forge_pool = &forge_pool;
+    let urls = urls.into_iter().map(|(url, branch)| (url.clone(), branch)).collect();
+    let forge_data: GithubData = collector
+        .collect(urls, concurrency, |url, branch| async move {
+            let Some(forge_pool) = forge_pool else {
+                bail!("no tokens provided");
+            };
+            let forge: Object<DynForge> = forge_pool
+                .get()
+                .await
+                .map_err(|err| format_err!("error getting {forge} client: {err:?}"))?;
+            collect_repository_data(&forge, repo_url_pattern, &url, branch.map(String::as_str)).await
+        })
+        .await;
+
+    debug!("done!");
+    Ok(forge_data)
+}
+
+/// Collect repository data from the forge provided. The commits data is
+/// collected from the branch provided, or from the default branch when none
+/// is set.
+#[instrument(skip_all, err)]
+async fn collect_repository_data(
+    forge: &DynForge,
+    repo_url_pattern: &Regex,
+    repo_url: &str,
+    branch: Option<&str>,
+) -> Result<RepositoryGithubData> {
+    // Collect some information from the forge
+    let project_path = get_project_path(repo_url_pattern, repo_url)?;
+    let project = forge.get_project(&project_path).await?;
+    let Some(default_branch) = project.default_branch else {
+        bail!("repository is empty");
+    };
+    let branch = branch.map_or(default_branch, ToString::to_string);
+    let contributors_count = forge.get_contributors_count(&project_path, &branch).await?;
+    let first_commit = forge.get_first_commit(&project_path, &branch).await?;
+    let languages = forge.get_languages(&project_path).await?;
+    let latest_commit = forge
+        .get_latest_commit(&project_path, &branch)
+        .await?
+        .ok_or_else(|| format_err!("latest commit not found"))?;
+    let latest_release = forge.get_latest_release(&project_path).await?;
+    let participation_stats = forge.get_participation_stats(&project_path, &branch).await?;
+
+    // Prepare repository instance using the information collected
+    Ok(RepositoryGithubData {
+        generated_at: Utc::now(),
+        archived: project.archived.then_some(true),
+        branch: Some(branch),
+        contributors: Contributors {
+            count: contributors_count,
+            url: project.contributors_url,
+        },
+        description: project.description.unwrap_or_default(),
+        disabled: None,
+        first_commit,
+        forks: project.forks,
+        languages,
+        latest_commit,
+        latest_release,
+        license: project.license,
+        open_issues: project.open_issues,
+        open_pull_requests: project.open_pull_requests,
+        participation_stats,
+        pull_requests_median_merge_time: None,
+        releases_last_year: None,
+        stale: None,
+        stars: project.stars,
+        topics: project.topics,
+        url: project.url,
+        watchers: project.watchers,
+    })
+}
+
+/// Type alias to represent a Forge trait object.
+type DynForge = Box<dyn Forge + Send + Sync>;
+
+/// Trait that defines some operations a Forge implementation must support.
+/// The project is identified by its path in the forge (i.e. owner/repo).
+#[allow(clippy::struct_field_names)]
+#[cfg_attr(test, automock)]
+#[async_trait]
+trait Forge {
+    /// Get number of project contributors.
+    async fn get_contributors_count(&self, project: &str, ref_: &str) -> Result<usize>;
+
+    /// Get first commit.
+    async fn get_first_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>>;
+
+    /// Get languages used in project.
+    async fn get_languages(&self, project: &str) -> Result<Option<BTreeMap<String, i64>>>;
+
+    /// Get latest commit.
+    async fn get_latest_commit(&self, project: &str, ref_: &str) -> Result<Option<Commit>>;
+
+    /// Get latest release.
+    async fn get_latest_release(&self, project: &str) -> Result<Option<Release>>;
+
+    /// Get participation stats (weekly number of commits during the last
+    /// year, oldest week first).
+    async fn get_participation_stats(&self, project: &str, ref_: &str) -> Result<Vec<i64>>;
+
+    /// Get project.
+    async fn get_project(&self, project: &str) -> Result<Project>;
+}
+
+/// Project information collected from a forge.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Project {
+    archived: bool,
+    contributors_url: String,
+    default_branch: Option<String>,
+    description: Option<String>,
+    forks: Option<i64>,
+    license: Option<String>,
+    open_issues: Option<i64>,
+    open_pull_requests: Option<i64>,
+    stars: i64,
+    topics: Vec<String>,
+    url: String,
+    watchers: Option<i64>,
+}
+
+/// Parse the repository url pattern provided, checking it includes the
+/// expected named group.
+fn parse_repo_url_pattern(value: &str) -> Result<Regex> {
+    parse_url_pattern(value, &["project"])
+}
+
+/// Extract the project path from the repository url provided.
+fn get_project_path(repo_url_pattern: &Regex, repo_url: &str) -> Result<String> {
+    let c = repo_url_pattern.captures(repo_url).ok_or_else(|| format_err!("invalid repository url"))?;
+    Ok(c["project"].trim_end_matches(".git").to_string())
+}
+
+/// Parse the value of the header provided.
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Compute the weekly number of commits during the last year (oldest week
+/// first) from the commits dates provided.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn participation_stats(now: DateTime<Utc>, commits_dates: impl Iterator<Item = DateTime<Utc>>) -> Vec<i64> {
+    let mut stats = vec![0; PARTICIPATION_STATS_WEEKS as usize];
+    for date in commits_dates {
+        let weeks_ago = (now - date).num_weeks();
+        if (0..PARTICIPATION_STATS_WEEKS).contains(&weeks_ago) {
+            stats[(PARTICIPATION_STATS_WEEKS - 1 - weeks_ago) as usize] += 1;
+        }
+    }
+    stats
+}
+
+/// Date since which commits are included in the participation stats.
+fn participation_stats_since(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::weeks(PARTICIPATION_STATS_WEEKS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use mockall::predicate::eq;
+
+    const PROJECT: &str = "group/subgroup/project";
+    const REPO_URL: &str = "https://gitlab.com/group/subgroup/project";
+    const DEFAULT_BRANCH: &str = "main";
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        options: ForgesOptions,
+    }
+
+    #[tokio::test]
+    async fn collect_repository_data_from_forge() {
+        let mut forge = MockForge::new();
+        forge.expect_get_project().with(eq(PROJECT)).times(1).returning(|_| {
+            Ok(Project {
+                contributors_url: format!("{REPO_URL}/-/graphs/main"),
+                default_branch: Some(DEFAULT_BRANCH.to_string()),
+                description: Some("description".to_string()),
+                forks: Some(5),
+                license: Some("Apache License 2.0".to_string()),
+                open_issues: Some(8),
+                stars: 100,
+                topics: vec!["topic1".to_string()],
+                url: REPO_URL.to_string(),
+                ..Default::default()
+            })
+        });
+        forge
+            .expect_get_contributors_count()
+            .with(eq(PROJECT), eq(DEFAULT_BRANCH))
+            .times(1)
+            .returning(|_, _| Ok(10));
+        forge
+            .expect_get_first_commit()
+            .with(eq(PROJECT), eq(DEFAULT_BRANCH))
+            .times(1)
+            .returning(|_, _| Ok(Some(sample_commit("first"))));
+        forge.expect_get_languages().with(eq(PROJECT)).times(1).returning(|_| {
+            Ok(Some(BTreeMap::from([
+                ("Go".to_string(), 9000),
+                ("Shell".to_string(), 1000),
+            ])))
+        });
+        forge
+            .expect_get_latest_commit()
+            .with(eq(PROJECT), eq(DEFAULT_BRANCH))
+            .times(1)
+            .returning(|_, _| Ok(Some(sample_commit("latest"))));
+        forge.expect_get_latest_release().with(eq(PROJECT)).times(1).returning(|_| Ok(None));
+        forge
+            .expect_get_participation_stats()
+            .with(eq(PROJECT), eq(DEFAULT_BRANCH))
+            .times(1)
+            .returning(|_, _| Ok(vec![1, 2, 3]));
+
+        let forge: DynForge = Box::new(forge);
+        let options = TestCli::parse_from(["test"]).options;
+        let repo_data = collect_repository_data(&forge, &options.gitlab.repo_url_pattern, REPO_URL, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo_data,
+            RepositoryGithubData {
+                contributors: Contributors {
+                    count: 10,
+                    url: format!("{REPO_URL}/-/graphs/main"),
+                },
+                description: "description".to_string(),
+                generated_at: repo_data.generated_at,
+                latest_commit: sample_commit("latest"),
+                participation_stats: vec![1, 2, 3],
+                stars: 100,
+                topics: vec!["topic1".to_string()],
+                url: REPO_URL.to_string(),
+                first_commit: Some(sample_commit("first")),
+                languages: Some(BTreeMap::from([
+                    ("Go".to_string(), 9000),
+                    ("Shell".to_string(), 1000)
+                ])),
+                latest_release: None,
+                license: Some("Apache License 2.0".to_string()),
+                archived: None,
+                branch: Some(DEFAULT_BRANCH.to_string()),
+                disabled: None,
+                forks: Some(5),
+                open_issues: Some(8),
+                open_pull_requests: None,
+                pull_requests_median_merge_time: None,
+                releases_last_year: None,
+                stale: None,
+                watchers: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_repository_data_empty_repository() {
+        let mut forge = MockForge::new();
+        forge.expect_get_project().with(eq(PROJECT)).times(1).returning(|_| {
+            Ok(Project {
+                url: REPO_URL.to_string(),
+                ..Default::default()
+            })
+        });
+
+        let forge: DynForge = Box::new(forge);
+        let options = TestCli::parse_from(["test"]).options;
+        let err = collect_repository_data(&forge, &options.gitlab.repo_url_pattern, REPO_URL, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "repository is empty");
+    }
+
+    #[test]
+    fn select_forge_using_repo_url() {
+        let options = TestCli::parse_from(["test"]).options;
+        for (repo_url, forge) in [
+            ("https://bitbucket.org/workspace/repo", Some(ForgeKind::Bitbucket)),
+            ("https://codeberg.org/owner/repo", Some(ForgeKind::Gitea)),
+            ("https://gitlab.com/group/project", Some(ForgeKind::Gitlab)),
+            ("https://github.com/owner/repo", None),
+        ] {
+            assert_eq!(options.select_forge(repo_url), forge);
+        }
+    }
+
+    #[test]
+    fn get_project_path_from_repo_url() {
+        let options = TestCli::parse_from(["test"]).options;
+        for (repo_url, project) in [
+            ("https://gitlab.com/group/project", "group/project"),
+            ("https://gitlab.com/group/project/", "group/project"),
+            ("https://gitlab.com/group/project.git", "group/project"),
+            (REPO_URL, PROJECT),
+        ] {
+            assert_eq!(
+                get_project_path(&options.gitlab.repo_url_pattern, repo_url).unwrap(),
+                project
+            );
+        }
+        assert!(get_project_path(&options.gitlab.repo_url_pattern, "https://gitlab.com/group").is_err());
+        assert!(get_project_path(&options.gitlab.repo_url_pattern, "https://github.com/owner/repo").is_err());
+    }
+
+    #[test]
+    fn parse_repo_url_pattern_requires_project_group() {
+        assert!(parse_repo_url_pattern("^https://gitlab.example.com/(?P<project>.+)$").is_ok());
+        assert_eq!(
+            parse_repo_url_pattern("^https://gitlab.example.com/(.+)$").unwrap_err().to_string(),
+            "project named group not found"
+        );
+    }
+
+    #[test]
+    fn participation_stats_groups_commits_by_week() {
+        let now = Utc::now();
+        let commits_dates = [
+            now - Duration::days(1),
+            now - Duration::days(2),
+            now - Duration::days(8),
+            now - Duration::weeks(51) - Duration::days(1),
+            now - Duration::weeks(60),
+        ];
+        let stats = participation_stats(now, commits_dates.into_iter());
+        assert_eq!(stats.len(), 52);
+        assert_eq!(stats[51], 2);
+        assert_eq!(stats[50], 1);
+        assert_eq!(stats[0], 1);
+        assert_eq!(stats.iter().sum::<i64>(), 4);
+    }
+
+    fn sample_commit(sha: &str) -> Commit {
+        Commit {
+            ts: Some(DateTime::parse_from_rfc3339("2024-01-02T01:04:05Z").unwrap().into()),
+            url: format!("{REPO_URL}/-/commit/{sha}"),
+        }
+    }
+}
@@ -2,179 +2,918 @@
 //! from GitHub for each of the landscape items repositories (when applicable),
 //! as well as the functionality used to collect that information.
 
-use super::{cache::Cache, LandscapeData};
-use anyhow::{format_err, Result};
+use super::{
+    cache::{Cache, CachedCollector, CachedData, CachedSource},
+    issues::{IssueKind, Issues},
+    LandscapeData,
+};
+use anyhow::{bail, format_err, Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use clap::{Args, ValueEnum};
 use deadpool::unmanaged::{Object, Pool};
-use futures::stream::{self, StreamExt};
-use landscape2_core::data::{Commit, Contributors, GithubData, Release, RepositoryGithubData};
+use futures::{
+    future,
+    stream::{self, StreamExt},
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use landscape2_core::{
+    data::{self, Commit, Contributors, GithubData, Release, RepositoryGithubData},
+    stats::median,
+};
 use lazy_static::lazy_static;
 #[cfg(test)]
 use mockall::automock;
 use octorust::auth::Credentials;
 use octorust::types::{FullRepository, ParticipationStats};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use regex::Regex;
-use reqwest::header::{self, HeaderMap, HeaderValue};
-use std::collections::BTreeMap;
+use reqwest::{
+    header::{self, HeaderMap, HeaderValue},
+    RequestBuilder, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::fmt;
+use std::fs;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 use tracing::{debug, instrument, warn};
+use url::Url;
 
-/// File used to cache data collected from GitHub.
-const GITHUB_CACHE_FILE: &str = "github.json";
+/// Name used to track the GitHub data in the cache stats.
+const GITHUB_CACHE_SOURCE: &str = "github";
 
-/// How long the GitHub data in the cache is valid (in days).
-const GITHUB_CACHE_TTL: i64 = 7;
+/// Namespace used to cache data collected from GitHub (one entry per
+/// repository).
+pub(crate) const GITHUB_CACHE_NAMESPACE: &str = "github";
+
+/// Default for how long the GitHub data in the cache is valid (in days).
+pub(crate) const GITHUB_CACHE_TTL: i64 = 7;
 
 /// Environment variable containing a comma separated list of GitHub tokens.
 const GITHUB_TOKENS: &str = "GITHUB_TOKENS";
 
+/// Environment variable containing the id of the GitHub App used to
+/// authenticate (as an alternative or in addition to the tokens).
+const GITHUB_APP_ID: &str = "GITHUB_APP_ID";
+
+/// Environment variable containing the id of the GitHub App installation.
+const GITHUB_APP_INSTALLATION_ID: &str = "GITHUB_APP_INSTALLATION_ID";
+
+/// Environment variable containing the path of the GitHub App private key
+/// file (PEM format).
+const GITHUB_APP_PRIVATE_KEY_FILE: &str = "GITHUB_APP_PRIVATE_KEY_FILE";
+
+/// How long before they expire GitHub App installation tokens are refreshed.
+const GITHUB_APP_TOKEN_REFRESH_MARGIN: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Number of repositories fetched in a single GraphQL query.
+const GITHUB_GRAPHQL_BATCH_SIZE: usize = 25;
+
+/// Number of requests kept in reserve in each token's quota. Tokens are not
+/// used once their remaining quota drops to this value, as some requests may
+/// still be in flight.
+const GITHUB_RATE_LIMIT_RESERVE: i64 = 10;
+
+/// How long a token is paused after hitting a secondary rate limit when GitHub
+/// does not specify how long to wait.
+const GITHUB_SECONDARY_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Default for how long (in seconds) the collection can wait for a rate
+/// limited token to be available again.
+const GITHUB_MAX_RATE_LIMIT_WAIT: u64 = 30 * 60;
+
+/// Maximum number of attempts to collect a repository data when the requests
+/// are rate limited.
+const GITHUB_MAX_ATTEMPTS: usize = 3;
+
+/// Maximum number of pages of results requested when listing the pull
+/// requests or releases of a repository using the REST API.
+const GITHUB_MAX_PAGES: usize = 10;
+
+/// Number of days considered when calculating the pull requests median merge
+/// time.
+const PULL_REQUESTS_MERGE_TIME_DAYS: i64 = 90;
+
+/// Number of days considered when counting the releases published.
+const RELEASES_DAYS: i64 = 365;
+
+/// GitHub collector options.
+#[derive(Args, Clone)]
+pub struct GithubOptions {
+    /// GitHub API used to collect the repositories data. The GraphQL API
+    /// fetches most of the data for many repositories in a single request,
+    /// using the REST API only for the data it does not provide.
+    #[arg(long = "github-api", value_enum, default_value_t = GithubApi::Rest)]
+    pub api: GithubApi,
+
+    /// GitHub API base url. It can be used to collect data from a GitHub
+    /// Enterprise Server instance (i.e. https://github.example.com/api/v3).
+    #[arg(
+        long = "github-api-url",
+        value_name = "URL",
+        default_value = GITHUB_API_URL,
+        value_parser = parse_api_url
+    )]
+    pub api_url: String,
+
+    /// Regular expression used to identify the repositories urls data will
+    /// be collected for. It must include the owner and repo named groups.
+    #[arg(
+        long = "github-repo-url-pattern",
+        value_name = "REGEX",
+        default_value = GITHUB_REPO_URL_PATTERN,
+        value_parser = parse_repo_url_pattern
+    )]
+    pub repo_url_pattern: Regex,
+
+    /// Maximum time (in seconds) to wait for a rate limited token to be
+    /// available again. Tokens that won't be available until later are not
+    /// used again in this build, and once none is left the data that could
+    /// not be collected is reported as an error (or stale data is used).
+    #[arg(
+        long = "github-max-rate-limit-wait",
+        value_name = "SECONDS",
+        default_value_t = GITHUB_MAX_RATE_LIMIT_WAIT
+    )]
+    pub max_rate_limit_wait: u64,
+}
+
+/// Parse the GitHub API base url provided.
+pub(crate) fn parse_api_url(value: &str) -> Result<String> {
+    let api_url = value.trim_end_matches('/');
+    let url = Url::parse(api_url)?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        bail!("invalid scheme (http or https expected)");
+    }
+    Ok(api_url.to_string())
+}
+
+/// Parse the repository url pattern provided, checking it includes the
+/// expected named groups.
+fn parse_repo_url_pattern(value: &str) -> Result<Regex> {
+    parse_url_pattern(value, &["owner", "repo"])
+}
+
+/// Parse the url pattern provided, checking it includes the named groups
+/// given.
+pub(crate) fn parse_url_pattern(value: &str, groups: &[&str]) -> Result<Regex> {
+    let url_pattern = Regex::new(value)?;
+    for group in groups {
+        if !url_pattern.capture_names().any(|name| name == Some(group)) {
+            bail!("{group} named group not found");
+        }
+    }
+    Ok(url_pattern)
+}
+
+/// GitHub API used to collect the repositories data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GithubApi {
+    Graphql,
+    #[default]
+    Rest,
+}
+
 /// Collect GitHub data for each of the items repositories in the landscape,
 /// reusing cached data whenever possible.
+#[allow(clippy::too_many_lines)]
 #[instrument(skip_all, err)]
-pub(crate) async fn collect_github_data(cache: &Cache, landscape_data: &LandscapeData) -> Result<GithubData> {
+pub(crate) async fn collect_github_data(
+    cache: &Cache,
+    landscape_data: &LandscapeData,
+    issues: &Issues,
+    options: &GithubOptions,
+    ttl: i64,
+    refresh_percentage: Option<u8>,
+    offline: bool,
+) -> Result<(GithubData, Vec<TokenUsage>)> {
     debug!("collecting repositories information from github (this may take a while)");
 
-    // Read cached data (if available)
-    let mut cached_data: Option<GithubData> = None;
-    match cache.read(GITHUB_CACHE_FILE) {
-        Ok(Some((_, json_data))) => match serde_json::from_slice(&json_data) {
-            Ok(github_data) => cached_data = Some(github_data),
-            Err(err) => warn!("error parsing github cache file: {err:?}"),
-        },
-        Ok(None) => {}
-        Err(err) => warn!("error reading github cache file: {err:?}"),
-    }
-
-    // Setup GitHub API clients pool if any tokens have been provided
-    let tokens: Option<Vec<String>> = match env::var(GITHUB_TOKENS) {
-        Ok(tokens) if !tokens.is_empty() => Some(tokens.split(',').map(ToString::to_string).collect()),
-        Ok(_) | Err(_) => None,
+    // Setup GitHub API clients pool if any credentials have been provided (in
+    // offline mode only cached data is used)
+    let credentials = if offline {
+        vec![]
+    } else {
+        get_credentials(&options.api_url)?
     };
-    let gh_pool: Option<Pool<DynGH>> = if let Some(tokens) = &tokens {
-        let mut gh_clients: Vec<DynGH> = vec![];
-        for token in tokens {
-            let gh = Box::new(GHApi::new(token)?);
-            gh_clients.push(gh);
+    let mut quotas: Vec<Arc<TokenQuota>> = vec![];
+    let gh_pool: Option<Pool<GHClient>> = if !credentials.is_empty() {
+        let mut gh_clients: Vec<GHClient> = vec![];
+        let prefetched = PrefetchedRepositories::default();
+        for (name, credentials) in &credentials {
+            let quota = Arc::new(TokenQuota::new(name, credentials.clone(), options)?);
+            let gh: DynGH = match options.api {
+                GithubApi::Graphql => Box::new(GHGraphQLApi::new(
+                    credentials.clone(),
+                    quota.clone(),
+                    &options.api_url,
+                    prefetched.clone(),
+                )?),
+                GithubApi::Rest => {
+                    Box::new(GHApi::new(credentials.clone(), quota.clone(), &options.api_url)?)
+                }
+            };
+            gh_clients.push(GHClient {
+                gh,
+                quota: quota.clone(),
+            });
+            quotas.push(quota);
         }
+
+        // Get the current quota of each token, so that tokens exhausted
+        // before the build started are not used until their quota is reset
+        refresh_quotas(&quotas).await;
+
         Some(Pool::from(gh_clients))
+    } else if offline {
+        debug!("offline mode enabled: only cached data will be used");
+        None
     } else {
-        warn!("github tokens not provided: no information will be collected from github");
+        warn!("github credentials not provided: no information will be collected from github");
         None
     };
 
-    // Collect urls of the repositories to process
-    let mut urls = vec![];
+    // Collect urls of the repositories to process (and the branch to use for
+    // each of them, when set)
+    let mut urls: BTreeMap<&String, Option<&String>> = BTreeMap::new();
     for item in &landscape_data.items {
         if let Some(repositories) = &item.repositories {
             for repo in repositories {
-                if GITHUB_REPO_URL.is_match(&repo.url) {
-                    urls.push(&repo.url);
+                if options.repo_url_pattern.is_match(&repo.url) {
+                    add_repository_url(&mut urls, repo);
                 }
             }
         }
     }
-    urls.sort();
-    urls.dedup();
 
-    // Collect repositories information from GitHub, reusing cached data when available
-    let concurrency = if let Some(tokens) = tokens {
-        tokens.len()
-    } else {
-        1
+    // Read cached data (if available) and select the expired entries that
+    // will be refreshed in this build
+    let source = CachedSource {
+        name: GITHUB_CACHE_SOURCE,
+        namespace: GITHUB_CACHE_NAMESPACE,
+        issue_kind: IssueKind::Github,
+        ttl,
+        refresh_percentage,
+        offline,
     };
-    let github_data: GithubData = stream::iter(urls)
-        .map(|url| async {
-            let url = url.clone();
-
-            // Use cached data when available if it hasn't expired yet
-            if let Some(cached_repo) = cached_data.as_ref().and_then(|cache| {
-                cache.get(&url).and_then(|repo| {
-                    if repo.generated_at + chrono::Duration::days(GITHUB_CACHE_TTL) > Utc::now() {
-                        Some(repo)
-                    } else {
-                        None
+    let collector = CachedCollector::new(cache, issues, source, urls.keys().copied(), |url, cached_repo| {
+        let valid = branch_matches(cached_repo, urls[url]);
+        if !valid {
+            debug!(?url, "branch changed, ignoring cached repository data");
+        }
+        valid
+    });
+
+    // Prefetch the data of the repositories to refresh in batches (when
+    // supported by the GH implementation in use)
+    let concurrency = credentials.len().max(1);
+    let max_wait = Duration::from_secs(options.max_rate_limit_wait);
+    if let Some(gh_pool) = &gh_pool {
+        let repos_to_fetch: Vec<(String, String)> = urls
+            .keys()
+            .filter(|url| collector.will_fetch(url))
+            .filter_map(|url| get_owner_and_repo(&options.repo_url_pattern, url).ok())
+            .collect();
+        stream::iter(repos_to_fetch.chunks(GITHUB_GRAPHQL_BATCH_SIZE))
+            .for_each_concurrent(concurrency, |repos| async move {
+                let gh_client = match get_gh_client(gh_pool, max_wait).await {
+                    Ok(gh_client) => gh_client,
+                    Err(err) => {
+                        warn!(?err, "error prefetching repositories data");
+                        return;
                     }
-                })
-            }) {
-                (url, Ok(cached_repo.clone()))
-            }
-            // Otherwise we pull it from GitHub if any tokens were provided
-            else if let Some(gh_pool) = &gh_pool {
-                let gh = gh_pool.get().await.expect("token -when available-");
-                (url.clone(), collect_repository_data(gh, &url).await)
-            } else {
-                (url.clone(), Err(format_err!("no tokens provided")))
-            }
-        })
-        .buffer_unordered(concurrency)
-        .collect::<BTreeMap<String, Result<RepositoryGithubData>>>()
-        .await
-        .into_iter()
-        .filter_map(|(url, result)| {
-            if let Ok(github_data) = result {
-                Some((url, github_data))
-            } else {
-                None
-            }
+                };
+                if let Err(err) = gh_client.gh.prefetch(repos).await {
+                    if let Some(rate_limit_err) = err.downcast_ref::<RateLimitError>() {
+                        gh_client.quota.rate_limited(rate_limit_err.retry_after).await;
+                    }
+                    warn!(?err, "error prefetching repositories data");
+                }
+            })
+            .await;
+    }
+
+    // Collect repositories information from GitHub, reusing cached data when
+    // available
+    let gh_pool = &gh_pool;
+    let urls = urls.into_iter().map(|(url, branch)| (url.clone(), branch)).collect();
+    let github_data: GithubData = collector
+        .collect(urls, concurrency, |url, branch| async move {
+            let Some(gh_pool) = gh_pool else {
+                bail!("no credentials provided");
+            };
+            collect_repository_data_with_retries(
+                gh_pool,
+                max_wait,
+                &options.repo_url_pattern,
+                &url,
+                branch.map(String::as_str),
+            )
+            .await
         })
-        .collect();
+        .await;
 
-    // Write data (in json format) to cache
-    cache.write(GITHUB_CACHE_FILE, &serde_json::to_vec_pretty(&github_data)?)?;
+    // Report the quota used from each of the tokens
+    refresh_quotas(&quotas).await;
+    let tokens_usage: Vec<TokenUsage> = quotas.iter().map(|quota| quota.usage()).collect();
+    for token_usage in &tokens_usage {
+        debug!(?token_usage, "github token quota usage");
+    }
 
     debug!("done!");
-    Ok(github_data)
+    Ok((github_data, tokens_usage))
 }
 
-/// Collect repository data from GitHub.
+/// Collect repository data from GitHub, retrying when the requests are rate
+/// limited (with another token or, if none is available, once the quota of
+/// the rate limited one is available again).
+async fn collect_repository_data_with_retries(
+    gh_pool: &Pool<GHClient>,
+    max_wait: Duration,
+    repo_url_pattern: &Regex,
+    repo_url: &str,
+    branch: Option<&str>,
+) -> Result<RepositoryGithubData> {
+    let mut attempt = 1;
+    loop {
+        let gh_client = get_gh_client(gh_pool, max_wait).await?;
+        let err = match collect_repository_data(&gh_client.gh, repo_url_pattern, repo_url, branch).await {
+            Ok(repo_data) => return Ok(repo_data),
+            Err(err) => err,
+        };
+        let Some(rate_limit_err) = err.downcast_ref::<RateLimitError>() else {
+            return Err(err);
+        };
+        gh_client.quota.rate_limited(rate_limit_err.retry_after).await;
+        if attempt == GITHUB_MAX_ATTEMPTS {
+            return Err(err);
+        }
+        debug!(
+            ?err,
+            ?repo_url,
+            attempt,
+            "rate limited collecting repository data, retrying"
+        );
+        attempt += 1;
+    }
+}
+
+/// Collect repository data from GitHub. The commits data is collected from
+/// the branch provided, or from the default branch when none is set.
 #[instrument(skip_all, err)]
-async fn collect_repository_data(gh: Object<DynGH>, repo_url: &str) -> Result<RepositoryGithubData> {
+async fn collect_repository_data(
+    gh: &DynGH,
+    repo_url_pattern: &Regex,
+    repo_url: &str,
+    branch: Option<&str>,
+) -> Result<RepositoryGithubData> {
     // Collect some information from GitHub
-    let (owner, repo) = get_owner_and_repo(repo_url)?;
+    let (owner, repo) = get_owner_and_repo(repo_url_pattern, repo_url)?;
     let gh_repo = gh.get_repository(&owner, &repo).await?;
+    let branch = branch.unwrap_or(&gh_repo.default_branch).to_string();
     let contributors_count = gh.get_contributors_count(&owner, &repo).await?;
-    let first_commit = gh.get_first_commit(&owner, &repo, &gh_repo.default_branch).await?;
+    let first_commit = gh.get_first_commit(&owner, &repo, &branch).await?;
     let languages = gh.get_languages(&owner, &repo).await?;
-    let latest_commit = gh.get_latest_commit(&owner, &repo, &gh_repo.default_branch).await?;
+    let latest_commit = gh.get_latest_commit(&owner, &repo, &branch).await?;
     let latest_release = gh.get_latest_release(&owner, &repo).await?;
+    // GitHub only provides the participation stats of the default branch
     let participation_stats = gh.get_participation_stats(&owner, &repo).await?.all;
+    let now = Utc::now();
+    let merge_times = gh
+        .get_pull_requests_merge_times(
+            &owner,
+            &repo,
+            now - chrono::Duration::days(PULL_REQUESTS_MERGE_TIME_DAYS),
+        )
+        .await?;
+    let releases_last_year =
+        gh.get_releases_count(&owner, &repo, now - chrono::Duration::days(RELEASES_DAYS)).await?;
 
     // Prepare repository instance using the information collected
     Ok(RepositoryGithubData {
         generated_at: Utc::now(),
+        archived: gh_repo.archived.then_some(true),
+        branch: Some(branch),
         contributors: Contributors {
             count: contributors_count,
-            url: format!("https://github.com/{owner}/{repo}/graphs/contributors"),
+            url: format!("{}/graphs/contributors", repo_url.trim_end_matches('/')),
         },
         description: gh_repo.description,
+        disabled: gh_repo.disabled.then_some(true),
         first_commit,
+        forks: Some(gh_repo.forks),
         languages,
         latest_commit,
         latest_release,
-        license: gh_repo.license.and_then(|l| {
-            if l.name == "NOASSERTION" {
-                None
-            } else {
-                Some(l.name)
-            }
-        }),
+        license: gh_repo.license.filter(|name| name != "NOASSERTION"),
+        open_issues: Some(gh_repo.open_issues),
+        open_pull_requests: Some(gh_repo.open_pull_requests),
         participation_stats,
-        stars: gh_repo.stargazers_count,
+        pull_requests_median_merge_time: median(merge_times),
+        releases_last_year: Some(releases_last_year),
+        stale: None,
+        stars: gh_repo.stars,
         topics: gh_repo.topics,
-        url: gh_repo.html_url,
+        url: gh_repo.url,
+        watchers: Some(gh_repo.watchers),
     })
 }
 
-/// GitHub API base url.
+/// Add the url of the repository provided to the urls to process, along with
+/// its branch. When the same url is used more than once, the first branch set
+/// is used.
+pub(crate) fn add_repository_url<'a>(
+    urls: &mut BTreeMap<&'a String, Option<&'a String>>,
+    repo: &'a data::Repository,
+) {
+    let branch = urls.entry(&repo.url).or_default();
+    if branch.is_none() {
+        *branch = repo.branch.as_ref();
+    }
+}
+
+/// Check if the cached repository data provided was collected from the branch
+/// given. When no branch is set, any cached data collected is valid.
+pub(crate) fn branch_matches(cached_repo: &RepositoryGithubData, branch: Option<&String>) -> bool {
+    branch.map_or(true, |branch| cached_repo.branch.as_ref() == Some(branch))
+}
+
+impl CachedData for RepositoryGithubData {
+    /// [CachedData::generated_at]
+    fn generated_at(&self) -> DateTime<Utc> {
+        self.generated_at
+    }
+
+    /// [CachedData::set_generated_at]
+    fn set_generated_at(&mut self, generated_at: DateTime<Utc>) {
+        self.generated_at = generated_at;
+    }
+
+    /// [CachedData::set_stale]
+    fn set_stale(&mut self, stale: bool) {
+        self.stale = stale.then_some(true);
+    }
+}
+
+/// Get the GitHub credentials provided in the environment (tokens and/or
+/// GitHub App installation), along with the name used to identify them.
+fn get_credentials(api_url: &str) -> Result<Vec<(String, GithubCredentials)>> {
+    let mut credentials = vec![];
+
+    // Tokens
+    if let Ok(tokens) = env::var(GITHUB_TOKENS) {
+        for (i, token) in tokens.split(',').filter(|token| !token.is_empty()).enumerate() {
+            credentials.push((
+                format!("token{}", i + 1),
+                GithubCredentials::Token(token.to_string()),
+            ));
+        }
+    }
+
+    // GitHub App installation
+    let app_id = env::var(GITHUB_APP_ID).ok().filter(|v| !v.is_empty());
+    let installation_id = env::var(GITHUB_APP_INSTALLATION_ID).ok().filter(|v| !v.is_empty());
+    let private_key_file = env::var(GITHUB_APP_PRIVATE_KEY_FILE).ok().filter(|v| !v.is_empty());
+    match (app_id, installation_id, private_key_file) {
+        (None, None, None) => {}
+        (Some(app_id), Some(installation_id), Some(private_key_file)) => {
+            let private_key = fs::read(&private_key_file)
+                .with_context(|| format!("error reading github app private key file {private_key_file}"))?;
+            let app = GithubApp::new(&app_id, &installation_id, &private_key, api_url)?;
+            credentials.push(("app".to_string(), GithubCredentials::App(Arc::new(app))));
+        }
+        _ => bail!(
+            "incomplete github app credentials: {GITHUB_APP_ID}, {GITHUB_APP_INSTALLATION_ID} and \
+             {GITHUB_APP_PRIVATE_KEY_FILE} must be provided"
+        ),
+    }
+
+    Ok(credentials)
+}
+
+/// Credentials used to authenticate the requests to the GitHub API.
+#[derive(Clone)]
+enum GithubCredentials {
+    /// Personal access token.
+    Token(String),
+
+    /// GitHub App installation.
+    App(Arc<GithubApp>),
+}
+
+impl GithubCredentials {
+    /// Get the token to use in the requests to the GitHub API.
+    async fn token(&self) -> Result<String> {
+        match self {
+            GithubCredentials::Token(token) => Ok(token.clone()),
+            GithubCredentials::App(app) => app.installation_token().await,
+        }
+    }
+}
+
+/// GitHub App installation used to authenticate the requests to the GitHub
+/// API. Installation tokens are minted when needed, and refreshed before they
+/// expire.
+struct GithubApp {
+    api_url: String,
+    app_id: String,
+    installation_id: String,
+    encoding_key: EncodingKey,
+    http_client: reqwest::Client,
+    token: tokio::sync::Mutex<Option<InstallationToken>>,
+}
+
+/// GitHub App installation token.
+#[derive(Debug, Clone, Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl GithubApp {
+    /// Create a new GithubApp instance.
+    fn new(app_id: &str, installation_id: &str, private_key: &[u8], api_url: &str) -> Result<Self> {
+        if app_id.parse::<u64>().is_err() {
+            bail!("invalid github app id: {app_id}");
+        }
+        if installation_id.parse::<u64>().is_err() {
+            bail!("invalid github app installation id: {installation_id}");
+        }
+        let encoding_key =
+            EncodingKey::from_rsa_pem(private_key).context("invalid github app private key")?;
+
+        Ok(Self {
+            api_url: api_url.to_string(),
+            app_id: app_id.to_string(),
+            installation_id: installation_id.to_string(),
+            encoding_key,
+            http_client: new_http_client()?,
+            token: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Get an installation token, minting a new one if there is no token
+    /// available yet or it is about to expire.
+    async fn installation_token(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some(token) = token.as_ref() {
+            if token.expires_at - GITHUB_APP_TOKEN_REFRESH_MARGIN > Utc::now() {
+                return Ok(token.token.clone());
+            }
+        }
+        let new_token = self.mint_installation_token().await?;
+        *token = Some(new_token.clone());
+        Ok(new_token.token)
+    }
+
+    /// Mint a new installation token.
+    #[instrument(skip(self), fields(installation_id = self.installation_id), err)]
+    async fn mint_installation_token(&self) -> Result<InstallationToken> {
+        debug!("minting github app installation token");
+        let url = format!(
+            "{}/app/installations/{}/access_tokens",
+            self.api_url, self.installation_id
+        );
+        let token = self
+            .http_client
+            .post(url)
+            .bearer_auth(self.jwt()?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(token)
+    }
+
+    /// Create a JWT to authenticate as the GitHub App (valid for 9 minutes,
+    /// issued a minute ago to allow for clock differences with GitHub).
+    fn jwt(&self) -> Result<String> {
+        let now = Utc::now().timestamp();
+        let claims = json!({
+            "iat": now - 60,
+            "exp": now + 9 * 60,
+            "iss": self.app_id,
+        });
+        let jwt = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.encoding_key)?;
+        Ok(jwt)
+    }
+}
+
+/// GH client in the pool, along with the quota of the token it uses.
+struct GHClient {
+    gh: DynGH,
+    quota: Arc<TokenQuota>,
+}
+
+/// Get a GH client from the pool whose token has some quota available. Tokens
+/// that have exhausted their quota (or have been paused after hitting a
+/// secondary rate limit) are kept out of the pool until they are available
+/// again. Tokens that won't be available within the maximum wait provided are
+/// removed from the pool, which is closed once none is left.
+async fn get_gh_client(gh_pool: &Pool<GHClient>, max_wait: Duration) -> Result<Object<GHClient>> {
+    loop {
+        let gh_client = gh_pool
+            .get()
+            .await
+            .map_err(|_| format_err!("no github tokens available (all rate limited)"))?;
+        let Some(available_at) = gh_client.quota.available_at() else {
+            return Ok(gh_client);
+        };
+
+        let wait = (available_at - Utc::now()).to_std().unwrap_or_default();
+        if wait > max_wait {
+            let gh_client = Object::take(gh_client);
+            warn!(
+                token = gh_client.quota.name,
+                %available_at,
+                "github token rate limited for longer than the maximum wait, it won't be used again"
+            );
+            if gh_pool.status().size == 0 {
+                gh_pool.close();
+            }
+            continue;
+        }
+
+        warn!(
+            token = gh_client.quota.name,
+            %available_at,
+            "github token rate limited, it won't be used until its quota is available again"
+        );
+        tokio::spawn(async move {
+            tokio::time::sleep(wait).await;
+            drop(gh_client);
+        });
+    }
+}
+
+/// Refresh the quotas provided concurrently.
+async fn refresh_quotas(quotas: &[Arc<TokenQuota>]) {
+    future::join_all(quotas.iter().map(|quota| async move {
+        if let Err(err) = quota.refresh().await {
+            warn!(?err, token = quota.name, "error refreshing github token quota");
+        }
+    }))
+    .await;
+}
+
+/// Rate limit quota of a GitHub token. It is updated from the rate limit
+/// headers included in the responses to the requests made with the token.
+struct TokenQuota {
+    name: String,
+    api_url: String,
+    credentials: GithubCredentials,
+    http_client: reqwest::Client,
+    resources: Vec<String>,
+    state: Mutex<TokenQuotaState>,
+}
+
+/// Mutable state of a token quota.
+#[derive(Debug, Default)]
+struct TokenQuotaState {
+    paused_until: Option<DateTime<Utc>>,
+    rate_limited: usize,
+    resources: BTreeMap<String, ResourceQuota>,
+}
+
+impl TokenQuota {
+    /// Create a new TokenQuota instance. Only the quota of the API resources
+    /// used by the GitHub API implementation selected will be tracked.
+    fn new(name: &str, credentials: GithubCredentials, options: &GithubOptions) -> Result<Self> {
+        let mut resources = vec!["core".to_string()];
+        if options.api == GithubApi::Graphql {
+            resources.push("graphql".to_string());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            api_url: options.api_url.clone(),
+            credentials,
+            http_client: new_http_client()?,
+            resources,
+            state: Mutex::new(TokenQuotaState::default()),
+        })
+    }
+
+    /// Return when the token will be available again, or None if it can be
+    /// used right away.
+    fn available_at(&self) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        let state = self.state();
+        let mut available_at = state.paused_until.filter(|paused_until| *paused_until > now);
+        for quota in state.resources.values() {
+            if quota.remaining <= GITHUB_RATE_LIMIT_RESERVE && quota.reset > now {
+                // Allow some margin for clock differences with GitHub
+                available_at = available_at.max(Some(quota.reset + chrono::Duration::seconds(1)));
+            }
+        }
+        available_at
+    }
+
+    /// Record that a request made with the token was rate limited, pausing
+    /// the token for the duration provided. When GitHub does not specify how
+    /// long to wait, the quota is refreshed to check if it has been exhausted
+    /// (the token will be paused until it is reset). Otherwise a secondary
+    /// rate limit was hit, and the token is paused for a while.
+    async fn rate_limited(&self, retry_after: Option<Duration>) {
+        let retry_after = if retry_after.is_some() {
+            retry_after
+        } else {
+            if let Err(err) = self.refresh().await {
+                warn!(?err, token = self.name, "error refreshing github token quota");
+            }
+            self.available_at().is_none().then_some(GITHUB_SECONDARY_RATE_LIMIT_BACKOFF)
+        };
+
+        let mut state = self.state();
+        state.rate_limited += 1;
+        if let Some(retry_after) = retry_after {
+            let paused_until = Utc::now() + chrono::Duration::from_std(retry_after).unwrap_or_default();
+            state.paused_until = state.paused_until.max(Some(paused_until));
+        }
+    }
+
+    /// Refresh the token quota using the rate limit API endpoint (requests to
+    /// this endpoint do not count against the quota).
+    #[instrument(skip(self), fields(token = self.name), err)]
+    async fn refresh(&self) -> Result<()> {
+        let url = format!("{}/rate_limit", self.api_url);
+        let token = self.credentials.token().await?;
+        let request = self.http_client.get(url).bearer_auth(token);
+        let rate_limit: RateLimitResponse = request.send().await?.error_for_status()?.json().await?;
+
+        let mut state = self.state();
+        for (resource, quota) in rate_limit.resources {
+            if self.resources.contains(&resource) {
+                state.resources.insert(resource, quota);
+            }
+        }
+        Ok(())
+    }
+
+    /// Return a mutable reference to the token quota state.
+    fn state(&self) -> MutexGuard<'_, TokenQuotaState> {
+        self.state.lock().expect("token quota lock not to be poisoned")
+    }
+
+    /// Update the token quota from the rate limit headers provided.
+    fn update_from_headers(&self, headers: &HeaderMap) {
+        let resource = header_value::<String>(headers, "x-ratelimit-resource").unwrap_or("core".to_string());
+        if !self.resources.contains(&resource) {
+            return;
+        }
+        let (Some(limit), Some(remaining), Some(used), Some(reset)) = (
+            header_value(headers, "x-ratelimit-limit"),
+            header_value(headers, "x-ratelimit-remaining"),
+            header_value(headers, "x-ratelimit-used"),
+            header_value(headers, "x-ratelimit-reset").and_then(|reset| DateTime::from_timestamp(reset, 0)),
+        ) else {
+            return;
+        };
+        self.state().resources.insert(
+            resource,
+            ResourceQuota {
+                limit,
+                remaining,
+                used,
+                reset,
+            },
+        );
+    }
+
+    /// Return the token quota usage.
+    fn usage(&self) -> TokenUsage {
+        let state = self.state();
+        TokenUsage {
+            token: self.name.clone(),
+            rate_limited: state.rate_limited,
+            resources: state.resources.clone(),
+        }
+    }
+}
+
+/// Quota usage of a GitHub token, included in the build report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct TokenUsage {
+    pub token: String,
+    pub rate_limited: usize,
+    pub resources: BTreeMap<String, ResourceQuota>,
+}
+
+/// Rate limit quota of a GitHub API resource (i.e. core or graphql).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ResourceQuota {
+    pub limit: i64,
+    pub remaining: i64,
+    pub used: i64,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub reset: DateTime<Utc>,
+}
+
+/// Rate limit API endpoint response.
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    resources: HashMap<String, ResourceQuota>,
+}
+
+/// Error returned when a request to the GitHub API is rate limited.
+#[derive(Debug, Clone, PartialEq)]
+struct RateLimitError {
+    retry_after: Option<Duration>,
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.retry_after {
+            Some(retry_after) => write!(
+                f,
+                "github rate limit hit (retry after {}s)",
+                retry_after.as_secs()
+            ),
+            None => write!(f, "github rate limit hit"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimitError {}
+
+/// Send the request provided authenticated with the credentials given,
+/// updating the token quota from the response headers. An error is returned if
+/// the request was rate limited.
+async fn send_request(
+    credentials: &GithubCredentials,
+    quota: &TokenQuota,
+    request: RequestBuilder,
+) -> Result<reqwest::Response> {
+    let response = request.bearer_auth(credentials.token().await?).send().await?;
+    quota.update_from_headers(response.headers());
+    if let Some(err) = rate_limit_error(response.status(), response.headers()) {
+        return Err(err.into());
+    }
+    Ok(response)
+}
+
+/// Check if the response status and headers provided correspond to a rate
+/// limited request, returning the corresponding error if so.
+fn rate_limit_error(status: StatusCode, headers: &HeaderMap) -> Option<RateLimitError> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    // Secondary rate limits usually specify how long to wait
+    if let Some(retry_after) = header_value(headers, "retry-after") {
+        return Some(RateLimitError {
+            retry_after: Some(Duration::from_secs(retry_after)),
+        });
+    }
+
+    // Primary rate limit exhausted, wait until it is reset
+    if header_value::<i64>(headers, "x-ratelimit-remaining") == Some(0) {
+        let retry_after = header_value::<i64>(headers, "x-ratelimit-reset")
+            .map(|reset| Duration::from_secs(u64::try_from(reset - Utc::now().timestamp()).unwrap_or(0)));
+        return Some(RateLimitError { retry_after });
+    }
+
+    (status == StatusCode::TOO_MANY_REQUESTS).then_some(RateLimitError { retry_after: None })
+}
+
+/// Convert the octorust error provided into an anyhow error, identifying the
+/// rate limit errors.
+fn octorust_error<E>(err: E) -> anyhow::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let message = err.to_string().to_lowercase();
+    if !message.contains("rate limit") {
+        return err.into();
+    }
+    let retry_after = RETRY_AFTER_SECONDS
+        .captures(&message)
+        .and_then(|c| c["seconds"].parse().ok())
+        .map(Duration::from_secs);
+    anyhow::Error::new(err).context(RateLimitError { retry_after })
+}
+
+/// Parse the value of the header provided.
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Default GitHub API base url.
 const GITHUB_API_URL: &str = "https://api.github.com";
 
+/// Default GitHub repository url regular expression.
+pub(crate) const GITHUB_REPO_URL_PATTERN: &str = r"^https://github\.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/?$";
+
 /// Type alias to represent a GH trait object.
 type DynGH = Box<dyn GH + Send + Sync>;
 
 /// Trait that defines some operations a GH implementation must support.
-#[async_trait]
 #[cfg_attr(test, automock)]
+#[async_trait]
 trait GH {
     /// Get number of repository contributors.
     async fn get_contributors_count(&self, owner: &str, repo: &str) -> Result<usize>;
@@ -191,53 +930,111 @@ trait GH {
     /// Get latest release.
     async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<Release>>;
 
-    /// Get participation stats.
+    /// Get participation stats. They always refer to the repository default
+    /// branch, as the GitHub API does not support selecting another one.
     async fn get_participation_stats(&self, owner: &str, repo: &str) -> Result<ParticipationStats>;
 
+    /// Get the time (in seconds) it took to merge each of the pull requests
+    /// merged since the date provided.
+    async fn get_pull_requests_merge_times(
+        &self,
+        owner: &str,
+        repo: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<i64>>;
+
+    /// Get number of releases published since the date provided.
+    async fn get_releases_count(&self, owner: &str, repo: &str, since: DateTime<Utc>) -> Result<usize>;
+
     /// Get repository.
-    async fn get_repository(&self, owner: &str, repo: &str) -> Result<FullRepository>;
+    async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository>;
+
+    /// Prefetch the data of the repositories provided, so that it's readily
+    /// available when requested (not all implementations support it).
+    async fn prefetch(&self, repos: &[(String, String)]) -> Result<()>;
+}
+
+/// Repository information returned by the GH implementations.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Repository {
+    archived: bool,
+    default_branch: String,
+    description: String,
+    disabled: bool,
+    forks: i64,
+    license: Option<String>,
+    open_issues: i64,
+    open_pull_requests: i64,
+    stars: i64,
+    topics: Vec<String>,
+    url: String,
+    watchers: i64,
 }
 
 /// GH implementation backed by the GitHub API.
 struct GHApi {
-    gh_client: octorust::Client,
+    api_url: String,
+    credentials: GithubCredentials,
+    gh_client: Mutex<Option<(String, octorust::Client)>>,
     http_client: reqwest::Client,
+    quota: Arc<TokenQuota>,
 }
 
 impl GHApi {
     /// Create a new GHApi instance.
-    fn new(token: &str) -> Result<Self> {
-        // Setup octorust GitHub API client
-        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
-        let gh_client = octorust::Client::custom(
-            user_agent.clone(),
-            Credentials::Token(token.to_string()),
-            reqwest_middleware::ClientBuilder::new(reqwest_octorust::Client::builder().build()?).build(),
-        );
-
+    fn new(credentials: GithubCredentials, quota: Arc<TokenQuota>, api_url: &str) -> Result<Self> {
         // Setup HTTP client ready to make requests to the GitHub API
         // (for some operations that cannot be done with the octorust client)
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            header::ACCEPT,
-            HeaderValue::from_str("application/vnd.github+json").unwrap(),
-        );
-        headers.insert(
-            header::AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
-        );
-        headers.insert(
-            "X-GitHub-Api-Version",
-            HeaderValue::from_str("2022-11-28").unwrap(),
-        );
-        let http_client =
-            reqwest::Client::builder().user_agent(user_agent).default_headers(headers).build()?;
+        let http_client = new_http_client()?;
 
         Ok(Self {
-            gh_client,
+            api_url: api_url.to_string(),
+            credentials,
+            gh_client: Mutex::new(None),
             http_client,
+            quota,
         })
     }
+
+    /// Get an octorust GitHub API client using the current token. A new
+    /// client is set up when the token changes (GitHub App installation
+    /// tokens are refreshed periodically).
+    async fn gh_client(&self) -> Result<octorust::Client> {
+        let token = self.credentials.token().await?;
+        let mut gh_client = self.gh_client.lock().expect("gh client lock not to be poisoned");
+        if let Some((client_token, client)) = gh_client.as_ref() {
+            if *client_token == token {
+                return Ok(client.clone());
+            }
+        }
+
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let mut client = octorust::Client::custom(
+            user_agent,
+            Credentials::Token(token.clone()),
+            reqwest_middleware::ClientBuilder::new(reqwest_octorust::Client::builder().build()?).build(),
+        );
+        client.with_host_override(&self.api_url);
+        *gh_client = Some((token, client.clone()));
+        Ok(client)
+    }
+}
+
+/// Create a new HTTP client ready to make requests to the GitHub API (the
+/// authorization header is added to each request, as tokens may change).
+fn new_http_client() -> Result<reqwest::Client> {
+    let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::ACCEPT,
+        HeaderValue::from_str("application/vnd.github+json").unwrap(),
+    );
+    headers.insert(
+        "X-GitHub-Api-Version",
+        HeaderValue::from_str("2022-11-28").unwrap(),
+    );
+    let http_client = reqwest::Client::builder().user_agent(user_agent).default_headers(headers).build()?;
+    Ok(http_client)
 }
 
 #[async_trait]
@@ -245,8 +1042,11 @@ impl GH for GHApi {
     /// [GH::get_contributors_count]
     #[instrument(skip(self), err)]
     async fn get_contributors_count(&self, owner: &str, repo: &str) -> Result<usize> {
-        let url = format!("{GITHUB_API_URL}/repos/{owner}/{repo}/contributors?per_page=1&anon=true");
-        let response = self.http_client.head(url).send().await?;
+        let url = format!(
+            "{}/repos/{owner}/{repo}/contributors?per_page=1&anon=true",
+            self.api_url
+        );
+        let response = send_request(&self.credentials, &self.quota, self.http_client.head(url)).await?;
         let count = get_last_page(response.headers())?.unwrap_or(1);
         Ok(count)
     }
@@ -256,16 +1056,22 @@ impl GH for GHApi {
     #[instrument(skip(self), err)]
     async fn get_first_commit(&self, owner: &str, repo: &str, ref_: &str) -> Result<Option<Commit>> {
         // Get last commits page
-        let url = format!("{GITHUB_API_URL}/repos/{owner}/{repo}/commits?sha={ref_}&per_page=1");
-        let response = self.http_client.head(url).send().await?;
+        let url = format!(
+            "{}/repos/{owner}/{repo}/commits?sha={}&per_page=1",
+            self.api_url,
+            utf8_percent_encode(ref_, NON_ALPHANUMERIC)
+        );
+        let response = send_request(&self.credentials, &self.quota, self.http_client.head(url)).await?;
         let last_page = get_last_page(response.headers())?.unwrap_or(1);
 
         // Get first repository commit and return it if found
         if let Some(commit) = self
-            .gh_client
+            .gh_client()
+            .await?
             .repos()
             .list_commits(owner, repo, ref_, "", "", None, None, 1, last_page as i64)
-            .await?
+            .await
+            .map_err(octorust_error)?
             .body
             .pop()
         {
@@ -277,28 +1083,38 @@ impl GH for GHApi {
     /// [GH::get_languages]
     #[instrument(skip(self), err)]
     async fn get_languages(&self, owner: &str, repo: &str) -> Result<Option<BTreeMap<String, i64>>> {
-        let url = format!("{GITHUB_API_URL}/repos/{owner}/{repo}/languages");
-        let languages: BTreeMap<String, i64> = self.http_client.get(url).send().await?.json().await?;
+        let url = format!("{}/repos/{owner}/{repo}/languages", self.api_url);
+        let languages: BTreeMap<String, i64> =
+            send_request(&self.credentials, &self.quota, self.http_client.get(url))
+                .await?
+                .json()
+                .await?;
         Ok(Some(languages))
     }
 
     /// [GH::get_latest_commit]
     #[instrument(skip(self), err)]
     async fn get_latest_commit(&self, owner: &str, repo: &str, ref_: &str) -> Result<Commit> {
-        let response = self.gh_client.repos().get_commit(owner, repo, 1, 1, ref_).await?;
+        let response = self
+            .gh_client()
+            .await?
+            .repos()
+            .get_commit(owner, repo, 1, 1, ref_)
+            .await
+            .map_err(octorust_error)?;
         Ok(new_commit_from(response.body))
     }
 
     /// [GH::get_latest_release]
     #[instrument(skip(self), err)]
     async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<Release>> {
-        match self.gh_client.repos().get_latest_release(owner, repo).await {
+        match self.gh_client().await?.repos().get_latest_release(owner, repo).await {
             Ok(response) => Ok(Some(new_release_from(response.body))),
             Err(err) => {
                 if err.to_string().to_lowercase().contains("not found") {
                     return Ok(None);
                 }
-                Err(err.into())
+                Err(octorust_error(err))
             }
         }
     }
@@ -306,23 +1122,506 @@ impl GH for GHApi {
     /// [GH::get_participation_stats]
     #[instrument(skip(self), err)]
     async fn get_participation_stats(&self, owner: &str, repo: &str) -> Result<ParticipationStats> {
-        let response = self.gh_client.repos().get_participation_stats(owner, repo).await?;
+        let response = self
+            .gh_client()
+            .await?
+            .repos()
+            .get_participation_stats(owner, repo)
+            .await
+            .map_err(octorust_error)?;
         Ok(response.body)
     }
 
+    /// [GH::get_pull_requests_merge_times]
+    #[instrument(skip(self), err)]
+    async fn get_pull_requests_merge_times(
+        &self,
+        owner: &str,
+        repo: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<i64>> {
+        // Pull requests are listed from the most recently updated one, so we
+        // can stop as soon as one was last updated before the date provided
+        let mut pull_requests = vec![];
+        for page in 1..=GITHUB_MAX_PAGES {
+            let url = format!(
+                "{}/repos/{owner}/{repo}/pulls?state=closed&sort=updated&direction=desc&per_page=100&page={page}",
+                self.api_url
+            );
+            let page_pull_requests: Vec<RestPullRequest> =
+                send_request(&self.credentials, &self.quota, self.http_client.get(url))
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+            let done =
+                page_pull_requests.len() < 100 || page_pull_requests.iter().any(|pr| pr.updated_at < since);
+            pull_requests.extend(
+                page_pull_requests
+                    .into_iter()
+                    .filter_map(|pr| pr.merged_at.map(|merged_at| (pr.created_at, merged_at))),
+            );
+            if done {
+                break;
+            }
+        }
+        Ok(merge_times(pull_requests, since))
+    }
+
+    /// [GH::get_releases_count]
+    #[instrument(skip(self), err)]
+    async fn get_releases_count(&self, owner: &str, repo: &str, since: DateTime<Utc>) -> Result<usize> {
+        // Releases are listed from the most recent one
+        let mut count = 0;
+        for page in 1..=GITHUB_MAX_PAGES {
+            let url = format!(
+                "{}/repos/{owner}/{repo}/releases?per_page=100&page={page}",
+                self.api_url
+            );
+            let releases: Vec<RestRelease> =
+                send_request(&self.credentials, &self.quota, self.http_client.get(url))
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+            let page_count = releases_count(releases.iter().map(|release| release.published_at), since);
+            count += page_count;
+            if releases.len() < 100 || page_count < releases.len() {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
     /// [GH::get_repository]
+    #[allow(clippy::cast_possible_wrap)]
     #[instrument(skip(self), err)]
-    async fn get_repository(&self, owner: &str, repo: &str) -> Result<FullRepository> {
-        let response = self.gh_client.repos().get(owner, repo).await?;
-        Ok(response.body)
+    async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let response = self.gh_client().await?.repos().get(owner, repo).await.map_err(octorust_error)?;
+        let mut repository = new_repository_from(response.body);
+
+        // The open issues count returned by the REST API includes the open
+        // pull requests, so we need to get them separately
+        let url = format!(
+            "{}/repos/{owner}/{repo}/pulls?state=open&per_page=1",
+            self.api_url
+        );
+        let response = send_request(&self.credentials, &self.quota, self.http_client.get(url))
+            .await?
+            .error_for_status()?;
+        let open_pull_requests = match get_last_page(response.headers())? {
+            Some(last_page) => last_page,
+            None => response.json::<Vec<serde_json::Value>>().await?.len(),
+        } as i64;
+        repository.open_issues -= open_pull_requests;
+        repository.open_pull_requests = open_pull_requests;
+
+        Ok(repository)
+    }
+
+    /// [GH::prefetch]
+    async fn prefetch(&self, _repos: &[(String, String)]) -> Result<()> {
+        // Prefetching is not supported by the REST API implementation
+        Ok(())
     }
 }
 
+/// Repositories data prefetched using the GraphQL API, keyed by owner/repo.
+/// It is shared by all the GHGraphQLApi instances in the pool.
+type PrefetchedRepositories = Arc<Mutex<HashMap<String, PrefetchedRepository>>>;
+
+/// Repository data prefetched using the GraphQL API.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PrefetchedRepository {
+    languages: BTreeMap<String, i64>,
+    latest_commit: Option<Commit>,
+    latest_release: Option<Release>,
+    merged_pull_requests: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    merged_pull_requests_has_next_page: bool,
+    merged_pull_requests_last_updated_at: Option<DateTime<Utc>>,
+    releases: Vec<Option<DateTime<Utc>>>,
+    releases_has_next_page: bool,
+    repository: Repository,
+}
+
+/// GH implementation backed by the GitHub GraphQL API. Most of the data is
+/// fetched for many repositories in a single request. The REST API is used
+/// for the data the GraphQL API does not provide (contributors count, first
+/// commit and participation stats).
+struct GHGraphQLApi {
+    credentials: GithubCredentials,
+    graphql_api_url: String,
+    http_client: reqwest::Client,
+    prefetched: PrefetchedRepositories,
+    quota: Arc<TokenQuota>,
+    rest: DynGH,
+}
+
+impl GHGraphQLApi {
+    /// Create a new GHGraphQLApi instance.
+    fn new(
+        credentials: GithubCredentials,
+        quota: Arc<TokenQuota>,
+        api_url: &str,
+        prefetched: PrefetchedRepositories,
+    ) -> Result<Self> {
+        Ok(Self {
+            credentials: credentials.clone(),
+            graphql_api_url: graphql_api_url(api_url),
+            http_client: new_http_client()?,
+            prefetched,
+            quota: quota.clone(),
+            rest: Box::new(GHApi::new(credentials, quota, api_url)?),
+        })
+    }
+
+    /// Fetch the data of the repositories provided using the GraphQL API and
+    /// store it in the prefetched repositories.
+    #[instrument(skip_all, err)]
+    async fn fetch(&self, repos: &[(String, String)]) -> Result<()> {
+        let request = self.http_client.post(&self.graphql_api_url).json(&build_graphql_query(repos));
+        let response: GraphQLResponse = send_request(&self.credentials, &self.quota, request)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        // Primary rate limit errors are returned with a successful status
+        if let Some(errors) = &response.errors {
+            if errors.iter().any(|e| e.kind.as_deref() == Some("RATE_LIMITED")) {
+                return Err(RateLimitError { retry_after: None }.into());
+            }
+        }
+
+        // Errors for some of the repositories (i.e. not found) are returned
+        // along with the data of the rest of them
+        let errors: Vec<String> =
+            response.errors.unwrap_or_default().into_iter().map(|e| e.message).collect();
+        let Some(mut data) = response.data else {
+            bail!("graphql query failed: {}", errors.join(", "));
+        };
+        if !errors.is_empty() {
+            debug!(?errors, "some errors found in graphql query response");
+        }
+
+        let mut prefetched = self.prefetched.lock().expect("prefetched lock not to be poisoned");
+        for (i, (owner, repo)) in repos.iter().enumerate() {
+            if let Some(Some(gql_repo)) = data.remove(&format!("repo{i}")) {
+                prefetched.insert(
+                    format!("{owner}/{repo}"),
+                    new_prefetched_repository_from(gql_repo),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the prefetched data of the repository provided, fetching it if it
+    /// is not available yet.
+    async fn get_prefetched(&self, owner: &str, repo: &str) -> Result<PrefetchedRepository> {
+        let key = format!("{owner}/{repo}");
+        if let Some(prefetched_repo) = self.prefetched_repo(&key) {
+            return Ok(prefetched_repo);
+        }
+        self.fetch(&[(owner.to_string(), repo.to_string())]).await?;
+        self.prefetched_repo(&key).ok_or_else(|| format_err!("repository {key} not found"))
+    }
+
+    /// Get the data of the repository provided from the prefetched data.
+    fn prefetched_repo(&self, key: &str) -> Option<PrefetchedRepository> {
+        self.prefetched.lock().expect("prefetched lock not to be poisoned").get(key).cloned()
+    }
+}
+
+#[async_trait]
+impl GH for GHGraphQLApi {
+    /// [GH::get_contributors_count]
+    async fn get_contributors_count(&self, owner: &str, repo: &str) -> Result<usize> {
+        self.rest.get_contributors_count(owner, repo).await
+    }
+
+    /// [GH::get_first_commit]
+    async fn get_first_commit(&self, owner: &str, repo: &str, ref_: &str) -> Result<Option<Commit>> {
+        self.rest.get_first_commit(owner, repo, ref_).await
+    }
+
+    /// [GH::get_languages]
+    async fn get_languages(&self, owner: &str, repo: &str) -> Result<Option<BTreeMap<String, i64>>> {
+        Ok(Some(self.get_prefetched(owner, repo).await?.languages))
+    }
+
+    /// [GH::get_latest_commit]
+    async fn get_latest_commit(&self, owner: &str, repo: &str, ref_: &str) -> Result<Commit> {
+        // Only the latest commit of the default branch is prefetched
+        let prefetched_repo = self.get_prefetched(owner, repo).await?;
+        match prefetched_repo.latest_commit {
+            Some(commit) if prefetched_repo.repository.default_branch == ref_ => Ok(commit),
+            _ => self.rest.get_latest_commit(owner, repo, ref_).await,
+        }
+    }
+
+    /// [GH::get_latest_release]
+    async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<Release>> {
+        Ok(self.get_prefetched(owner, repo).await?.latest_release)
+    }
+
+    /// [GH::get_participation_stats]
+    async fn get_participation_stats(&self, owner: &str, repo: &str) -> Result<ParticipationStats> {
+        self.rest.get_participation_stats(owner, repo).await
+    }
+
+    /// [GH::get_pull_requests_merge_times]
+    ///
+    /// Only the first page of the most recently updated merged pull requests
+    /// is prefetched. When some pull requests of the period considered may be
+    /// missing, the REST API is used instead (which reads up to the same
+    /// number of pages as when the REST API is used for all the data).
+    async fn get_pull_requests_merge_times(
+        &self,
+        owner: &str,
+        repo: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<i64>> {
+        let prefetched_repo = self.get_prefetched(owner, repo).await?;
+        if prefetched_repo.merged_pull_requests_has_next_page
+            && prefetched_repo.merged_pull_requests_last_updated_at.is_some_and(|ts| ts >= since)
+        {
+            return self.rest.get_pull_requests_merge_times(owner, repo, since).await;
+        }
+        Ok(merge_times(prefetched_repo.merged_pull_requests, since))
+    }
+
+    /// [GH::get_releases_count]
+    ///
+    /// Only the first page of the most recent releases is prefetched. When
+    /// all of them were published in the period considered, the REST API is
+    /// used instead to count the rest.
+    async fn get_releases_count(&self, owner: &str, repo: &str, since: DateTime<Utc>) -> Result<usize> {
+        let prefetched_repo = self.get_prefetched(owner, repo).await?;
+        let releases = prefetched_repo.releases.len();
+        let count = releases_count(prefetched_repo.releases.into_iter(), since);
+        if prefetched_repo.releases_has_next_page && count == releases {
+            return self.rest.get_releases_count(owner, repo, since).await;
+        }
+        Ok(count)
+    }
+
+    /// [GH::get_repository]
+    async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
+        Ok(self.get_prefetched(owner, repo).await?.repository)
+    }
+
+    /// [GH::prefetch]
+    #[instrument(skip_all, err)]
+    async fn prefetch(&self, repos: &[(String, String)]) -> Result<()> {
+        self.fetch(repos).await
+    }
+}
+
+/// GraphQL fragment with the repository fields fetched.
+const GRAPHQL_REPOSITORY_FIELDS: &str = "
+fragment RepositoryFields on Repository {
+    defaultBranchRef {
+        name
+        target {
+            ... on Commit {
+                author { date }
+                url
+            }
+        }
+    }
+    description
+    forkCount
+    isArchived
+    isDisabled
+    languages(first: 100, orderBy: { field: SIZE, direction: DESC }) {
+        edges { size node { name } }
+    }
+    latestRelease { publishedAt url }
+    licenseInfo { name }
+    mergedPullRequests: pullRequests(
+        states: MERGED, first: 100, orderBy: { field: UPDATED_AT, direction: DESC }
+    ) {
+        nodes { createdAt mergedAt updatedAt }
+        pageInfo { hasNextPage }
+    }
+    openIssues: issues(states: OPEN) { totalCount }
+    openPullRequests: pullRequests(states: OPEN) { totalCount }
+    releases(first: 100, orderBy: { field: CREATED_AT, direction: DESC }) {
+        nodes { publishedAt }
+        pageInfo { hasNextPage }
+    }
+    repositoryTopics(first: 100) { nodes { topic { name } } }
+    stargazerCount
+    url
+    watchers { totalCount }
+}";
+
+/// Build the GraphQL query used to fetch the data of the repositories
+/// provided (each repository is aliased as repoN).
+fn build_graphql_query(repos: &[(String, String)]) -> serde_json::Value {
+    let mut params = vec![];
+    let mut fields = vec![];
+    let mut variables = serde_json::Map::new();
+    for (i, (owner, repo)) in repos.iter().enumerate() {
+        params.push(format!("$owner{i}: String!, $repo{i}: String!"));
+        fields.push(format!(
+            "repo{i}: repository(owner: $owner{i}, name: $repo{i}) {{ ...RepositoryFields }}"
+        ));
+        variables.insert(format!("owner{i}"), owner.clone().into());
+        variables.insert(format!("repo{i}"), repo.clone().into());
+    }
+    let query = format!(
+        "query({}) {{ {} }}{GRAPHQL_REPOSITORY_FIELDS}",
+        params.join(", "),
+        fields.join(" ")
+    );
+    json!({ "query": query, "variables": variables })
+}
+
+/// GraphQL query response.
+#[derive(Debug, Deserialize)]
+struct GraphQLResponse {
+    data: Option<HashMap<String, Option<GraphQLRepository>>>,
+    errors: Option<Vec<GraphQLError>>,
+}
+
+/// GraphQL error.
+#[derive(Debug, Deserialize)]
+struct GraphQLError {
+    message: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+/// Repository data returned by the GraphQL API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLRepository {
+    default_branch_ref: Option<GraphQLRef>,
+    description: Option<String>,
+    fork_count: i64,
+    is_archived: bool,
+    is_disabled: bool,
+    languages: Option<GraphQLLanguages>,
+    latest_release: Option<GraphQLRelease>,
+    license_info: Option<GraphQLName>,
+    merged_pull_requests: GraphQLNodes<GraphQLPullRequest>,
+    open_issues: GraphQLCount,
+    open_pull_requests: GraphQLCount,
+    releases: GraphQLNodes<GraphQLReleaseDate>,
+    repository_topics: GraphQLTopics,
+    stargazer_count: i64,
+    url: String,
+    watchers: GraphQLCount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLCount {
+    total_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLNodes<T> {
+    nodes: Vec<T>,
+    #[serde(default)]
+    page_info: GraphQLPageInfo,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLPageInfo {
+    has_next_page: bool,
+}
+
+#[allow(clippy::struct_field_names)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLPullRequest {
+    created_at: DateTime<Utc>,
+    merged_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLReleaseDate {
+    published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLRef {
+    name: String,
+    target: Option<GraphQLCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLCommit {
+    author: Option<GraphQLGitActor>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLGitActor {
+    date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLLanguages {
+    edges: Vec<GraphQLLanguageEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLLanguageEdge {
+    node: GraphQLName,
+    size: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLRelease {
+    published_at: Option<DateTime<Utc>>,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLTopics {
+    nodes: Vec<GraphQLTopicNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLTopicNode {
+    topic: GraphQLName,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLName {
+    name: String,
+}
+
+/// Pull request data returned by the REST API.
+#[allow(clippy::struct_field_names)]
+#[derive(Debug, Deserialize)]
+struct RestPullRequest {
+    created_at: DateTime<Utc>,
+    merged_at: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Release data returned by the REST API.
+#[derive(Debug, Deserialize)]
+struct RestRelease {
+    published_at: Option<DateTime<Utc>>,
+}
+
 lazy_static! {
-    /// GitHub repository url regular expression.
-    pub(crate) static ref GITHUB_REPO_URL: Regex =
-        Regex::new("^https://github.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/?$")
-            .expect("exprs in GITHUB_REPO_URL to be valid");
+    /// Regular expression used to extract how long to wait from the rate
+    /// limit errors returned by the octorust client.
+    static ref RETRY_AFTER_SECONDS: Regex =
+        Regex::new(r"(?P<seconds>\d+) seconds").expect("exprs in RETRY_AFTER_SECONDS to be valid");
 }
 
 /// Return the last page of results available from the headers provided.
@@ -338,12 +1637,40 @@ fn get_last_page(headers: &HeaderMap) -> Result<Option<usize>> {
     Ok(None)
 }
 
+/// Return the time (in seconds) it took to merge each of the pull requests
+/// provided (creation and merge dates) merged since the date given.
+fn merge_times(
+    pull_requests: impl IntoIterator<Item = (DateTime<Utc>, DateTime<Utc>)>,
+    since: DateTime<Utc>,
+) -> Vec<i64> {
+    pull_requests
+        .into_iter()
+        .filter(|(_, merged_at)| *merged_at >= since)
+        .map(|(created_at, merged_at)| (merged_at - created_at).num_seconds())
+        .collect()
+}
+
+/// Count the releases published (publication dates provided) since the date
+/// given. Draft releases are not published, so they are not counted.
+fn releases_count(published_at: impl Iterator<Item = Option<DateTime<Utc>>>, since: DateTime<Utc>) -> usize {
+    published_at.filter(|published_at| published_at.is_some_and(|ts| ts >= since)).count()
+}
+
 /// Extract the owner and repository from the repository url provided.
-fn get_owner_and_repo(repo_url: &str) -> Result<(String, String)> {
-    let c = GITHUB_REPO_URL.captures(repo_url).ok_or_else(|| format_err!("invalid repository url"))?;
+pub(crate) fn get_owner_and_repo(repo_url_pattern: &Regex, repo_url: &str) -> Result<(String, String)> {
+    let c = repo_url_pattern.captures(repo_url).ok_or_else(|| format_err!("invalid repository url"))?;
     Ok((c["owner"].to_string(), c["repo"].to_string()))
 }
 
+/// Return the GraphQL API url corresponding to the GitHub API base url
+/// provided (GitHub Enterprise Server serves it at /api/graphql).
+fn graphql_api_url(api_url: &str) -> String {
+    match api_url.strip_suffix("/api/v3") {
+        Some(base_url) => format!("{base_url}/api/graphql"),
+        None => format!("{api_url}/graphql"),
+    }
+}
+
 /// Create a new commit instance from the octorust commit data provided.
 fn new_commit_from(value: octorust::types::CommitDataType) -> Commit {
     let mut commit = Commit {
@@ -363,3 +1690,747 @@ fn new_release_from(value: octorust::types::Release) -> Release {
         url: value.html_url,
     }
 }
+
+/// Create a new prefetched repository instance from the GraphQL repository
+/// data provided.
+fn new_prefetched_repository_from(value: GraphQLRepository) -> PrefetchedRepository {
+    let (default_branch, latest_commit) = match value.default_branch_ref {
+        Some(branch) => {
+            let latest_commit = branch.target.and_then(|commit| {
+                commit.url.map(|url| Commit {
+                    ts: commit.author.and_then(|author| author.date),
+                    url,
+                })
+            });
+            (branch.name, latest_commit)
+        }
+        None => (String::new(), None),
+    };
+
+    PrefetchedRepository {
+        languages: value
+            .languages
+            .map(|languages| languages.edges.into_iter().map(|edge| (edge.node.name, edge.size)).collect())
+            .unwrap_or_default(),
+        latest_commit,
+        latest_release: value.latest_release.map(|release| Release {
+            ts: release.published_at,
+            url: release.url,
+        }),
+        merged_pull_requests_has_next_page: value.merged_pull_requests.page_info.has_next_page,
+        merged_pull_requests_last_updated_at: value
+            .merged_pull_requests
+            .nodes
+            .last()
+            .and_then(|pr| pr.updated_at),
+        merged_pull_requests: value
+            .merged_pull_requests
+            .nodes
+            .into_iter()
+            .filter_map(|pr| pr.merged_at.map(|merged_at| (pr.created_at, merged_at)))
+            .collect(),
+        releases_has_next_page: value.releases.page_info.has_next_page,
+        releases: value.releases.nodes.into_iter().map(|release| release.published_at).collect(),
+        repository: Repository {
+            archived: value.is_archived,
+            default_branch,
+            description: value.description.unwrap_or_default(),
+            disabled: value.is_disabled,
+            forks: value.fork_count,
+            license: value.license_info.map(|license| license.name),
+            open_issues: value.open_issues.total_count,
+            open_pull_requests: value.open_pull_requests.total_count,
+            stars: value.stargazer_count,
+            topics: value.repository_topics.nodes.into_iter().map(|node| node.topic.name).collect(),
+            url: value.url,
+            watchers: value.watchers.total_count,
+        },
+    }
+}
+
+/// Create a new repository instance from the octorust repository data
+/// provided.
+fn new_repository_from(value: FullRepository) -> Repository {
+    Repository {
+        archived: value.archived,
+        default_branch: value.default_branch,
+        description: value.description,
+        disabled: value.disabled,
+        forks: value.forks_count,
+        license: value.license.map(|license| license.name),
+        // Includes the open pull requests, collected separately
+        open_issues: value.open_issues_count,
+        open_pull_requests: 0,
+        stars: value.stargazers_count,
+        topics: value.topics,
+        url: value.html_url,
+        watchers: value.subscribers_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::{always, eq};
+
+    const OWNER: &str = "owner";
+    const REPO: &str = "repo";
+    const REPO_URL: &str = "https://github.com/owner/repo";
+    const DEFAULT_BRANCH: &str = "main";
+
+    #[tokio::test]
+    async fn collect_repository_data_rest() {
+        let mut gh = MockGH::new();
+        gh.expect_get_repository()
+            .with(eq(OWNER), eq(REPO))
+            .times(1)
+            .returning(|_, _| Ok(sample_repository()));
+        expect_rest_only_calls(&mut gh);
+        gh.expect_get_languages()
+            .with(eq(OWNER), eq(REPO))
+            .times(1)
+            .returning(|_, _| Ok(Some(sample_languages())));
+        gh.expect_get_latest_commit()
+            .with(eq(OWNER), eq(REPO), eq(DEFAULT_BRANCH))
+            .times(1)
+            .returning(|_, _, _| Ok(sample_commit("latest")));
+        gh.expect_get_latest_release()
+            .with(eq(OWNER), eq(REPO))
+            .times(1)
+            .returning(|_, _| Ok(Some(sample_release())));
+        gh.expect_get_pull_requests_merge_times()
+            .with(eq(OWNER), eq(REPO), always())
+            .times(1)
+            .returning(|_, _, _| Ok(vec![10_800, 3600, 7200]));
+        gh.expect_get_releases_count()
+            .with(eq(OWNER), eq(REPO), always())
+            .times(1)
+            .returning(|_, _, _| Ok(4));
+
+        let gh: DynGH = Box::new(gh);
+        let repo_data = collect_repository_data(&gh, &sample_options().repo_url_pattern, REPO_URL, None)
+            .await
+            .unwrap();
+        assert_repository_data(&repo_data);
+    }
+
+    #[tokio::test]
+    async fn collect_repository_data_graphql_uses_prefetched_data() {
+        // Only the data not provided by the GraphQL API is requested to the
+        // REST API implementation
+        let mut rest = MockGH::new();
+        expect_rest_only_calls(&mut rest);
+
+        let now = Utc::now();
+        let days_ago = |days| now - chrono::Duration::days(days);
+        let prefetched = PrefetchedRepositories::default();
+        prefetched.lock().unwrap().insert(
+            format!("{OWNER}/{REPO}"),
+            PrefetchedRepository {
+                languages: sample_languages(),
+                latest_commit: Some(sample_commit("latest")),
+                latest_release: Some(sample_release()),
+                merged_pull_requests: vec![
+                    (days_ago(1), days_ago(1) + chrono::Duration::hours(3)),
+                    (days_ago(2), days_ago(2) + chrono::Duration::hours(1)),
+                    (days_ago(3), days_ago(3) + chrono::Duration::hours(2)),
+                    (days_ago(200), days_ago(100)), // Merged before the period considered
+                ],
+                merged_pull_requests_has_next_page: false,
+                merged_pull_requests_last_updated_at: Some(days_ago(100)),
+                releases: vec![
+                    Some(days_ago(1)),
+                    Some(days_ago(10)),
+                    None, // Draft release
+                    Some(days_ago(100)),
+                    Some(days_ago(300)),
+                    Some(days_ago(400)), // Published before the period considered
+                ],
+                releases_has_next_page: false,
+                repository: sample_repository(),
+            },
+        );
+        let gh: DynGH = Box::new(GHGraphQLApi {
+            credentials: sample_credentials(),
+            graphql_api_url: graphql_api_url(GITHUB_API_URL),
+            http_client: reqwest::Client::new(),
+            prefetched,
+            quota: Arc::new(sample_quota()),
+            rest: Box::new(rest),
+        });
+
+        let repo_data = collect_repository_data(&gh, &sample_options().repo_url_pattern, REPO_URL, None)
+            .await
+            .unwrap();
+        assert_repository_data(&repo_data);
+    }
+
+    #[tokio::test]
+    async fn collect_repository_data_uses_configured_branch() {
+        let mut gh = MockGH::new();
+        gh.expect_get_repository().returning(|_, _| Ok(sample_repository()));
+        gh.expect_get_contributors_count().returning(|_, _| Ok(10));
+        gh.expect_get_first_commit()
+            .with(eq(OWNER), eq(REPO), eq("dev"))
+            .times(1)
+            .returning(|_, _, _| Ok(Some(sample_commit("first"))));
+        gh.expect_get_languages().returning(|_, _| Ok(None));
+        gh.expect_get_latest_commit()
+            .with(eq(OWNER), eq(REPO), eq("dev"))
+            .times(1)
+            .returning(|_, _, _| Ok(sample_commit("dev")));
+        gh.expect_get_latest_release().returning(|_, _| Ok(None));
+        gh.expect_get_participation_stats().returning(|_, _| {
+            Ok(ParticipationStats {
+                all: vec![],
+                owner: vec![],
+            })
+        });
+        gh.expect_get_pull_requests_merge_times().returning(|_, _, _| Ok(vec![]));
+        gh.expect_get_releases_count().returning(|_, _, _| Ok(0));
+
+        let gh: DynGH = Box::new(gh);
+        let repo_data =
+            collect_repository_data(&gh, &sample_options().repo_url_pattern, REPO_URL, Some("dev"))
+                .await
+                .unwrap();
+        assert_eq!(repo_data.branch, Some("dev".to_string()));
+        assert_eq!(repo_data.latest_commit, sample_commit("dev"));
+    }
+
+    #[test]
+    fn add_repository_url_keeps_first_branch_set() {
+        let url = REPO_URL.to_string();
+        let repos = [
+            data::Repository {
+                url: url.clone(),
+                ..Default::default()
+            },
+            data::Repository {
+                url: url.clone(),
+                branch: Some("dev".to_string()),
+                ..Default::default()
+            },
+            data::Repository {
+                url: url.clone(),
+                branch: Some("other".to_string()),
+                ..Default::default()
+            },
+        ];
+        let mut urls = BTreeMap::new();
+        for repo in &repos {
+            add_repository_url(&mut urls, repo);
+        }
+        assert_eq!(urls, BTreeMap::from([(&url, Some(&"dev".to_string()))]));
+    }
+
+    #[test]
+    fn branch_matches_configured_branch() {
+        let cached_repo = RepositoryGithubData {
+            branch: Some(DEFAULT_BRANCH.to_string()),
+            ..Default::default()
+        };
+        assert!(branch_matches(&cached_repo, None));
+        assert!(branch_matches(&cached_repo, Some(&DEFAULT_BRANCH.to_string())));
+        assert!(!branch_matches(&cached_repo, Some(&"dev".to_string())));
+        assert!(!branch_matches(
+            &RepositoryGithubData::default(),
+            Some(&"dev".to_string())
+        ));
+    }
+
+    #[tokio::test]
+    async fn graphql_get_latest_commit_other_ref_falls_back_to_rest() {
+        let mut rest = MockGH::new();
+        rest.expect_get_latest_commit()
+            .with(eq(OWNER), eq(REPO), eq("other"))
+            .times(1)
+            .returning(|_, _, _| Ok(sample_commit("other")));
+
+        let prefetched = PrefetchedRepositories::default();
+        prefetched.lock().unwrap().insert(
+            format!("{OWNER}/{REPO}"),
+            PrefetchedRepository {
+                latest_commit: Some(sample_commit("latest")),
+                repository: sample_repository(),
+                ..Default::default()
+            },
+        );
+        let gh = GHGraphQLApi {
+            credentials: sample_credentials(),
+            graphql_api_url: graphql_api_url(GITHUB_API_URL),
+            http_client: reqwest::Client::new(),
+            prefetched,
+            quota: Arc::new(sample_quota()),
+            rest: Box::new(rest),
+        };
+
+        let commit = gh.get_latest_commit(OWNER, REPO, "other").await.unwrap();
+        assert_eq!(commit, sample_commit("other"));
+    }
+
+    #[tokio::test]
+    async fn graphql_truncated_pull_requests_and_releases_fall_back_to_rest() {
+        let since = Utc::now() - chrono::Duration::days(90);
+        let mut rest = MockGH::new();
+        rest.expect_get_pull_requests_merge_times()
+            .with(eq(OWNER), eq(REPO), eq(since))
+            .times(1)
+            .returning(|_, _, _| Ok(vec![1, 2, 3]));
+        rest.expect_get_releases_count()
+            .with(eq(OWNER), eq(REPO), eq(since))
+            .times(1)
+            .returning(|_, _, _| Ok(150));
+
+        // All the prefetched pull requests and releases are in the period
+        // considered, and there are more pages available
+        let prefetched = PrefetchedRepositories::default();
+        prefetched.lock().unwrap().insert(
+            format!("{OWNER}/{REPO}"),
+            PrefetchedRepository {
+                merged_pull_requests: vec![(since, since + chrono::Duration::hours(1))],
+                merged_pull_requests_has_next_page: true,
+                merged_pull_requests_last_updated_at: Some(since + chrono::Duration::days(1)),
+                releases: vec![Some(since + chrono::Duration::days(1))],
+                releases_has_next_page: true,
+                repository: sample_repository(),
+                ..Default::default()
+            },
+        );
+        let gh = GHGraphQLApi {
+            credentials: sample_credentials(),
+            graphql_api_url: graphql_api_url(GITHUB_API_URL),
+            http_client: reqwest::Client::new(),
+            prefetched,
+            quota: Arc::new(sample_quota()),
+            rest: Box::new(rest),
+        };
+
+        assert_eq!(
+            gh.get_pull_requests_merge_times(OWNER, REPO, since).await.unwrap(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(gh.get_releases_count(OWNER, REPO, since).await.unwrap(), 150);
+    }
+
+    #[test]
+    fn get_owner_and_repo_custom_pattern() {
+        let repo_url_pattern =
+            parse_repo_url_pattern("^https://github.example.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/?$")
+                .unwrap();
+        assert_eq!(
+            get_owner_and_repo(&repo_url_pattern, "https://github.example.com/owner/repo").unwrap(),
+            (OWNER.to_string(), REPO.to_string())
+        );
+        assert!(get_owner_and_repo(&repo_url_pattern, REPO_URL).is_err());
+    }
+
+    #[test]
+    fn graphql_api_url_from_api_url() {
+        assert_eq!(graphql_api_url(GITHUB_API_URL), "https://api.github.com/graphql");
+        assert_eq!(
+            graphql_api_url("https://github.example.com/api/v3"),
+            "https://github.example.com/api/graphql"
+        );
+        assert_eq!(
+            graphql_api_url("http://localhost:8080"),
+            "http://localhost:8080/graphql"
+        );
+    }
+
+    #[test]
+    fn parse_api_url_trims_trailing_slash() {
+        assert_eq!(
+            parse_api_url("https://github.example.com/api/v3/").unwrap(),
+            "https://github.example.com/api/v3"
+        );
+        assert!(parse_api_url("github.example.com").is_err());
+        assert!(parse_api_url("ftp://github.example.com").is_err());
+    }
+
+    #[test]
+    fn parse_repo_url_pattern_requires_named_groups() {
+        assert!(parse_repo_url_pattern(GITHUB_REPO_URL_PATTERN).is_ok());
+        assert_eq!(
+            parse_repo_url_pattern("^https://github.example.com/(?P<owner>[^/]+)/([^/]+)$")
+                .unwrap_err()
+                .to_string(),
+            "repo named group not found"
+        );
+        assert!(parse_repo_url_pattern("(").is_err());
+    }
+
+    #[test]
+    fn build_graphql_query_aliases_repositories() {
+        let repos = vec![
+            ("owner1".to_string(), "repo1".to_string()),
+            ("owner2".to_string(), "repo2".to_string()),
+        ];
+        let query = build_graphql_query(&repos);
+
+        let query_text = query["query"].as_str().unwrap();
+        assert!(query_text.starts_with(
+            "query($owner0: String!, $repo0: String!, $owner1: String!, $repo1: String!) { \
+            repo0: repository(owner: $owner0, name: $repo0) { ...RepositoryFields } \
+            repo1: repository(owner: $owner1, name: $repo1) { ...RepositoryFields } }"
+        ));
+        assert!(query_text.contains("fragment RepositoryFields on Repository"));
+        assert_eq!(
+            query["variables"],
+            json!({
+                "owner0": "owner1",
+                "repo0": "repo1",
+                "owner1": "owner2",
+                "repo1": "repo2",
+            })
+        );
+    }
+
+    #[test]
+    fn new_prefetched_repository_from_graphql_response() {
+        let response: GraphQLResponse = serde_json::from_value(json!({
+            "data": {
+                "repo0": {
+                    "defaultBranchRef": {
+                        "name": "main",
+                        "target": {
+                            "author": { "date": "2024-01-02T03:04:05+02:00" },
+                            "url": "https://github.com/owner/repo/commit/latest"
+                        }
+                    },
+                    "description": "description",
+                    "forkCount": 5,
+                    "isArchived": true,
+                    "isDisabled": false,
+                    "languages": {
+                        "edges": [
+                            { "size": 100, "node": { "name": "Rust" } },
+                            { "size": 10, "node": { "name": "TypeScript" } }
+                        ]
+                    },
+                    "latestRelease": {
+                        "publishedAt": "2024-01-01T00:00:00Z",
+                        "url": "https://github.com/owner/repo/releases/tag/v1.0.0"
+                    },
+                    "licenseInfo": { "name": "Apache License 2.0" },
+                    "mergedPullRequests": {
+                        "nodes": [
+                            {
+                                "createdAt": "2024-01-01T00:00:00Z",
+                                "mergedAt": "2024-01-01T01:00:00Z",
+                                "updatedAt": "2024-01-01T02:00:00Z"
+                            }
+                        ],
+                        "pageInfo": { "hasNextPage": true }
+                    },
+                    "openIssues": { "totalCount": 8 },
+                    "openPullRequests": { "totalCount": 2 },
+                    "releases": {
+                        "nodes": [{ "publishedAt": "2024-01-01T00:00:00Z" }, { "publishedAt": null }],
+                        "pageInfo": { "hasNextPage": false }
+                    },
+                    "repositoryTopics": { "nodes": [{ "topic": { "name": "topic1" } }] },
+                    "stargazerCount": 100,
+                    "url": "https://github.com/owner/repo",
+                    "watchers": { "totalCount": 3 }
+                },
+                "repo1": null
+            },
+            "errors": [{ "message": "Could not resolve to a Repository" }]
+        }))
+        .unwrap();
+
+        let mut data = response.data.unwrap();
+        assert!(data.remove("repo1").unwrap().is_none());
+        let prefetched_repo = new_prefetched_repository_from(data.remove("repo0").unwrap().unwrap());
+        assert_eq!(
+            prefetched_repo,
+            PrefetchedRepository {
+                languages: sample_languages(),
+                latest_commit: Some(Commit {
+                    ts: Some(DateTime::parse_from_rfc3339("2024-01-02T01:04:05Z").unwrap().into()),
+                    url: "https://github.com/owner/repo/commit/latest".to_string(),
+                }),
+                latest_release: Some(sample_release()),
+                merged_pull_requests: vec![(
+                    DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().into(),
+                    DateTime::parse_from_rfc3339("2024-01-01T01:00:00Z").unwrap().into(),
+                )],
+                merged_pull_requests_has_next_page: true,
+                merged_pull_requests_last_updated_at: Some(
+                    DateTime::parse_from_rfc3339("2024-01-01T02:00:00Z").unwrap().into()
+                ),
+                releases: vec![sample_release().ts, None],
+                releases_has_next_page: false,
+                repository: sample_repository(),
+            }
+        );
+    }
+
+    #[test]
+    fn merge_times_ignores_pull_requests_merged_before_since() {
+        let since: DateTime<Utc> = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().into();
+        let pull_requests = vec![
+            (
+                since - chrono::Duration::days(1),
+                since + chrono::Duration::hours(1),
+            ),
+            (
+                since - chrono::Duration::days(2),
+                since - chrono::Duration::hours(1),
+            ),
+        ];
+        assert_eq!(merge_times(pull_requests, since), vec![90_000]);
+    }
+
+    #[test]
+    fn releases_count_ignores_drafts_and_older_releases() {
+        let since: DateTime<Utc> = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().into();
+        let published_at = vec![Some(since), None, Some(since - chrono::Duration::days(1))];
+        assert_eq!(releases_count(published_at.into_iter(), since), 1);
+    }
+
+    #[test]
+    fn rate_limit_error_secondary_rate_limit() {
+        let headers = sample_headers(&[("retry-after", "30"), ("x-ratelimit-remaining", "100")]);
+        assert_eq!(
+            rate_limit_error(StatusCode::FORBIDDEN, &headers),
+            Some(RateLimitError {
+                retry_after: Some(Duration::from_secs(30))
+            })
+        );
+        assert_eq!(
+            rate_limit_error(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new()),
+            Some(RateLimitError { retry_after: None })
+        );
+    }
+
+    #[test]
+    fn rate_limit_error_primary_rate_limit_exhausted() {
+        let reset = (Utc::now().timestamp() + 120).to_string();
+        let headers = sample_headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", &reset)]);
+        let retry_after = rate_limit_error(StatusCode::FORBIDDEN, &headers).unwrap().retry_after.unwrap();
+        assert!(retry_after > Duration::from_secs(110) && retry_after <= Duration::from_secs(120));
+    }
+
+    #[test]
+    fn rate_limit_error_not_rate_limited() {
+        let headers = sample_headers(&[("x-ratelimit-remaining", "100")]);
+        assert_eq!(rate_limit_error(StatusCode::FORBIDDEN, &headers), None);
+        assert_eq!(rate_limit_error(StatusCode::NOT_FOUND, &headers), None);
+        assert_eq!(rate_limit_error(StatusCode::OK, &headers), None);
+    }
+
+    #[test]
+    fn token_quota_available_at_tracks_remaining_quota() {
+        let quota = sample_quota();
+        assert_eq!(quota.available_at(), None);
+
+        let reset = Utc::now().timestamp() + 600;
+        let headers = |remaining: &str| {
+            sample_headers(&[
+                ("x-ratelimit-limit", "5000"),
+                ("x-ratelimit-remaining", remaining),
+                ("x-ratelimit-used", "4000"),
+                ("x-ratelimit-reset", &reset.to_string()),
+                ("x-ratelimit-resource", "core"),
+            ])
+        };
+        quota.update_from_headers(&headers("1000"));
+        assert_eq!(quota.available_at(), None);
+
+        quota.update_from_headers(&headers(&GITHUB_RATE_LIMIT_RESERVE.to_string()));
+        assert_eq!(
+            quota.available_at(),
+            Some(DateTime::from_timestamp(reset + 1, 0).unwrap())
+        );
+        assert_eq!(
+            quota.usage().resources["core"].remaining,
+            GITHUB_RATE_LIMIT_RESERVE
+        );
+    }
+
+    #[tokio::test]
+    async fn token_quota_rate_limited_pauses_token() {
+        let quota = sample_quota();
+        quota.rate_limited(Some(Duration::from_secs(30))).await;
+
+        let available_at = quota.available_at().unwrap();
+        assert!(available_at > Utc::now() + chrono::Duration::seconds(25));
+        assert_eq!(quota.usage().rate_limited, 1);
+    }
+
+    #[tokio::test]
+    async fn get_gh_client_token_rate_limited_longer_than_max_wait() {
+        let quota = Arc::new(sample_quota());
+        quota.rate_limited(Some(Duration::from_secs(3600))).await;
+        let gh_pool = Pool::from(vec![GHClient {
+            gh: Box::new(MockGH::new()),
+            quota,
+        }]);
+
+        let err = get_gh_client(&gh_pool, Duration::from_secs(60)).await.err().unwrap();
+        assert_eq!(err.to_string(), "no github tokens available (all rate limited)");
+        assert!(get_gh_client(&gh_pool, Duration::from_secs(60)).await.is_err());
+    }
+
+    #[test]
+    fn octorust_error_identifies_rate_limits() {
+        let err = octorust_error(std::io::Error::other("Rate limited for the next 30 seconds"));
+        assert_eq!(
+            err.downcast_ref::<RateLimitError>(),
+            Some(&RateLimitError {
+                retry_after: Some(Duration::from_secs(30))
+            })
+        );
+
+        let err = octorust_error(std::io::Error::other("Not Found"));
+        assert!(err.downcast_ref::<RateLimitError>().is_none());
+    }
+
+    #[test]
+    fn github_app_new_invalid_credentials() {
+        let err = GithubApp::new("app", "1", b"", GITHUB_API_URL).err().unwrap();
+        assert_eq!(err.to_string(), "invalid github app id: app");
+
+        let err = GithubApp::new("1", "installation", b"", GITHUB_API_URL).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "invalid github app installation id: installation"
+        );
+
+        let err = GithubApp::new("1", "1", b"not a private key", GITHUB_API_URL).err().unwrap();
+        assert_eq!(err.to_string(), "invalid github app private key");
+    }
+
+    #[tokio::test]
+    async fn github_app_installation_token_reuses_valid_token() {
+        let app = sample_github_app(Some(InstallationToken {
+            token: "installation-token".to_string(),
+            expires_at: Utc::now() + chrono::Duration::minutes(30),
+        }));
+        let credentials = GithubCredentials::App(Arc::new(app));
+
+        assert_eq!(credentials.token().await.unwrap(), "installation-token");
+    }
+
+    fn expect_rest_only_calls(gh: &mut MockGH) {
+        gh.expect_get_contributors_count()
+            .with(eq(OWNER), eq(REPO))
+            .times(1)
+            .returning(|_, _| Ok(10));
+        gh.expect_get_first_commit()
+            .with(eq(OWNER), eq(REPO), eq(DEFAULT_BRANCH))
+            .times(1)
+            .returning(|_, _, _| Ok(Some(sample_commit("first"))));
+        gh.expect_get_participation_stats().with(eq(OWNER), eq(REPO)).times(1).returning(|_, _| {
+            Ok(ParticipationStats {
+                all: vec![1, 2, 3],
+                owner: vec![],
+            })
+        });
+    }
+
+    fn assert_repository_data(repo_data: &RepositoryGithubData) {
+        assert_eq!(
+            repo_data,
+            &RepositoryGithubData {
+                contributors: Contributors {
+                    count: 10,
+                    url: "https://github.com/owner/repo/graphs/contributors".to_string(),
+                },
+                description: "description".to_string(),
+                generated_at: repo_data.generated_at,
+                latest_commit: sample_commit("latest"),
+                participation_stats: vec![1, 2, 3],
+                stars: 100,
+                topics: vec!["topic1".to_string()],
+                url: REPO_URL.to_string(),
+                archived: Some(true),
+                branch: Some(DEFAULT_BRANCH.to_string()),
+                disabled: None,
+                first_commit: Some(sample_commit("first")),
+                forks: Some(5),
+                languages: Some(sample_languages()),
+                latest_release: Some(sample_release()),
+                license: Some("Apache License 2.0".to_string()),
+                open_issues: Some(8),
+                open_pull_requests: Some(2),
+                pull_requests_median_merge_time: Some(7200),
+                releases_last_year: Some(4),
+                stale: None,
+                watchers: Some(3),
+            }
+        );
+    }
+
+    fn sample_commit(sha: &str) -> Commit {
+        Commit {
+            ts: Some(DateTime::parse_from_rfc3339("2024-01-02T01:04:05Z").unwrap().into()),
+            url: format!("https://github.com/owner/repo/commit/{sha}"),
+        }
+    }
+
+    fn sample_credentials() -> GithubCredentials {
+        GithubCredentials::Token("token".to_string())
+    }
+
+    fn sample_github_app(token: Option<InstallationToken>) -> GithubApp {
+        GithubApp {
+            api_url: GITHUB_API_URL.to_string(),
+            app_id: "1".to_string(),
+            installation_id: "1".to_string(),
+            encoding_key: EncodingKey::from_secret(b"secret"),
+            http_client: reqwest::Client::new(),
+            token: tokio::sync::Mutex::new(token),
+        }
+    }
+
+    fn sample_headers(headers: &[(&'static str, &str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| (header::HeaderName::from_static(name), value.parse().unwrap()))
+            .collect()
+    }
+
+    fn sample_languages() -> BTreeMap<String, i64> {
+        BTreeMap::from([("Rust".to_string(), 100), ("TypeScript".to_string(), 10)])
+    }
+
+    fn sample_options() -> GithubOptions {
+        GithubOptions {
+            api: GithubApi::Rest,
+            api_url: GITHUB_API_URL.to_string(),
+            repo_url_pattern: Regex::new(GITHUB_REPO_URL_PATTERN).unwrap(),
+            max_rate_limit_wait: GITHUB_MAX_RATE_LIMIT_WAIT,
+        }
+    }
+
+    fn sample_quota() -> TokenQuota {
+        TokenQuota::new("token1", sample_credentials(), &sample_options()).unwrap()
+    }
+
+    fn sample_release() -> Release {
+        Release {
+            ts: Some(DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().into()),
+            url: "https://github.com/owner/repo/releases/tag/v1.0.0".to_string(),
+        }
+    }
+
+    fn sample_repository() -> Repository {
+        Repository {
+            archived: true,
+            default_branch: DEFAULT_BRANCH.to_string(),
+            description: "description".to_string(),
+            disabled: false,
+            forks: 5,
+            license: Some("Apache License 2.0".to_string()),
+            open_issues: 8,
+            open_pull_requests: 2,
+            stars: 100,
+            topics: vec!["topic1".to_string()],
+            url: REPO_URL.to_string(),
+            watchers: 3,
+        }
+    }
+}
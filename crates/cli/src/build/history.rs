@@ -0,0 +1,46 @@
+//! This module defines the functionality used to maintain the history of some
+//! of the repositories metrics (stars, contributors, etc) across builds.
+
+use super::cache::Cache;
+use chrono::NaiveDate;
+use landscape2_core::{
+    data::GithubData,
+    history::{add_point, HistoryPoint, RepositoriesHistory, RepositoryHistory},
+};
+use tracing::{debug, instrument, warn};
+
+/// Cache namespace used to store the repositories history.
+pub(crate) const HISTORY_CACHE_NAMESPACE: &str = "history";
+
+/// Update the history of the repositories provided with the data collected in
+/// this build, returning the full history of those repositories. The history
+/// is stored in the cache (one entry per repository) so that it's preserved
+/// across builds. A point dated on the build day provided is recorded for each
+/// repository (only one point per day is kept).
+#[instrument(skip_all)]
+pub(crate) fn update_repositories_history(
+    cache: &Cache,
+    github_data: &GithubData,
+    build_date: NaiveDate,
+) -> RepositoriesHistory {
+    debug!("updating repositories history");
+
+    let mut history = RepositoriesHistory::new();
+    for (url, gh_data) in github_data {
+        let mut repo_history: RepositoryHistory = match cache.read_entry(HISTORY_CACHE_NAMESPACE, url) {
+            Ok(repo_history) => repo_history.unwrap_or_default(),
+            Err(err) => {
+                warn!(?err, ?url, "error reading repository history from cache");
+                RepositoryHistory::default()
+            }
+        };
+        if add_point(&mut repo_history, HistoryPoint::new(gh_data, build_date)) {
+            if let Err(err) = cache.write_entry(HISTORY_CACHE_NAMESPACE, url, &repo_history) {
+                warn!(?err, ?url, "error writing repository history to cache");
+            }
+        }
+        history.insert(url.clone(), repo_history);
+    }
+
+    history
+}
@@ -0,0 +1,197 @@
+//! This module defines the functionality used to keep track of the issues
+//! found during the build (i.e. logos that could not be prepared or data that
+//! could not be collected or refreshed from external services), as well as the
+//! strict mode checks that can be applied to them.
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Mutex};
+
+/// Maximum number of issues of each kind displayed in the strict mode summary.
+const SUMMARY_MAX_ISSUES: usize = 20;
+
+/// Strict mode arguments.
+#[derive(Args, Clone, Default)]
+pub struct StrictMode {
+    /// Fail the build if any logos cannot be prepared or any data cannot be
+    /// collected (or refreshed) from external services (see thresholds
+    /// below). Stale cached data used when a refresh fails counts as an error.
+    #[arg(long, default_value_t = false)]
+    pub strict: bool,
+
+    /// Maximum number of repositories that can fail to be collected from
+    /// Bitbucket in strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_bitbucket_errors: Option<usize>,
+
+    /// Maximum number of CLOMonitor reports that can fail to be collected in
+    /// strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_clomonitor_errors: Option<usize>,
+
+    /// Maximum number of organizations that can fail to be collected from
+    /// Crunchbase in strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_crunchbase_errors: Option<usize>,
+
+    /// Maximum number of repositories that can fail to be collected from
+    /// Gitea/Forgejo in strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_gitea_errors: Option<usize>,
+
+    /// Maximum number of repositories that can fail to be collected from
+    /// GitHub in strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_github_errors: Option<usize>,
+
+    /// Maximum number of repositories that can fail to be collected from
+    /// GitLab in strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_gitlab_errors: Option<usize>,
+
+    /// Maximum number of logos that can fail to be prepared in strict mode
+    /// [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_logos_errors: Option<usize>,
+
+    /// Maximum number of OpenSSF Scorecard results that can fail to be
+    /// collected in strict mode [default: 0].
+    #[arg(long, requires = "strict")]
+    pub max_scorecard_errors: Option<usize>,
+}
+
+/// Kind of issue found during the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum IssueKind {
+    Bitbucket,
+    Clomonitor,
+    Crunchbase,
+    Gitea,
+    Github,
+    Gitlab,
+    Logo,
+    Scorecard,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueKind::Bitbucket => write!(f, "bitbucket"),
+            IssueKind::Clomonitor => write!(f, "clomonitor"),
+            IssueKind::Crunchbase => write!(f, "crunchbase"),
+            IssueKind::Gitea => write!(f, "gitea"),
+            IssueKind::Github => write!(f, "github"),
+            IssueKind::Gitlab => write!(f, "gitlab"),
+            IssueKind::Logo => write!(f, "logo"),
+            IssueKind::Scorecard => write!(f, "scorecard"),
+        }
+    }
+}
+
+/// Issue found during the build.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Issue {
+    pub kind: IssueKind,
+    pub id: String,
+    pub error: String,
+}
+
+/// Issues found during the build. They can be added concurrently from the
+/// different steps of the build process.
+#[derive(Debug, Default)]
+pub(crate) struct Issues {
+    issues: Mutex<Vec<Issue>>,
+    stale_data: Mutex<Vec<Issue>>,
+}
+
+impl Issues {
+    /// Create a new Issues instance.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a new issue.
+    pub(crate) fn add<E: fmt::Display + ?Sized>(&self, kind: IssueKind, id: &str, error: &E) {
+        let issue = Issue {
+            kind,
+            id: id.to_string(),
+            error: format!("{error:#}"),
+        };
+        self.issues.lock().expect("issues lock not to be poisoned").push(issue);
+    }
+
+    /// Add a new stale data issue. These issues are added when some expired
+    /// data cannot be refreshed and the cached data is used instead.
+    pub(crate) fn add_stale_data<E: fmt::Display + ?Sized>(&self, kind: IssueKind, id: &str, error: &E) {
+        let issue = Issue {
+            kind,
+            id: id.to_string(),
+            error: format!("{error:#}"),
+        };
+        self.stale_data.lock().expect("stale data lock not to be poisoned").push(issue);
+    }
+
+    /// Get all the issues of the kind provided.
+    pub(crate) fn get(&self, kind: IssueKind) -> Vec<Issue> {
+        let issues = self.issues.lock().expect("issues lock not to be poisoned");
+        let mut issues: Vec<Issue> = issues.iter().filter(|i| i.kind == kind).cloned().collect();
+        issues.sort_by(|a, b| a.id.cmp(&b.id));
+        issues
+    }
+
+    /// Get all the stale data issues, sorted by kind.
+    pub(crate) fn get_stale_data(&self) -> Vec<Issue> {
+        let mut stale_data = self.stale_data.lock().expect("stale data lock not to be poisoned").clone();
+        stale_data.sort_by(|a, b| a.kind.cmp(&b.kind).then(a.id.cmp(&b.id)));
+        stale_data
+    }
+
+    /// Check that the number of issues of each kind doesn't exceed the
+    /// thresholds allowed when the strict mode is enabled.
+    pub(crate) fn check_strict_mode(&self, strict_mode: &StrictMode) -> Result<()> {
+        if !strict_mode.strict {
+            return Ok(());
+        }
+
+        let thresholds = [
+            (IssueKind::Bitbucket, strict_mode.max_bitbucket_errors),
+            (IssueKind::Clomonitor, strict_mode.max_clomonitor_errors),
+            (IssueKind::Crunchbase, strict_mode.max_crunchbase_errors),
+            (IssueKind::Gitea, strict_mode.max_gitea_errors),
+            (IssueKind::Github, strict_mode.max_github_errors),
+            (IssueKind::Gitlab, strict_mode.max_gitlab_errors),
+            (IssueKind::Logo, strict_mode.max_logos_errors),
+            (IssueKind::Scorecard, strict_mode.max_scorecard_errors),
+        ];
+        let stale_data = self.get_stale_data();
+        let mut summary = vec![];
+        for (kind, max_errors) in thresholds {
+            let mut issues = self.get(kind);
+            issues.extend(stale_data.iter().filter(|i| i.kind == kind).map(|i| Issue {
+                error: format!("stale cached data used: {}", i.error),
+                ..i.clone()
+            }));
+            let max_errors = max_errors.unwrap_or_default();
+            if issues.len() <= max_errors {
+                continue;
+            }
+            summary.push(format!(
+                "{} {kind} errors (max allowed: {max_errors})",
+                issues.len()
+            ));
+            for issue in issues.iter().take(SUMMARY_MAX_ISSUES) {
+                summary.push(format!("  - {}: {}", issue.id, issue.error));
+            }
+            if issues.len() > SUMMARY_MAX_ISSUES {
+                summary.push(format!("  - ... and {} more", issues.len() - SUMMARY_MAX_ISSUES));
+            }
+        }
+        if !summary.is_empty() {
+            bail!("strict mode checks failed:\n{}", summary.join("\n"));
+        }
+
+        Ok(())
+    }
+}
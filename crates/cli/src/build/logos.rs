@@ -44,17 +44,9 @@ pub(crate) struct Logo {
     pub digest: String,
 }
 
-/// Get logo from the source provided and apply some modifications to it when
+/// Prepare the logo data provided, applying some modifications to it when
 /// applicable.
-pub(crate) async fn prepare_logo(
-    http_client: reqwest::Client,
-    logos_source: &LogosSource,
-    logos_viewbox: &LogosViewbox,
-    file_name: &str,
-) -> Result<Logo> {
-    // Get logo from the source provided
-    let mut logo_data = get_logo(http_client.clone(), logos_source, file_name).await?;
-
+pub(crate) fn prepare_logo(mut logo_data: Vec<u8>, logos_viewbox: &LogosViewbox, file_name: &str) -> Logo {
     // Apply some modifications to the logo if it is an SVG file
     let extension = Path::new(file_name)
         .extension()
@@ -87,16 +79,16 @@ pub(crate) async fn prepare_logo(
     // Calculate digest
     let digest = hex::encode(Sha256::digest(&logo_data));
 
-    Ok(Logo {
+    Logo {
         data: logo_data,
         extension,
         digest,
-    })
+    }
 }
 
 /// Get logo content from the corresponding source.
 #[allow(clippy::similar_names)]
-async fn get_logo(
+pub(crate) async fn get_logo(
     http_client: reqwest::Client,
     logos_source: &LogosSource,
     file_name: &str,
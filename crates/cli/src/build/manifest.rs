@@ -0,0 +1,154 @@
+//! This module defines the manifest used to support incremental builds. It
+//! keeps track of the inputs and outputs of some of the build steps, so that
+//! they can be skipped when nothing has changed since the previous build.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::Path};
+use tracing::{debug, instrument, warn};
+use walkdir::WalkDir;
+
+/// Name of the file the manifest will be written to in the output directory.
+const MANIFEST_FILE: &str = "build-manifest.json";
+
+/// Version of the tool that generated the manifest. Manifests generated by
+/// other versions are ignored, as the outputs may have changed.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Build manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    pub version: String,
+
+    #[serde(default)]
+    pub logos: BTreeMap<String, LogoEntry>,
+
+    #[serde(default)]
+    pub steps: BTreeMap<String, StepEntry>,
+}
+
+impl Manifest {
+    /// Create a new empty Manifest instance.
+    pub(crate) fn new() -> Self {
+        Self {
+            version: VERSION.to_string(),
+            logos: BTreeMap::new(),
+            steps: BTreeMap::new(),
+        }
+    }
+
+    /// Load the manifest from the output directory provided. An empty manifest
+    /// is returned when it's not available or it cannot be used.
+    #[instrument]
+    pub(crate) fn load(output_dir: &Path) -> Self {
+        let Ok(data) = fs::read(output_dir.join(MANIFEST_FILE)) else {
+            return Self::new();
+        };
+        match serde_json::from_slice::<Manifest>(&data) {
+            Ok(manifest) if manifest.version == VERSION => manifest,
+            Ok(_) => {
+                debug!("manifest generated by a different version, ignoring it");
+                Self::new()
+            }
+            Err(err) => {
+                warn!(?err, "error parsing manifest, ignoring it");
+                Self::new()
+            }
+        }
+    }
+
+    /// Check if the outputs of the step provided are up to date. This is the
+    /// case when the step's inputs haven't changed since it was recorded and
+    /// its outputs are still present and unmodified in the output directory.
+    pub(crate) fn is_step_up_to_date(&self, step: &str, inputs_digest: &str, output_dir: &Path) -> bool {
+        let Some(entry) = self.steps.get(step) else {
+            return false;
+        };
+        if entry.inputs_digest != inputs_digest || entry.outputs.is_empty() {
+            return false;
+        }
+        entry
+            .outputs
+            .iter()
+            .all(|(path, digest)| file_digest(&output_dir.join(path)).is_ok_and(|d| d == *digest))
+    }
+
+    /// Record the inputs digest and the outputs of the step provided. Outputs
+    /// are paths relative to the output directory (directories are walked).
+    /// If any of the outputs is missing, the step will not be recorded.
+    pub(crate) fn record_step<I, P>(
+        &mut self,
+        step: &str,
+        inputs_digest: String,
+        output_dir: &Path,
+        outputs: I,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut entry = StepEntry {
+            inputs_digest,
+            outputs: BTreeMap::new(),
+        };
+        for output in outputs {
+            let path = output_dir.join(output);
+            if !path.exists() {
+                self.steps.remove(step);
+                return Ok(());
+            }
+            for e in WalkDir::new(&path) {
+                let e = e?;
+                if e.file_type().is_file() {
+                    let relative_path = e.path().strip_prefix(output_dir)?.to_string_lossy().into_owned();
+                    entry.outputs.insert(relative_path, file_digest(e.path())?);
+                }
+            }
+        }
+        self.steps.insert(step.to_string(), entry);
+
+        Ok(())
+    }
+
+    /// Write manifest to the output directory provided.
+    #[instrument(skip(self), err)]
+    pub(crate) fn write(&self, output_dir: &Path) -> Result<()> {
+        debug!("writing build manifest");
+
+        fs::write(output_dir.join(MANIFEST_FILE), serde_json::to_vec_pretty(self)?)?;
+
+        Ok(())
+    }
+}
+
+/// Logo entry in the manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LogoEntry {
+    /// Digest of the logo source data and the logos viewbox settings.
+    pub input_digest: String,
+
+    /// Path of the prepared logo, relative to the output directory.
+    pub path: String,
+}
+
+/// Build step entry in the manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct StepEntry {
+    /// Digest of all the inputs used by the step.
+    pub inputs_digest: String,
+
+    /// Digests of the files generated by the step, keyed by their path
+    /// relative to the output directory.
+    pub outputs: BTreeMap<String, String>,
+}
+
+/// Compute the digest of the value provided (serialized as JSON).
+pub(crate) fn digest<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(hex::encode(Sha256::digest(serde_json::to_vec(value)?)))
+}
+
+/// Compute the digest of the content of the file provided.
+fn file_digest(path: &Path) -> Result<String> {
+    Ok(hex::encode(Sha256::digest(fs::read(path)?)))
+}
@@ -1,12 +1,21 @@
 //! This module defines the functionality of the build CLI subcommand.
 
 use self::{
-    cache::Cache,
-    crunchbase::collect_crunchbase_data,
+    cache::{Cache, CacheTtls},
     export::generate_items_csv,
-    github::collect_github_data,
-    logos::{prepare_logo, LogosSource},
+    forges::{collect_forges_data, ForgesOptions},
+    github::{collect_github_data, GithubOptions},
+    history::update_repositories_history,
+    issues::{IssueKind, Issues, StrictMode},
+    logos::{get_logo, prepare_logo, LogosSource},
+    manifest::{digest, LogoEntry, Manifest},
+    organizations::{
+        apply_organizations_overrides, collect_organizations_data, get_organizations_overrides,
+        resolve_ultimate_parents, setup_organizations_providers, OrganizationsOptions,
+    },
     projects::{generate_projects_csv, ProjectsMd},
+    report::Report,
+    scorecard::{collect_scorecard_data, ScorecardOptions},
 };
 use crate::{
     build::{
@@ -18,6 +27,7 @@ use crate::{
 use anyhow::{bail, Context, Result};
 use askama::Template;
 use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
+use chrono::Utc;
 use futures::stream::{self, StreamExt};
 use headless_chrome::{
     browser,
@@ -26,7 +36,7 @@ use headless_chrome::{
     Browser, LaunchOptions,
 };
 use landscape2_core::{
-    data::{self, CrunchbaseData, DataSource, GithubData, Item, LandscapeData},
+    data::{self, ClomonitorReport, CrunchbaseData, DataSource, GithubData, Item, LandscapeData},
     datasets::{embed::EmbedView, full::Full, Datasets, NewDatasetsInput},
     games::{GamesSource, LandscapeGames},
     guide::{GuideSource, LandscapeGuide},
@@ -35,6 +45,7 @@ use landscape2_core::{
 use qrcode::render::svg;
 use reqwest::StatusCode;
 use rust_embed::{EmbeddedFile, RustEmbed};
+use sha2::{Digest, Sha256};
 use std::{
     collections::{BTreeMap, HashMap},
     ffi::OsStr,
@@ -50,13 +61,21 @@ use tracing::{debug, error, info, instrument, warn};
 use url::Url;
 
 mod api;
-mod cache;
-mod clomonitor;
-mod crunchbase;
+pub(crate) mod cache;
+pub(crate) mod clomonitor;
+pub(crate) mod crunchbase;
 mod export;
-mod github;
+pub(crate) mod forges;
+pub(crate) mod github;
+pub(crate) mod history;
+mod issues;
 mod logos;
+mod manifest;
+pub(crate) mod organizations;
 mod projects;
+mod report;
+pub(crate) mod scorecard;
+mod watch;
 
 /// Maximum number of CLOMonitor reports summaries to fetch concurrently.
 const CLOMONITOR_MAX_CONCURRENCY: usize = 10;
@@ -85,6 +104,15 @@ const SOURCES_PATH: &str = "sources";
 /// Maximum number of logos to prepare concurrently.
 const PREPARE_LOGOS_MAX_CONCURRENCY: usize = 20;
 
+/// Prefix of the cache files used to store the settings images.
+pub(crate) const SETTINGS_IMAGE_CACHE_FILE_PREFIX: &str = "settings_image_";
+
+/// Names of the build steps tracked in the build manifest.
+const API_STEP: &str = "api";
+const DATASETS_STEP: &str = "datasets";
+const DOCS_STEP: &str = "docs";
+const SCREENSHOT_STEP: &str = "screenshot";
+
 /// Embed landscape embeddable views assets into binary.
 /// (these assets will be built automatically from the build script)
 #[derive(RustEmbed)]
@@ -105,19 +133,43 @@ struct WebappAssets;
 
 /// Build arguments.
 #[derive(clap::Args)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct BuildArgs {
     /// Cache directory.
     #[arg(long)]
     pub cache_dir: Option<PathBuf>,
 
+    /// Maximum percentage of the GitHub and Crunchbase cached entries that
+    /// will be refreshed in a single build. Expired entries exceeding it are
+    /// refreshed in the next builds (oldest first), using the cached data in
+    /// the meantime.
+    #[arg(long, value_name = "PERCENTAGE", value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub cache_refresh_percentage: Option<u8>,
+
+    /// Cache TTLs.
+    #[command(flatten)]
+    pub cache_ttls: CacheTtls,
+
     /// Data source.
     #[command(flatten)]
     pub data_source: DataSource,
 
+    /// Forges (other than GitHub) collectors options.
+    #[command(flatten)]
+    pub forges_options: ForgesOptions,
+
+    /// Rebuild everything, ignoring the manifest from the previous build.
+    #[arg(long, default_value_t = false)]
+    pub force_rebuild: bool,
+
     /// Games source.
     #[command(flatten)]
     pub games_source: GamesSource,
 
+    /// GitHub collector options.
+    #[command(flatten)]
+    pub github_options: GithubOptions,
+
     /// Guide source.
     #[command(flatten)]
     pub guide_source: GuideSource,
@@ -126,23 +178,75 @@ pub struct BuildArgs {
     #[command(flatten)]
     pub logos_source: LogosSource,
 
+    /// Build the landscape without accessing the network. Only local data
+    /// sources are allowed, and the data from external services is taken
+    /// from the cache (regardless of its age).
+    #[arg(long, default_value_t = false)]
+    pub offline: bool,
+
+    /// Organizations data collection options.
+    #[command(flatten)]
+    pub organizations_options: OrganizationsOptions,
+
     /// Output directory to write files to.
     #[arg(long)]
     pub output_dir: PathBuf,
 
+    /// Write a human readable summary of the build report (in markdown
+    /// format) to the output directory.
+    #[arg(long, default_value_t = false)]
+    pub report_summary: bool,
+
+    /// OpenSSF Scorecard collector options.
+    #[command(flatten)]
+    pub scorecard_options: ScorecardOptions,
+
     /// Settings source.
     #[command(flatten)]
     pub settings_source: SettingsSource,
+
+    /// Strict mode.
+    #[command(flatten)]
+    pub strict_mode: StrictMode,
+
+    /// Watch the local data sources files and logos for changes, rebuilding
+    /// the landscape and reloading it in the browser when they change.
+    #[arg(long, default_value_t = false)]
+    pub watch: bool,
+
+    /// Address the web server will listen on in watch mode.
+    #[arg(long, default_value = "127.0.0.1:8000")]
+    pub watch_addr: String,
 }
 
 /// Build landscape website.
-#[instrument(skip_all)]
 pub async fn build(args: &BuildArgs) -> Result<()> {
+    // Check required web assets are present
+    check_web_assets()?;
+
+    // In watch mode the landscape will be rebuilt every time the sources change
+    if args.watch {
+        return watch::watch(args).await;
+    }
+
+    build_landscape(args).await?;
+    display_success_msg(&args.output_dir.to_string_lossy());
+
+    Ok(())
+}
+
+/// Build landscape website once.
+#[allow(clippy::too_many_lines)]
+#[instrument(skip_all)]
+async fn build_landscape(args: &BuildArgs) -> Result<()> {
     info!("building landscape website..");
     let start = Instant::now();
+    let mut report = Report::new();
 
-    // Check required web assets are present
-    check_web_assets()?;
+    // Check no remote data sources have been provided in offline mode
+    if args.offline {
+        check_offline_sources(args)?;
+    }
 
     // Setup output directory, creating it when needed
     setup_output_dir(&args.output_dir)?;
@@ -150,35 +254,103 @@ pub async fn build(args: &BuildArgs) -> Result<()> {
     // Setup cache
     let cache = Cache::new(&args.cache_dir)?;
 
+    // Load manifest from the previous build, used to skip the steps whose
+    // inputs haven't changed since then
+    let mut manifest = if args.force_rebuild {
+        Manifest::new()
+    } else {
+        Manifest::load(&args.output_dir)
+    };
+
+    // Keep track of the issues found during the build
+    let issues = Issues::new();
+    report.step_completed("setup");
+
     // Get landscape data from the source provided
     let mut landscape_data = LandscapeData::new(&args.data_source).await?;
 
     // Get landscape settings from the source provided
     let mut settings = LandscapeSettings::new(&args.settings_source).await?;
+    report.step_completed("get_data_and_settings");
 
     // Prepare games data and copy it to the output directory
     let games = prepare_games_data(&args.games_source, &args.output_dir).await?;
 
     // Prepare guide and copy it to the output directory
     let guide = prepare_guide(&args.guide_source, &args.output_dir).await?;
+    report.step_completed("prepare_games_and_guide");
 
     // Prepare items logos and copy them to the output directory
     prepare_items_logos(
         &args.logos_source,
         &settings.logos_viewbox,
         &mut landscape_data,
+        &mut manifest,
+        &issues,
         &args.output_dir,
     )
     .await?;
+    report.step_completed("prepare_items_logos");
 
     // Fetch some settings images and update their urls to the local copy
-    prepare_settings_images(&mut settings, &args.output_dir).await?;
+    prepare_settings_images(&mut settings, &cache, args.offline, &args.output_dir).await?;
+    report.step_completed("prepare_settings_images");
 
     // Collect data from external services
-    let (crunchbase_data, github_data) = tokio::try_join!(
-        collect_crunchbase_data(&cache, &landscape_data),
-        collect_github_data(&cache, &landscape_data)
+    let organizations_providers = setup_organizations_providers(
+        &cache,
+        &issues,
+        &args.organizations_options,
+        args.cache_ttls.crunchbase,
+        args.cache_refresh_percentage,
+        args.offline,
+    )?;
+    let (mut crunchbase_data, (mut github_data, github_tokens_usage), forges_data) = tokio::try_join!(
+        collect_organizations_data(&organizations_providers, &landscape_data),
+        collect_github_data(
+            &cache,
+            &landscape_data,
+            &issues,
+            &args.github_options,
+            args.cache_ttls.github,
+            args.cache_refresh_percentage,
+            args.offline
+        ),
+        collect_forges_data(
+            &cache,
+            &landscape_data,
+            &issues,
+            &args.forges_options,
+            &args.cache_ttls,
+            args.cache_refresh_percentage,
+            args.offline
+        )
     )?;
+    report.step_completed("collect_crunchbase_and_github_data");
+    report.github_tokens_usage = github_tokens_usage;
+
+    // Apply the organizations overrides to the organizations data collected
+    let organizations_overrides =
+        get_organizations_overrides(&settings, &args.settings_source, args.offline).await?;
+    apply_organizations_overrides(&landscape_data, &mut crunchbase_data, &organizations_overrides);
+    report.step_completed("apply_organizations_overrides");
+
+    // Resolve the ultimate parent of the organizations (subsidiaries)
+    resolve_ultimate_parents(
+        &organizations_providers,
+        &mut crunchbase_data,
+        &organizations_overrides,
+    )
+    .await?;
+    report.step_completed("resolve_ultimate_parents");
+
+    // Repositories data collected from other forges is handled the same way
+    // as the data collected from GitHub from this point on
+    github_data.extend(forges_data);
+
+    // Update the repositories history with the data collected in this build
+    let history = update_repositories_history(&cache, &github_data, Utc::now().date_naive());
+    report.step_completed("update_repositories_history");
 
     // Enrich landscape data with some extra information from the settings and
     // external services
@@ -188,61 +360,150 @@ pub async fn build(args: &BuildArgs) -> Result<()> {
     landscape_data.add_member_subcategory(&settings.members_category);
     landscape_data.add_tags(&settings);
     landscape_data.set_enduser_flag(&settings);
+    report.find_unflagged_archived_items(&landscape_data);
+    report.step_completed("enrich_landscape_data");
 
     // Collect CLOMonitor reports summaries and copy them to the output directory
-    collect_clomonitor_reports(&cache, &mut landscape_data, &settings, &args.output_dir).await?;
+    collect_clomonitor_reports(
+        &cache,
+        &mut landscape_data,
+        &settings,
+        &issues,
+        args.cache_ttls.clomonitor,
+        args.offline,
+        &args.output_dir,
+    )
+    .await?;
+    report.step_completed("collect_clomonitor_reports");
+
+    // Collect OpenSSF Scorecard results of the items primary repositories
+    let scorecard_data = collect_scorecard_data(
+        &cache,
+        &landscape_data,
+        &issues,
+        &args.scorecard_options,
+        &args.github_options.repo_url_pattern,
+        args.cache_ttls.scorecard,
+        args.cache_refresh_percentage,
+        args.offline,
+    )
+    .await?;
+    landscape_data.add_scorecard_data(&scorecard_data);
+    report.step_completed("collect_scorecard_data");
 
     // Generate API data files
-    generate_api(
-        &ApiSources {
-            landscape_data: &landscape_data,
-            settings: &settings,
-        },
-        &args.output_dir,
-    )?;
+    let api_inputs_digest = digest(&(&landscape_data, &crunchbase_data, &github_data, &settings))?;
+    if manifest.is_step_up_to_date(API_STEP, &api_inputs_digest, &args.output_dir) {
+        debug!("api data files are up to date, skipping");
+    } else {
+        generate_api(
+            &ApiSources {
+                landscape_data: &landscape_data,
+                settings: &settings,
+            },
+            &args.output_dir,
+        )?;
+        manifest.record_step(API_STEP, api_inputs_digest, &args.output_dir, [API_PATH])?;
+    }
+    report.step_completed("generate_api");
 
     // Generate QR code
     let qr_code = generate_qr_code(&settings.url, &args.output_dir)?;
 
     // Generate datasets for web application
-    let datasets = generate_datasets(
-        &NewDatasetsInput {
-            crunchbase_data: &crunchbase_data,
-            games: &games,
-            github_data: &github_data,
-            guide: &guide,
-            landscape_data: &landscape_data,
-            qr_code: &qr_code,
-            settings: &settings,
-        },
-        &args.output_dir,
-    )?;
+    let datasets_input = NewDatasetsInput {
+        crunchbase_data: &crunchbase_data,
+        games: &games,
+        github_data: &github_data,
+        guide: &guide,
+        history: &history,
+        landscape_data: &landscape_data,
+        qr_code: &qr_code,
+        settings: &settings,
+    };
+    let datasets_inputs_digest = digest(&(
+        &crunchbase_data,
+        &games,
+        &github_data,
+        &guide,
+        &history,
+        &landscape_data,
+        &qr_code,
+        &settings,
+    ))?;
+    if manifest.is_step_up_to_date(DATASETS_STEP, &datasets_inputs_digest, &args.output_dir) {
+        debug!("datasets are up to date, skipping");
+    } else {
+        let datasets = generate_datasets(&datasets_input, &args.output_dir)?;
+
+        // Render index html file (it embeds some of the datasets)
+        render_index_html(&settings.analytics, &datasets, &settings.osano, &args.output_dir)?;
+
+        let mut datasets_files = get_datasets_files(&datasets);
+        datasets_files.push(PathBuf::from("index.html"));
+        manifest.record_step(
+            DATASETS_STEP,
+            datasets_inputs_digest.clone(),
+            &args.output_dir,
+            datasets_files,
+        )?;
+    }
+    report.step_completed("generate_qr_code_and_datasets");
 
-    // Render index and embed-item html files and write them to the output dir
-    render_index_html(&settings.analytics, &datasets, &settings.osano, &args.output_dir)?;
+    // Render embed-item html file and write it to the output dir
     render_embed_item_html(&settings.colors, &args.output_dir)?;
 
     // Copy embed and web application assets files to the output directory
     copy_embed_assets(&args.output_dir)?;
     copy_webapp_assets(&args.output_dir)?;
-
-    // Generate items.csv file
-    generate_items_csv_file(&landscape_data, &args.output_dir)?;
-
-    // Generate projects.* files
-    generate_projects_files(&landscape_data, &args.output_dir)?;
+    report.step_completed("render_html_and_copy_assets");
+
+    // Generate items.csv and projects.* files
+    let docs_inputs_digest = digest(&(&landscape_data, &crunchbase_data, &github_data))?;
+    if manifest.is_step_up_to_date(DOCS_STEP, &docs_inputs_digest, &args.output_dir) {
+        debug!("docs are up to date, skipping");
+    } else {
+        generate_items_csv_file(&landscape_data, &args.output_dir)?;
+        generate_projects_files(&landscape_data, &args.output_dir)?;
+        let docs_files = ["items.csv", "projects.md", "projects.csv"].map(|f| Path::new(DOCS_PATH).join(f));
+        manifest.record_step(DOCS_STEP, docs_inputs_digest, &args.output_dir, docs_files)?;
+    }
+    report.step_completed("generate_docs");
 
     // Prepare landscape screenshot (in PNG and PDF formats)
     if let Some(width) = &settings.screenshot_width {
-        prepare_screenshot(*width, &args.output_dir).await?;
+        let screenshot_inputs_digest = digest(&(width, &datasets_inputs_digest))?;
+        if manifest.is_step_up_to_date(SCREENSHOT_STEP, &screenshot_inputs_digest, &args.output_dir) {
+            debug!("screenshot is up to date, skipping");
+        } else {
+            prepare_screenshot(*width, &args.output_dir).await?;
+            let screenshot_files = ["landscape.png", "landscape.pdf"].map(|f| Path::new(DOCS_PATH).join(f));
+            manifest.record_step(
+                SCREENSHOT_STEP,
+                screenshot_inputs_digest,
+                &args.output_dir,
+                screenshot_files,
+            )?;
+        }
+        report.step_completed("prepare_screenshot");
     }
 
     // Copy data sources files to the output directory
     copy_data_sources_files(args, &args.output_dir).await?;
+    report.step_completed("copy_data_sources_files");
+
+    // Write build manifest to the output directory
+    manifest.write(&args.output_dir)?;
+
+    // Write build report to the output directory
+    report.finish(&issues, &cache, &args.output_dir);
+    report.write(&args.output_dir, args.report_summary)?;
+
+    // Check the issues found don't exceed the strict mode thresholds
+    issues.check_strict_mode(&args.strict_mode)?;
 
     let duration = start.elapsed().as_secs_f64();
     info!("landscape website built! (took: {:.3}s)", duration);
-    display_success_msg(&args.output_dir.to_string_lossy());
 
     Ok(())
 }
@@ -259,20 +520,57 @@ fn check_web_assets() -> Result<()> {
     Ok(())
 }
 
-/// Collect projects CLOMonitor reports summaries and copy them to the output
-/// directory.
+/// Check that only local data sources have been provided (offline mode).
+#[instrument(skip_all, err)]
+fn check_offline_sources(args: &BuildArgs) -> Result<()> {
+    debug!("checking data sources are available offline");
+
+    let remote_sources = [
+        ("data", &args.data_source.data_url),
+        ("games", &args.games_source.games_url),
+        ("guide", &args.guide_source.guide_url),
+        ("logos", &args.logos_source.logos_url),
+        ("settings", &args.settings_source.settings_url),
+    ];
+    for (name, url) in remote_sources {
+        if let Some(url) = url {
+            bail!("remote {name} source ({url}) not allowed in offline mode, please provide a local one");
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect projects CLOMonitor reports and reports summaries, copying the
+/// latter to the output directory.
 #[instrument(skip_all, err)]
 async fn collect_clomonitor_reports(
     cache: &Cache,
     landscape_data: &mut LandscapeData,
     settings: &LandscapeSettings,
+    issues: &Issues,
+    ttl: i64,
+    offline: bool,
     output_dir: &Path,
 ) -> Result<()> {
+    // CLOMonitor must be configured in the settings
+    let Some(clomonitor_settings) = &settings.clomonitor else {
+        if landscape_data.items.iter().any(|item| item.clomonitor_name.is_some()) {
+            warn!(
+                "some items define a clomonitor_name but clomonitor has not been configured in the \
+                 settings, skipping clomonitor reports collection"
+            );
+        }
+        return Ok(());
+    };
+
     debug!("collecting clomonitor reports");
 
-    // Fetch CLOMonitor reports summaries and copy them to the output directory
+    // Fetch CLOMonitor reports and reports summaries (the latter are copied
+    // to the output directory)
     let http_client = reqwest::Client::new();
-    let foundation = &settings.foundation.to_lowercase();
+    let foundation = &clomonitor_settings.foundation;
+    let reports: Mutex<HashMap<String, ClomonitorReport>> = Mutex::new(HashMap::new());
     let reports_summaries: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
     stream::iter(landscape_data.items.iter())
         .for_each_concurrent(CLOMONITOR_MAX_CONCURRENCY, |item| async {
@@ -281,17 +579,47 @@ async fn collect_clomonitor_reports(
                 return;
             };
 
+            // Fetch report
+            match clomonitor::fetch_report(
+                cache,
+                http_client.clone(),
+                clomonitor_settings,
+                project_name,
+                ttl,
+                offline,
+            )
+            .await
+            {
+                Ok(Some(report)) => {
+                    reports.lock().await.insert(item.id.clone(), report);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    error!(?err, ?foundation, ?project_name, "error fetching report");
+                    issues.add(IssueKind::Clomonitor, project_name, &err);
+                }
+            }
+
             // Fetch report summary
             let http_client = http_client.clone();
-            let report_summary =
-                match clomonitor::fetch_report_summary(cache, http_client, foundation, project_name).await {
-                    Ok(Some(report_summary)) => report_summary,
-                    Ok(None) => return,
-                    Err(err) => {
-                        error!(?err, ?foundation, ?project_name, "error fetching report summary");
-                        return;
-                    }
-                };
+            let report_summary = match clomonitor::fetch_report_summary(
+                cache,
+                http_client,
+                clomonitor_settings,
+                project_name,
+                ttl,
+                offline,
+            )
+            .await
+            {
+                Ok(Some(report_summary)) => report_summary,
+                Ok(None) => return,
+                Err(err) => {
+                    error!(?err, ?foundation, ?project_name, "error fetching report summary");
+                    issues.add(IssueKind::Clomonitor, project_name, &err);
+                    return;
+                }
+            };
 
             // Copy report summary to the output dir
             let file_name = format!("clomonitor_{foundation}_{project_name}.svg");
@@ -299,11 +627,13 @@ async fn collect_clomonitor_reports(
                 Ok(file) => file,
                 Err(err) => {
                     error!(?err, ?file_name, "error creating report summary file");
+                    issues.add(IssueKind::Clomonitor, project_name, &err);
                     return;
                 }
             };
             if let Err(err) = file.write_all(&report_summary) {
                 error!(?err, ?file_name, "error writing report summary to file");
+                issues.add(IssueKind::Clomonitor, project_name, &err);
                 return;
             };
 
@@ -316,10 +646,14 @@ async fn collect_clomonitor_reports(
         })
         .await;
 
-    // Update clomonitor_report_summary field in landscape items with the path
-    // of the SVG image
+    // Update clomonitor_report field in landscape items with the report data
+    // and clomonitor_report_summary with the path of the SVG image
+    let reports = reports.lock().await;
     let reports_summaries = reports_summaries.lock().await;
     for item in &mut landscape_data.items {
+        if let Some(report) = reports.get(&item.id) {
+            item.clomonitor_report = Some(report.clone());
+        }
         if let Some(report_summary) = reports_summaries.get(&item.id) {
             item.clomonitor_report_summary = Some(report_summary.clone());
         }
@@ -513,9 +847,29 @@ fn generate_datasets(input: &NewDatasetsInput, output_dir: &Path) -> Result<Data
     let mut stats_file = File::create(datasets_path.join("stats.json"))?;
     stats_file.write_all(&serde_json::to_vec(&datasets.stats)?)?;
 
+    // Trends
+    let mut trends_file = File::create(datasets_path.join("trends.json"))?;
+    trends_file.write_all(&serde_json::to_vec(&datasets.trends)?)?;
+
     Ok(datasets)
 }
 
+/// Get the paths (relative to the output directory) of the datasets files
+/// written by generate_datasets.
+fn get_datasets_files(datasets: &Datasets) -> Vec<PathBuf> {
+    let mut files = vec![
+        "base.json".to_string(),
+        "full.json".to_string(),
+        "stats.json".to_string(),
+        "trends.json".to_string(),
+    ];
+    for key in datasets.embed.views.keys() {
+        files.push(format!("embed_{key}.json"));
+        files.push(format!("embed_full_{key}.json"));
+    }
+    files.into_iter().map(|f| Path::new(DATASETS_PATH).join(f)).collect()
+}
+
 /// Generate the items.csv file from the landscape data.
 #[instrument(skip_all, err)]
 fn generate_items_csv_file(landscape_data: &LandscapeData, output_dir: &Path) -> Result<()> {
@@ -602,12 +956,15 @@ async fn prepare_guide(guide_source: &GuideSource, output_dir: &Path) -> Result<
 }
 
 /// Prepare items logos and copy them to the output directory, updating the
-/// logo reference on each landscape item.
+/// logo reference on each landscape item. Logos prepared in a previous build
+/// whose source hasn't changed since then are reused.
 #[instrument(skip_all, err)]
 async fn prepare_items_logos(
     logos_source: &LogosSource,
     logos_viewbox: &LogosViewbox,
     landscape_data: &mut LandscapeData,
+    manifest: &mut Manifest,
+    issues: &Issues,
     output_dir: &Path,
 ) -> Result<()> {
     debug!("preparing logos");
@@ -618,27 +975,41 @@ async fn prepare_items_logos(
         concurrency = PREPARE_LOGOS_MAX_CONCURRENCY;
     }
     let http_client = reqwest::Client::new();
-    let logos_source = Arc::new(logos_source.clone());
+    let logos_viewbox_digest = digest(logos_viewbox)?;
     let logos_viewbox = Arc::new(logos_viewbox.clone());
-    let logos: HashMap<String, Option<String>> = stream::iter(landscape_data.items.iter())
+    let prev_logos = &manifest.logos;
+    let logos: HashMap<String, Option<LogoEntry>> = stream::iter(landscape_data.items.iter())
         .map(|item| async {
+            // Get logo from the source
+            let logo_data = match get_logo(http_client.clone(), logos_source, &item.logo).await {
+                Ok(logo_data) => logo_data,
+                Err(err) => {
+                    error!(?err, ?item.logo, "error getting logo");
+                    issues.add(IssueKind::Logo, &item.id, &format!("{}: {err:#}", item.logo));
+                    return (item.id.clone(), None);
+                }
+            };
+
+            // Reuse logo prepared in a previous build if the source is the same
+            let input_digest = hex::encode(Sha256::digest(
+                [logos_viewbox_digest.as_bytes(), &logo_data].concat(),
+            ));
+            if let Some(prev_logo) = prev_logos.get(&item.logo) {
+                if prev_logo.input_digest == input_digest && output_dir.join(&prev_logo.path).exists() {
+                    return (item.id.clone(), Some(prev_logo.clone()));
+                }
+            }
+
             // Prepare logo
-            let http_client = http_client.clone();
-            let logos_source = logos_source.clone();
             let logos_viewbox = logos_viewbox.clone();
             let file_name = item.logo.clone();
-            let logo = match tokio::spawn(async move {
-                prepare_logo(http_client, &logos_source, &logos_viewbox, &file_name).await
-            })
-            .await
+            let logo = match tokio::spawn(async move { prepare_logo(logo_data, &logos_viewbox, &file_name) })
+                .await
             {
-                Ok(Ok(logo)) => logo,
-                Ok(Err(err)) => {
-                    error!(?err, ?item.logo, "error preparing logo");
-                    return (item.id.clone(), None);
-                }
+                Ok(logo) => logo,
                 Err(err) => {
                     error!(?err, ?item.logo, "error executing prepare_logo task");
+                    issues.add(IssueKind::Logo, &item.id, &format!("{}: {err}", item.logo));
                     return (item.id.clone(), None);
                 }
             };
@@ -649,27 +1020,35 @@ async fn prepare_items_logos(
                 Ok(file) => file,
                 Err(err) => {
                     error!(?err, ?file_name, "error creating logo file in output dir");
+                    issues.add(IssueKind::Logo, &item.id, &format!("{}: {err}", item.logo));
                     return (item.id.clone(), None);
                 }
             };
             if let Err(err) = file.write_all(&logo.data) {
                 error!(?err, ?file_name, "error writing logo to file in output dir");
+                issues.add(IssueKind::Logo, &item.id, &format!("{}: {err}", item.logo));
+                return (item.id.clone(), None);
             };
 
-            (item.id.clone(), Some(format!("{LOGOS_PATH}/{file_name}")))
+            let path = format!("{LOGOS_PATH}/{file_name}");
+            (item.id.clone(), Some(LogoEntry { input_digest, path }))
         })
         .buffer_unordered(concurrency)
         .collect()
         .await;
 
-    // Update logo field in landscape items to logo digest path
+    // Update logo field in landscape items to logo digest path, keeping track
+    // of the logos prepared in the manifest
+    let mut manifest_logos = BTreeMap::new();
     for item in &mut landscape_data.items {
         item.logo = if let Some(Some(logo)) = logos.get(&item.id) {
-            logo.clone()
+            manifest_logos.insert(item.logo.clone(), logo.clone());
+            logo.path.clone()
         } else {
             String::new()
         }
     }
+    manifest.logos = manifest_logos;
 
     debug!("done!");
     Ok(())
@@ -700,6 +1079,7 @@ async fn prepare_screenshot(width: u32, output_dir: &Path) -> Result<()> {
             graceful_shutdown: false,
             landscape_dir,
             silent: true,
+            live_reload: None,
         };
         serve(&args).await
     });
@@ -763,21 +1143,42 @@ async fn prepare_screenshot(width: u32, output_dir: &Path) -> Result<()> {
 }
 
 /// Fetch some settings images, copy them to the output directory and update
-/// their urls to the local copy.
+/// their urls to the local copy. Images fetched are cached, so that they can
+/// be used later in offline mode.
 #[instrument(skip_all, err)]
-async fn prepare_settings_images(settings: &mut LandscapeSettings, output_dir: &Path) -> Result<()> {
+async fn prepare_settings_images(
+    settings: &mut LandscapeSettings,
+    cache: &Cache,
+    offline: bool,
+    output_dir: &Path,
+) -> Result<()> {
     // Helper function to process the image provided
-    async fn process_image(url: &Option<String>, output_dir: &Path) -> Result<Option<String>> {
+    async fn process_image(
+        url: &Option<String>,
+        cache: &Cache,
+        offline: bool,
+        output_dir: &Path,
+    ) -> Result<Option<String>> {
         let Some(url) = url else {
             return Ok(None);
         };
 
-        // Fetch image from url
-        let resp = reqwest::get(url).await?;
-        if resp.status() != StatusCode::OK {
-            bail!("unexpected status ({}) code getting logo {url}", resp.status());
-        }
-        let img = resp.bytes().await?.to_vec();
+        // Fetch image from url (or from the cache in offline mode)
+        let cache_file = settings_image_cache_file(url);
+        let img = if offline {
+            let Some((_, img)) = cache.read(&cache_file)? else {
+                bail!("image {url} not available in cache (offline mode)");
+            };
+            img
+        } else {
+            let resp = reqwest::get(url).await?;
+            if resp.status() != StatusCode::OK {
+                bail!("unexpected status ({}) code getting logo {url}", resp.status());
+            }
+            let img = resp.bytes().await?.to_vec();
+            cache.write(&cache_file, &img)?;
+            img
+        };
 
         // Write image to output dir
         let url = Url::parse(url).context("invalid image url")?;
@@ -794,22 +1195,42 @@ async fn prepare_settings_images(settings: &mut LandscapeSettings, output_dir: &
 
     // Header
     if let Some(header) = &mut settings.header {
-        header.logo = process_image(&header.logo, output_dir).await?;
+        header.logo = process_image(&header.logo, cache, offline, output_dir).await?;
     };
 
     // Footer
     if let Some(footer) = &mut settings.footer {
-        footer.logo = process_image(&footer.logo, output_dir).await?;
+        footer.logo = process_image(&footer.logo, cache, offline, output_dir).await?;
     };
 
     // Other images
     if let Some(images) = &mut settings.images {
-        images.favicon = process_image(&images.favicon, output_dir).await?;
+        images.favicon = process_image(&images.favicon, cache, offline, output_dir).await?;
     };
 
     Ok(())
 }
 
+/// Get the urls of the settings images fetched by `prepare_settings_images`.
+pub(crate) fn settings_images_urls(settings: &LandscapeSettings) -> Vec<&String> {
+    [
+        settings.header.as_ref().and_then(|header| header.logo.as_ref()),
+        settings.footer.as_ref().and_then(|footer| footer.logo.as_ref()),
+        settings.images.as_ref().and_then(|images| images.favicon.as_ref()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Get the name of the cache file used to store the settings image provided.
+pub(crate) fn settings_image_cache_file(url: &str) -> String {
+    format!(
+        "{SETTINGS_IMAGE_CACHE_FILE_PREFIX}{}",
+        hex::encode(Sha256::digest(url.as_bytes()))
+    )
+}
+
 /// Prepare view full dataset creating a stripped down version of the full
 /// dataset with only the data needed for the provided embed view.
 fn prepare_view_full_dataset(full: &Full, view: &EmbedView) -> Full {
@@ -824,12 +1245,7 @@ fn prepare_view_full_dataset(full: &Full, view: &EmbedView) -> Full {
     // Crunchbase data
     let mut crunchbase_data: CrunchbaseData = BTreeMap::new();
     for (url, org) in &full.crunchbase_data {
-        if items.iter().any(|i| {
-            let Some(crunchbase_url) = &i.crunchbase_url else {
-                return false;
-            };
-            crunchbase_url == url
-        }) {
+        if items.iter().any(|i| i.organization_key() == Some(url)) {
             crunchbase_data.insert(url.clone(), org.clone());
         }
     }
@@ -959,3 +1375,100 @@ mod filters {
         Ok(writer.finish())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use tempfile::TempDir;
+
+    /// Wrapper used to parse the build arguments in the tests.
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: BuildArgs,
+    }
+
+    #[tokio::test]
+    async fn build_landscape_skips_up_to_date_steps() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = |name: &str| tmp_dir.path().join(name).to_string_lossy().into_owned();
+        fs::write(
+            path("data.yml"),
+            r"
+landscape:
+  - category:
+    name: Category
+    subcategories:
+      - subcategory:
+        name: Subcategory
+        items:
+          - item:
+            name: Item
+            homepage_url: https://item.example.com
+            logo: logo.svg
+            crunchbase: https://www.crunchbase.com/organization/org
+",
+        )
+        .unwrap();
+        fs::write(
+            path("settings.yml"),
+            "foundation: Foundation\nurl: https://landscape.example.com\n",
+        )
+        .unwrap();
+        fs::write(
+            path("organizations.yml"),
+            "https://www.crunchbase.com/organization/org:\n  name: Organization\n",
+        )
+        .unwrap();
+        fs::create_dir(path("logos")).unwrap();
+        fs::write(
+            tmp_dir.path().join("logos").join("logo.svg"),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><rect width="10" height="10"/></svg>"#,
+        )
+        .unwrap();
+        let args = TestCli::parse_from([
+            "landscape2",
+            "--offline",
+            "--data-file",
+            &path("data.yml"),
+            "--settings-file",
+            &path("settings.yml"),
+            "--logos-path",
+            &path("logos"),
+            "--organizations-file",
+            &path("organizations.yml"),
+            "--cache-dir",
+            &path("cache"),
+            "--output-dir",
+            &path("output"),
+        ])
+        .args;
+
+        // Nothing has changed, so the second build must skip the steps
+        // (including those depending on the organizations data)
+        let outputs = [
+            args.output_dir.join(API_PATH).join("members").join("all.json"),
+            args.output_dir.join(DATASETS_PATH).join("full.json"),
+            args.output_dir.join("index.html"),
+            args.output_dir.join(DOCS_PATH).join("items.csv"),
+        ];
+        let modified_at = |path: &PathBuf| fs::metadata(path).unwrap().modified().unwrap();
+
+        build_landscape(&args).await.unwrap();
+        let first_build_steps = Manifest::load(&args.output_dir).steps;
+        let first_build_outputs_modified_at: Vec<_> = outputs.iter().map(modified_at).collect();
+
+        build_landscape(&args).await.unwrap();
+        let second_build_steps = Manifest::load(&args.output_dir).steps;
+        let second_build_outputs_modified_at: Vec<_> = outputs.iter().map(modified_at).collect();
+
+        for step in [API_STEP, DATASETS_STEP, DOCS_STEP] {
+            assert_eq!(
+                first_build_steps[step].inputs_digest, second_build_steps[step].inputs_digest,
+                "{step} step inputs changed"
+            );
+        }
+        assert_eq!(first_build_outputs_modified_at, second_build_outputs_modified_at);
+    }
+}
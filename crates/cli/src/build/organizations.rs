@@ -0,0 +1,725 @@
+//! This module defines the functionality used to collect the organizations
+//! data of the landscape items. The data can be obtained from multiple
+//! providers (a local organizations file and Crunchbase at the moment), which
+//! are queried in order of precedence until each organization is found. The
+//! data collected can be patched later using the organizations overrides
+//! file referenced in the settings, and the ultimate parent of each
+//! organization is resolved from the parents and acquirers collected (which
+//! are collected from the providers as well when they are not part of the
+//! landscape).
+
+use super::{
+    cache::Cache,
+    crunchbase::{CrunchbaseProvider, CRUNCHBASE_URL},
+    issues::Issues,
+    LandscapeData,
+};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use landscape2_core::{
+    data::{CrunchbaseData, Organization, OrganizationRef, OrganizationsOverrides},
+    settings::{LandscapeSettings, SettingsSource},
+};
+#[cfg(test)]
+use mockall::automock;
+use reqwest::StatusCode;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, instrument, warn};
+
+/// Maximum number of levels followed when resolving the ultimate parent of
+/// an organization.
+pub(crate) const ULTIMATE_PARENT_MAX_DEPTH: usize = 10;
+
+/// Organizations data collection options.
+#[derive(Args, Clone)]
+pub struct OrganizationsOptions {
+    /// Organizations file (YAML or JSON) with the data of the items
+    /// organizations, keyed by their Crunchbase url or organization id. The
+    /// data in this file takes precedence over the data from Crunchbase.
+    #[arg(long, value_name = "FILE")]
+    pub organizations_file: Option<PathBuf>,
+}
+
+/// Setup the organizations data providers available (in order of precedence).
+pub(crate) fn setup_organizations_providers<'a>(
+    cache: &'a Cache,
+    issues: &'a Issues,
+    options: &OrganizationsOptions,
+    ttl: i64,
+    refresh_percentage: Option<u8>,
+    offline: bool,
+) -> Result<Vec<DynOrganizationsProvider<'a>>> {
+    let mut providers: Vec<DynOrganizationsProvider> = vec![];
+    if let Some(file) = &options.organizations_file {
+        providers.push(Box::new(LocalFileProvider::new(file)?));
+    }
+    providers.push(Box::new(CrunchbaseProvider {
+        cache,
+        issues,
+        ttl,
+        refresh_percentage,
+        offline,
+    }));
+
+    Ok(providers)
+}
+
+/// Collect the organizations data of the landscape items from the providers
+/// given. Organizations are looked up using both the item's Crunchbase url
+/// and organization id (when available), but the data returned is keyed by
+/// the items organization key (see
+/// [`landscape2_core::data::Item::organization_key`]).
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_organizations_data(
+    providers: &[DynOrganizationsProvider<'_>],
+    landscape_data: &LandscapeData,
+) -> Result<CrunchbaseData> {
+    // Collect items organizations keys (the first one of each item is its
+    // organization key)
+    let mut organizations_keys: Vec<Vec<String>> = landscape_data
+        .items
+        .iter()
+        .map(|item| item.crunchbase_url.iter().chain(item.organization_id.iter()).cloned().collect())
+        .filter(|keys: &Vec<String>| !keys.is_empty())
+        .collect();
+    organizations_keys.sort();
+    organizations_keys.dedup();
+
+    collect_from_providers(providers, organizations_keys).await
+}
+
+/// Collect the data of the organizations provided from the providers given,
+/// in order. Each organization is identified by a list of keys, and the data
+/// found for any of them is stored using the first one. Organizations found
+/// in a provider are not requested from the following ones.
+async fn collect_from_providers(
+    providers: &[DynOrganizationsProvider<'_>],
+    mut organizations_keys: Vec<Vec<String>>,
+) -> Result<CrunchbaseData> {
+    let mut organizations_data = CrunchbaseData::new();
+    for provider in providers {
+        if organizations_keys.is_empty() {
+            break;
+        }
+        let mut keys: Vec<String> = organizations_keys.iter().flatten().cloned().collect();
+        keys.sort();
+        keys.dedup();
+        let provider_data = provider.collect(&keys).await?;
+        debug!(
+            provider = provider.name(),
+            count = provider_data.len(),
+            "organizations data collected"
+        );
+        organizations_keys.retain(|keys| {
+            let Some(org) = keys.iter().find_map(|key| provider_data.get(key)) else {
+                return true;
+            };
+            organizations_data.insert(keys[0].clone(), org.clone());
+            false
+        });
+    }
+
+    // Warn about the organizations not found that aren't identified by a
+    // Crunchbase url (those are already tracked as Crunchbase issues)
+    for key in organizations_keys.iter().map(|keys| &keys[0]).filter(|key| !CRUNCHBASE_URL.is_match(key)) {
+        warn!(?key, "organization data not found in any provider");
+    }
+
+    Ok(organizations_data)
+}
+
+/// Get the organizations overrides from the file referenced in the settings
+/// (if any). The file can be located using a url or a local path. Relative
+/// paths are resolved from the settings file location (when it's local).
+#[instrument(skip_all, err)]
+pub(crate) async fn get_organizations_overrides(
+    settings: &LandscapeSettings,
+    settings_source: &SettingsSource,
+    offline: bool,
+) -> Result<OrganizationsOverrides> {
+    let Some(location) = &settings.organizations_overrides else {
+        return Ok(OrganizationsOverrides::new());
+    };
+    debug!(?location, "getting organizations overrides");
+
+    // Read overrides file from the location provided
+    let raw_data = if let Some(path) = get_organizations_overrides_path(settings, settings_source) {
+        fs::read_to_string(&path)
+            .with_context(|| format!("error reading organizations overrides file: {}", path.display()))?
+    } else {
+        if offline {
+            bail!("remote organizations overrides file ({location}) not allowed in offline mode, please provide a local one");
+        }
+        let resp = reqwest::get(location).await?;
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code getting organizations overrides file: {}",
+                resp.status()
+            );
+        }
+        resp.text().await?
+    };
+
+    serde_yaml::from_str(&raw_data).context("invalid organizations overrides file")
+}
+
+/// Get the path of the organizations overrides file referenced in the
+/// settings, when it's a local one. Relative paths are resolved from the
+/// settings file location (when it's local).
+pub(crate) fn get_organizations_overrides_path(
+    settings: &LandscapeSettings,
+    settings_source: &SettingsSource,
+) -> Option<PathBuf> {
+    let location = settings.organizations_overrides.as_ref()?;
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return None;
+    }
+    let mut path = PathBuf::from(location);
+    if path.is_relative() {
+        if let Some(settings_dir) = settings_source.settings_file.as_ref().and_then(|file| file.parent()) {
+            path = settings_dir.join(path);
+        }
+    }
+    Some(path)
+}
+
+/// Apply the organizations overrides provided to the organizations data.
+/// Overrides can be keyed by the organization's Crunchbase url or by its
+/// organization id. Overrides that don't match any of the organizations (or
+/// their parents, which are handled when resolving the ultimate parents) are
+/// ignored.
+pub(crate) fn apply_organizations_overrides(
+    landscape_data: &LandscapeData,
+    organizations_data: &mut CrunchbaseData,
+    overrides: &OrganizationsOverrides,
+) {
+    // Map the keys of the items organizations to their organization key
+    let mut organizations_keys: HashMap<&String, &String> = HashMap::new();
+    for item in &landscape_data.items {
+        if let Some(organization_key) = item.organization_key() {
+            for key in item.crunchbase_url.iter().chain(item.organization_id.iter()) {
+                organizations_keys.insert(key, organization_key);
+            }
+        }
+    }
+    let parents_keys: HashSet<String> = organizations_data
+        .values()
+        .filter_map(get_parent)
+        .flat_map(OrganizationRef::lookup_keys)
+        .cloned()
+        .collect();
+
+    for (key, org_overrides) in overrides {
+        let organization_key = organizations_keys.get(key).copied().unwrap_or(key);
+        if let Some(org) = organizations_data.get_mut(organization_key) {
+            org.apply_overrides(org_overrides);
+        } else if !parents_keys.contains(key) {
+            warn!(?key, "organization overridden not found in the data collected");
+        }
+    }
+}
+
+/// Resolve the ultimate parent of each of the organizations provided, by
+/// following its chain of parents (or acquirers). Parents not available in
+/// the organizations data are collected from the providers given, one level
+/// at a time, and the overrides provided are applied to them as well.
+#[instrument(skip_all, err)]
+pub(crate) async fn resolve_ultimate_parents(
+    providers: &[DynOrganizationsProvider<'_>],
+    organizations_data: &mut CrunchbaseData,
+    overrides: &OrganizationsOverrides,
+) -> Result<()> {
+    // Collect the missing parents (the providers cache the data collected)
+    let mut parents_data = CrunchbaseData::new();
+    let mut requested_keys = HashSet::new();
+    let mut missing_keys = get_missing_parents_keys(
+        organizations_data.values(),
+        |key| organizations_data.contains_key(key),
+        &mut requested_keys,
+    );
+    for _ in 1..ULTIMATE_PARENT_MAX_DEPTH {
+        if missing_keys.is_empty() {
+            break;
+        }
+        let mut level_data = collect_from_providers(providers, missing_keys.clone()).await?;
+        for keys in &missing_keys {
+            let org_overrides = keys.iter().find_map(|key| overrides.get(key));
+            if let (Some(org), Some(org_overrides)) = (level_data.get_mut(&keys[0]), org_overrides) {
+                org.apply_overrides(org_overrides);
+            }
+        }
+        missing_keys = get_missing_parents_keys(
+            level_data.values(),
+            |key| organizations_data.contains_key(key) || parents_data.contains_key(key),
+            &mut requested_keys,
+        );
+        parents_data.extend(level_data);
+    }
+    debug!(count = parents_data.len(), "parents organizations data collected");
+
+    // Resolve the ultimate parents using the organizations data available
+    let lookup = |key: &str| organizations_data.get(key).or_else(|| parents_data.get(key));
+    let mut ultimate_parents: BTreeMap<String, OrganizationRef> = organizations_data
+        .iter()
+        .filter_map(|(key, org)| get_ultimate_parent(org, lookup).map(|parent| (key.clone(), parent)))
+        .collect();
+    for (key, org) in organizations_data.iter_mut() {
+        org.ultimate_parent = ultimate_parents.remove(key);
+    }
+
+    Ok(())
+}
+
+/// Get the keys of the parents (or acquirers) of the organizations provided
+/// that are not available yet and haven't been requested before.
+fn get_missing_parents_keys<'a>(
+    organizations: impl Iterator<Item = &'a Organization>,
+    is_available: impl Fn(&str) -> bool,
+    requested_keys: &mut HashSet<String>,
+) -> Vec<Vec<String>> {
+    organizations
+        .filter_map(get_parent)
+        .map(|parent| parent.lookup_keys().cloned().collect::<Vec<_>>())
+        .filter(|keys| {
+            !keys.is_empty()
+                && !keys.iter().any(|key| is_available(key))
+                && requested_keys.insert(keys[0].clone())
+        })
+        .collect()
+}
+
+/// Get the parent (or acquirer) of the organization provided, if any.
+pub(crate) fn get_parent(org: &Organization) -> Option<&OrganizationRef> {
+    org.parent.as_ref().or(org.acquired_by.as_ref())
+}
+
+/// Get the ultimate parent of the organization provided (if any), looking up
+/// the parents data using the function given. Parents not available end the
+/// chain.
+fn get_ultimate_parent<'a>(
+    org: &'a Organization,
+    lookup: impl Fn(&str) -> Option<&'a Organization>,
+) -> Option<OrganizationRef> {
+    let mut ultimate_parent = None;
+    let mut current = org;
+    for _ in 0..ULTIMATE_PARENT_MAX_DEPTH {
+        let Some(parent) = get_parent(current) else {
+            break;
+        };
+        ultimate_parent = Some(parent);
+        match parent.lookup_keys().find_map(|key| lookup(key)) {
+            Some(parent_org) => current = parent_org,
+            None => break,
+        }
+    }
+    ultimate_parent.cloned()
+}
+
+/// Type alias to represent an OrganizationsProvider trait object.
+pub(crate) type DynOrganizationsProvider<'a> = Box<dyn OrganizationsProvider + Send + Sync + 'a>;
+
+/// Trait that defines some operations an organizations data provider must
+/// support.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub(crate) trait OrganizationsProvider {
+    /// Name of the provider.
+    fn name(&self) -> &'static str;
+
+    /// Collect the data of the organizations identified by the keys provided.
+    /// Organizations not supported by the provider or not found are not
+    /// included in the data returned.
+    async fn collect(&self, keys: &[String]) -> Result<CrunchbaseData>;
+}
+
+/// Organizations data provider backed by a local organizations file.
+struct LocalFileProvider {
+    organizations: BTreeMap<String, Organization>,
+}
+
+impl LocalFileProvider {
+    /// Create a new LocalFileProvider instance from the file provided.
+    fn new(file: &Path) -> Result<Self> {
+        let raw_data = fs::read_to_string(file).context("error reading organizations file")?;
+        Self::new_from_raw_data(&raw_data)
+    }
+
+    /// Create a new LocalFileProvider instance from the raw data provided
+    /// (JSON documents are also valid YAML documents).
+    fn new_from_raw_data(raw_data: &str) -> Result<Self> {
+        let organizations = serde_yaml::from_str(raw_data).context("invalid organizations file")?;
+        Ok(Self { organizations })
+    }
+}
+
+#[async_trait]
+impl OrganizationsProvider for LocalFileProvider {
+    /// [OrganizationsProvider::name]
+    fn name(&self) -> &'static str {
+        "organizations file"
+    }
+
+    /// [OrganizationsProvider::collect]
+    async fn collect(&self, keys: &[String]) -> Result<CrunchbaseData> {
+        let organizations_data = keys
+            .iter()
+            .filter_map(|key| self.organizations.get(key).map(|org| (key.clone(), org.clone())))
+            .collect();
+
+        Ok(organizations_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use landscape2_core::data::{Item, OrganizationOverrides};
+    use mockall::predicate::eq;
+    use tempfile::TempDir;
+
+    const CB_URL: &str = "https://www.crunchbase.com/organization/org1";
+    const ORG_ID: &str = "org2";
+
+    #[tokio::test]
+    async fn local_file_provider_yaml() {
+        let provider = LocalFileProvider::new_from_raw_data(
+            r"
+https://www.crunchbase.com/organization/org1:
+  name: Organization 1
+  city: City
+  country: Country
+  funding: 1000
+  funding_rounds:
+    - amount: 1000
+      announced_on: 2024-01-02
+      kind: seed
+org2:
+  name: Organization 2
+",
+        )
+        .unwrap();
+
+        let keys = vec![CB_URL.to_string(), ORG_ID.to_string(), "org3".to_string()];
+        let organizations_data = provider.collect(&keys).await.unwrap();
+        assert_eq!(organizations_data.len(), 2);
+        let org1 = &organizations_data[CB_URL];
+        assert_eq!(org1.name, Some("Organization 1".to_string()));
+        assert_eq!(org1.city, Some("City".to_string()));
+        assert_eq!(org1.funding, Some(1000));
+        assert_eq!(org1.funding_rounds.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            organizations_data[ORG_ID].name,
+            Some("Organization 2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn local_file_provider_json() {
+        let provider = LocalFileProvider::new_from_raw_data(
+            r#"{"org2": {"name": "Organization 2", "country": "Country"}}"#,
+        )
+        .unwrap();
+
+        let organizations_data = provider.collect(&[ORG_ID.to_string()]).await.unwrap();
+        assert_eq!(organizations_data[ORG_ID].country, Some("Country".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid organizations file")]
+    fn local_file_provider_invalid_file() {
+        LocalFileProvider::new_from_raw_data("- invalid").unwrap();
+    }
+
+    #[tokio::test]
+    async fn collect_from_providers_in_order() {
+        let org = |name: &str| Organization {
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+
+        let mut file_provider = MockOrganizationsProvider::new();
+        file_provider.expect_name().return_const("file_provider");
+        file_provider
+            .expect_collect()
+            .with(eq(vec![CB_URL.to_string(), ORG_ID.to_string()]))
+            .times(1)
+            .returning(move |_| Ok(CrunchbaseData::from([(ORG_ID.to_string(), org("file_provider"))])));
+        let mut cb_provider = MockOrganizationsProvider::new();
+        cb_provider.expect_name().return_const("cb_provider");
+        cb_provider
+            .expect_collect()
+            .with(eq(vec![CB_URL.to_string()]))
+            .times(1)
+            .returning(move |_| Ok(CrunchbaseData::from([(CB_URL.to_string(), org("cb_provider"))])));
+        let mut other_provider = MockOrganizationsProvider::new();
+        other_provider.expect_collect().never();
+
+        let providers: Vec<DynOrganizationsProvider> = vec![
+            Box::new(file_provider),
+            Box::new(cb_provider),
+            Box::new(other_provider),
+        ];
+        let organizations_keys = vec![vec![CB_URL.to_string()], vec![ORG_ID.to_string()]];
+        let organizations_data = collect_from_providers(&providers, organizations_keys).await.unwrap();
+        assert_eq!(
+            organizations_data,
+            CrunchbaseData::from([
+                (CB_URL.to_string(), org("cb_provider")),
+                (ORG_ID.to_string(), org("file_provider")),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_from_providers_tries_all_keys() {
+        let org = Organization {
+            name: Some("org".to_string()),
+            ..Default::default()
+        };
+
+        // The organization is found in the file using its id, but the data
+        // is keyed by its Crunchbase url (the organization key)
+        let mut file_provider = MockOrganizationsProvider::new();
+        file_provider.expect_name().return_const("file_provider");
+        let file_org = org.clone();
+        file_provider
+            .expect_collect()
+            .with(eq(vec![CB_URL.to_string(), ORG_ID.to_string()]))
+            .times(1)
+            .returning(move |_| Ok(CrunchbaseData::from([(ORG_ID.to_string(), file_org.clone())])));
+        let mut cb_provider = MockOrganizationsProvider::new();
+        cb_provider.expect_collect().never();
+
+        let providers: Vec<DynOrganizationsProvider> = vec![Box::new(file_provider), Box::new(cb_provider)];
+        let organizations_keys = vec![vec![CB_URL.to_string(), ORG_ID.to_string()]];
+        let organizations_data = collect_from_providers(&providers, organizations_keys).await.unwrap();
+        assert_eq!(
+            organizations_data,
+            CrunchbaseData::from([(CB_URL.to_string(), org)])
+        );
+    }
+
+    #[tokio::test]
+    async fn get_organizations_overrides_from_local_file() {
+        let settings_dir = TempDir::new().unwrap();
+        fs::write(
+            settings_dir.path().join("overrides.yml"),
+            format!("{CB_URL}:\n  ticker: TICK\n"),
+        )
+        .unwrap();
+        let settings = LandscapeSettings {
+            organizations_overrides: Some("overrides.yml".to_string()),
+            ..Default::default()
+        };
+        let settings_source = SettingsSource {
+            settings_file: Some(settings_dir.path().join("settings.yml")),
+            settings_url: None,
+        };
+
+        let overrides = get_organizations_overrides(&settings, &settings_source, true).await.unwrap();
+        assert_eq!(
+            overrides,
+            OrganizationsOverrides::from([(
+                CB_URL.to_string(),
+                OrganizationOverrides {
+                    ticker: Some("TICK".to_string()),
+                    ..Default::default()
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn get_organizations_overrides_path_local_and_remote() {
+        let settings_source = SettingsSource {
+            settings_file: Some(PathBuf::from("landscape/settings.yml")),
+            settings_url: None,
+        };
+        let settings = |location: &str| LandscapeSettings {
+            organizations_overrides: Some(location.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_organizations_overrides_path(&settings("overrides.yml"), &settings_source),
+            Some(PathBuf::from("landscape/overrides.yml"))
+        );
+        assert_eq!(
+            get_organizations_overrides_path(
+                &settings("https://overrides.url/overrides.yml"),
+                &settings_source
+            ),
+            None
+        );
+        assert_eq!(
+            get_organizations_overrides_path(&LandscapeSettings::default(), &settings_source),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn get_organizations_overrides_from_url() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/overrides.yml")
+            .with_status(200)
+            .with_body(format!("{CB_URL}:\n  city: City\n"))
+            .create_async()
+            .await;
+        let settings = LandscapeSettings {
+            organizations_overrides: Some(format!("{}/overrides.yml", server.url())),
+            ..Default::default()
+        };
+
+        let overrides =
+            get_organizations_overrides(&settings, &SettingsSource::default(), false).await.unwrap();
+        assert_eq!(overrides[CB_URL].city, Some("City".to_string()));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "not allowed in offline mode")]
+    async fn get_organizations_overrides_from_url_offline() {
+        let settings = LandscapeSettings {
+            organizations_overrides: Some("https://overrides.url/overrides.yml".to_string()),
+            ..Default::default()
+        };
+
+        get_organizations_overrides(&settings, &SettingsSource::default(), true).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "invalid organizations overrides file")]
+    async fn get_organizations_overrides_unknown_field() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/overrides.yml")
+            .with_status(200)
+            .with_body(format!("{CB_URL}:\n  unknown: value\n"))
+            .create_async()
+            .await;
+        let settings = LandscapeSettings {
+            organizations_overrides: Some(format!("{}/overrides.yml", server.url())),
+            ..Default::default()
+        };
+
+        get_organizations_overrides(&settings, &SettingsSource::default(), false).await.unwrap();
+    }
+
+    #[test]
+    fn apply_organizations_overrides_to_collected_data() {
+        let landscape_data = LandscapeData {
+            items: vec![Item {
+                crunchbase_url: Some(CB_URL.to_string()),
+                organization_id: Some(ORG_ID.to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut organizations_data = CrunchbaseData::from([(
+            CB_URL.to_string(),
+            Organization {
+                city: Some("Wrong city".to_string()),
+                ..Default::default()
+            },
+        )]);
+
+        // Overrides can use any of the organization keys
+        let overrides = OrganizationsOverrides::from([
+            (
+                CB_URL.to_string(),
+                OrganizationOverrides {
+                    city: Some("City".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                ORG_ID.to_string(),
+                OrganizationOverrides {
+                    ticker: Some("TICK".to_string()),
+                    ..Default::default()
+                },
+            ),
+            ("org3".to_string(), OrganizationOverrides::default()),
+        ]);
+
+        apply_organizations_overrides(&landscape_data, &mut organizations_data, &overrides);
+        assert_eq!(
+            organizations_data,
+            CrunchbaseData::from([(
+                CB_URL.to_string(),
+                Organization {
+                    city: Some("City".to_string()),
+                    overridden_fields: Some(vec!["city".to_string(), "ticker".to_string()]),
+                    ticker: Some("TICK".to_string()),
+                    ..Default::default()
+                },
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_ultimate_parents_chain() {
+        let org_ref = |name: &str| OrganizationRef {
+            name: name.to_string(),
+            crunchbase_url: Some(format!("https://www.crunchbase.com/organization/{name}")),
+            organization_id: None,
+        };
+        let mut organizations_data = CrunchbaseData::from([
+            (
+                org_ref("product").crunchbase_url.unwrap(),
+                Organization {
+                    parent: Some(org_ref("vendor")),
+                    ..Default::default()
+                },
+            ),
+            (
+                org_ref("vendor").crunchbase_url.unwrap(),
+                Organization {
+                    acquired_by: Some(org_ref("holding")),
+                    ..Default::default()
+                },
+            ),
+            (
+                org_ref("independent").crunchbase_url.unwrap(),
+                Organization::default(),
+            ),
+        ]);
+
+        // The holding isn't in the organizations data, so it's collected
+        // from the providers (its parent is set using the overrides)
+        let mut provider = MockOrganizationsProvider::new();
+        provider.expect_name().return_const("provider");
+        provider
+            .expect_collect()
+            .with(eq(vec![org_ref("holding").crunchbase_url.unwrap()]))
+            .times(1)
+            .returning(move |keys| Ok(CrunchbaseData::from([(keys[0].clone(), Organization::default())])));
+        provider
+            .expect_collect()
+            .with(eq(vec![org_ref("group").crunchbase_url.unwrap()]))
+            .times(1)
+            .returning(|_| Ok(CrunchbaseData::new()));
+        let providers: Vec<DynOrganizationsProvider> = vec![Box::new(provider)];
+        let overrides = OrganizationsOverrides::from([(
+            org_ref("holding").crunchbase_url.unwrap(),
+            OrganizationOverrides {
+                parent: Some(org_ref("group")),
+                ..Default::default()
+            },
+        )]);
+
+        resolve_ultimate_parents(&providers, &mut organizations_data, &overrides).await.unwrap();
+        let ultimate_parent =
+            |name: &str| organizations_data[&org_ref(name).crunchbase_url.unwrap()].ultimate_parent.clone();
+        assert_eq!(ultimate_parent("product"), Some(org_ref("group")));
+        assert_eq!(ultimate_parent("vendor"), Some(org_ref("group")));
+        assert_eq!(ultimate_parent("independent"), None);
+        assert_eq!(organizations_data.len(), 3);
+    }
+}
@@ -0,0 +1,259 @@
+//! This module defines the build report, which contains some information about
+//! the build process, like how long each of the steps took or the data that
+//! could not be collected from external services.
+
+use super::{
+    cache::{Cache, CacheStats},
+    github::TokenUsage,
+    issues::{Issue, IssueKind, Issues},
+    LandscapeData, DATASETS_PATH,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Write as _, fs, path::Path, time::Instant};
+use tracing::{debug, instrument};
+
+/// Name of the file the report will be written to in the output directory.
+const REPORT_FILE: &str = "build-report.json";
+
+/// Name of the file the report summary will be written to in the output dir.
+const REPORT_SUMMARY_FILE: &str = "build-report.md";
+
+/// Datasets files whose sizes will be included in the report.
+const REPORT_DATASETS_FILES: [&str; 2] = ["base.json", "full.json"];
+
+/// Build report.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Report {
+    pub generated_at: DateTime<Utc>,
+    pub duration: f64,
+    pub steps: Vec<StepDuration>,
+    pub cache: BTreeMap<String, CacheStats>,
+    pub github_tokens_usage: Vec<TokenUsage>,
+    pub failed_clomonitor_reports: Vec<Issue>,
+    pub failed_logos: Vec<Issue>,
+    pub failed_scorecard_results: Vec<Issue>,
+    pub repositories_without_bitbucket_data: Vec<Issue>,
+    pub repositories_without_gitea_data: Vec<Issue>,
+    pub repositories_without_github_data: Vec<Issue>,
+    pub repositories_without_gitlab_data: Vec<Issue>,
+    pub unresolved_crunchbase_urls: Vec<Issue>,
+    pub stale_data: Vec<Issue>,
+    pub unflagged_archived_items: Vec<UnflaggedArchivedItem>,
+    pub outputs_sizes: BTreeMap<String, u64>,
+
+    #[serde(skip)]
+    started_at: Instant,
+
+    #[serde(skip)]
+    last_step_completed_at: Instant,
+}
+
+impl Report {
+    /// Create a new Report instance.
+    pub(crate) fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            generated_at: Utc::now(),
+            duration: 0.0,
+            steps: vec![],
+            cache: BTreeMap::new(),
+            github_tokens_usage: vec![],
+            failed_clomonitor_reports: vec![],
+            failed_logos: vec![],
+            failed_scorecard_results: vec![],
+            repositories_without_bitbucket_data: vec![],
+            repositories_without_gitea_data: vec![],
+            repositories_without_github_data: vec![],
+            repositories_without_gitlab_data: vec![],
+            unresolved_crunchbase_urls: vec![],
+            stale_data: vec![],
+            unflagged_archived_items: vec![],
+            outputs_sizes: BTreeMap::new(),
+            started_at: now,
+            last_step_completed_at: now,
+        }
+    }
+
+    /// Record the duration of the step provided, which is the time elapsed
+    /// since the previous step was completed.
+    pub(crate) fn step_completed(&mut self, name: &str) {
+        let now = Instant::now();
+        self.steps.push(StepDuration {
+            name: name.to_string(),
+            duration: (now - self.last_step_completed_at).as_secs_f64(),
+        });
+        self.last_step_completed_at = now;
+    }
+
+    /// Find the items whose primary repository has been archived (or
+    /// disabled) upstream that have not been flagged as archived in the
+    /// landscape data yet.
+    pub(crate) fn find_unflagged_archived_items(&mut self, landscape_data: &LandscapeData) {
+        self.unflagged_archived_items = landscape_data
+            .items
+            .iter()
+            .filter(|item| item.archived_at.is_none() && item.primary_repository_archived())
+            .filter_map(|item| {
+                item.primary_repository().map(|repo| UnflaggedArchivedItem {
+                    id: item.id.clone(),
+                    repository_url: repo.url.clone(),
+                })
+            })
+            .collect();
+        self.unflagged_archived_items.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    /// Complete the report with the issues found during the build, the cache
+    /// stats and the sizes of some of the files in the output directory.
+    pub(crate) fn finish(&mut self, issues: &Issues, cache: &Cache, output_dir: &Path) {
+        self.duration = self.started_at.elapsed().as_secs_f64();
+        self.cache = cache.stats();
+        self.failed_clomonitor_reports = issues.get(IssueKind::Clomonitor);
+        self.failed_logos = issues.get(IssueKind::Logo);
+        self.failed_scorecard_results = issues.get(IssueKind::Scorecard);
+        self.repositories_without_bitbucket_data = issues.get(IssueKind::Bitbucket);
+        self.repositories_without_gitea_data = issues.get(IssueKind::Gitea);
+        self.repositories_without_github_data = issues.get(IssueKind::Github);
+        self.repositories_without_gitlab_data = issues.get(IssueKind::Gitlab);
+        self.unresolved_crunchbase_urls = issues.get(IssueKind::Crunchbase);
+        self.stale_data = issues.get_stale_data();
+        for file in REPORT_DATASETS_FILES {
+            let path = Path::new(DATASETS_PATH).join(file);
+            if let Ok(md) = fs::metadata(output_dir.join(&path)) {
+                self.outputs_sizes.insert(path.to_string_lossy().into_owned(), md.len());
+            }
+        }
+    }
+
+    /// Write report to the output directory, as well as its summary if
+    /// requested.
+    #[instrument(skip(self), err)]
+    pub(crate) fn write(&self, output_dir: &Path, summary: bool) -> Result<()> {
+        debug!("writing build report");
+
+        fs::write(output_dir.join(REPORT_FILE), serde_json::to_vec_pretty(self)?)?;
+        if summary {
+            fs::write(output_dir.join(REPORT_SUMMARY_FILE), self.summary())?;
+        }
+
+        Ok(())
+    }
+
+    /// Prepare a human readable summary of the report (in markdown format).
+    fn summary(&self) -> String {
+        let mut s = String::new();
+        _ = writeln!(s, "# Build report\n");
+        _ = writeln!(s, "Build took **{:.3}s**.\n", self.duration);
+
+        // Steps
+        _ = writeln!(s, "## Steps\n");
+        _ = writeln!(s, "| Step | Duration |");
+        _ = writeln!(s, "| --- | --- |");
+        for step in &self.steps {
+            _ = writeln!(s, "| {} | {:.3}s |", step.name, step.duration);
+        }
+
+        // Cache
+        _ = writeln!(s, "\n## Cache\n");
+        _ = writeln!(s, "| Source | Hits | Misses |");
+        _ = writeln!(s, "| --- | --- | --- |");
+        for (source, stats) in &self.cache {
+            _ = writeln!(s, "| {source} | {} | {} |", stats.hits, stats.misses);
+        }
+
+        // GitHub tokens quota usage
+        if !self.github_tokens_usage.is_empty() {
+            _ = writeln!(s, "\n## GitHub tokens usage\n");
+            _ = writeln!(
+                s,
+                "| Token | Resource | Used | Remaining | Limit | Reset | Rate limited |"
+            );
+            _ = writeln!(s, "| --- | --- | --- | --- | --- | --- | --- |");
+            for token_usage in &self.github_tokens_usage {
+                for (resource, quota) in &token_usage.resources {
+                    _ = writeln!(
+                        s,
+                        "| {} | {resource} | {} | {} | {} | {} | {} |",
+                        token_usage.token,
+                        quota.used,
+                        quota.remaining,
+                        quota.limit,
+                        quota.reset.to_rfc3339(),
+                        token_usage.rate_limited
+                    );
+                }
+            }
+        }
+
+        // Outputs sizes
+        _ = writeln!(s, "\n## Outputs sizes\n");
+        for (file, size) in &self.outputs_sizes {
+            _ = writeln!(s, "- {file}: {size} bytes");
+        }
+
+        // Data gaps
+        let gaps = [
+            ("Failed logos", &self.failed_logos),
+            (
+                "Repositories without GitHub data",
+                &self.repositories_without_github_data,
+            ),
+            (
+                "Repositories without GitLab data",
+                &self.repositories_without_gitlab_data,
+            ),
+            (
+                "Repositories without Gitea/Forgejo data",
+                &self.repositories_without_gitea_data,
+            ),
+            (
+                "Repositories without Bitbucket data",
+                &self.repositories_without_bitbucket_data,
+            ),
+            ("Unresolved Crunchbase urls", &self.unresolved_crunchbase_urls),
+            ("Failed CLOMonitor reports", &self.failed_clomonitor_reports),
+            ("Failed OpenSSF Scorecard results", &self.failed_scorecard_results),
+        ];
+        for (title, issues) in gaps {
+            _ = writeln!(s, "\n## {title} ({})\n", issues.len());
+            for issue in issues {
+                _ = writeln!(s, "- `{}`: {}", issue.id, issue.error);
+            }
+        }
+
+        // Stale data (expired data that could not be refreshed)
+        _ = writeln!(s, "\n## Stale data used ({})\n", self.stale_data.len());
+        for issue in &self.stale_data {
+            _ = writeln!(s, "- {} `{}`: {}", issue.kind, issue.id, issue.error);
+        }
+
+        // Items to review
+        _ = writeln!(
+            s,
+            "\n## Items with archived primary repository but no archived date ({})\n",
+            self.unflagged_archived_items.len()
+        );
+        for item in &self.unflagged_archived_items {
+            _ = writeln!(s, "- `{}`: {}", item.id, item.repository_url);
+        }
+
+        s
+    }
+}
+
+/// Item whose primary repository has been archived upstream while the item
+/// has no archived date.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct UnflaggedArchivedItem {
+    pub id: String,
+    pub repository_url: String,
+}
+
+/// Duration of a build step.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct StepDuration {
+    pub name: String,
+    pub duration: f64,
+}
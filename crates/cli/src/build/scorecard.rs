@@ -0,0 +1,400 @@
+//! This module defines the functionality used to collect the OpenSSF Scorecard
+//! (https://scorecard.dev) results of the items' primary repositories.
+
+use super::{
+    cache::{Cache, CachedCollector, CachedData, CachedSource},
+    github::{get_owner_and_repo, parse_api_url},
+    issues::{IssueKind, Issues},
+    LandscapeData,
+};
+use anyhow::{bail, format_err, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use landscape2_core::data::{Scorecard, ScorecardCheck, ScorecardData};
+#[cfg(test)]
+use mockall::automock;
+use regex::Regex;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, instrument};
+use url::Url;
+
+/// Name used to track the Scorecard data in the cache stats.
+const SCORECARD_CACHE_SOURCE: &str = "scorecard";
+
+/// Namespace used to cache data collected from the Scorecard API (one entry
+/// per repository).
+pub(crate) const SCORECARD_CACHE_NAMESPACE: &str = "scorecard";
+
+/// Default for how long the Scorecard data in the cache is valid (in days).
+pub(crate) const SCORECARD_CACHE_TTL: i64 = 7;
+
+/// Scorecard API base url.
+const SCORECARD_API_URL: &str = "https://api.securityscorecards.dev";
+
+/// Scorecard viewer url.
+const SCORECARD_VIEWER_URL: &str = "https://scorecard.dev/viewer/?uri=";
+
+/// Maximum number of requests sent concurrently to the Scorecard API.
+const SCORECARD_MAX_CONCURRENCY: usize = 10;
+
+/// OpenSSF Scorecard collector options.
+#[derive(Args, Clone)]
+pub struct ScorecardOptions {
+    /// OpenSSF Scorecard API base url.
+    #[arg(
+        id = "scorecard_api_url",
+        long = "scorecard-api-url",
+        value_name = "URL",
+        default_value = SCORECARD_API_URL,
+        value_parser = parse_api_url
+    )]
+    pub api_url: String,
+}
+
+/// Scorecard cache entry. Repositories that have not been analyzed by
+/// Scorecard are cached as well, so that they are not requested again until
+/// the entry expires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ScorecardEntry {
+    /// Scorecard results of the repository.
+    Found(Scorecard),
+
+    /// The repository has not been analyzed by Scorecard.
+    NotFound { generated_at: DateTime<Utc> },
+}
+
+impl CachedData for ScorecardEntry {
+    /// [CachedData::generated_at]
+    fn generated_at(&self) -> DateTime<Utc> {
+        match self {
+            ScorecardEntry::Found(scorecard) => scorecard.generated_at,
+            ScorecardEntry::NotFound { generated_at } => *generated_at,
+        }
+    }
+
+    /// [CachedData::set_generated_at]
+    fn set_generated_at(&mut self, value: DateTime<Utc>) {
+        match self {
+            ScorecardEntry::Found(scorecard) => scorecard.generated_at = value,
+            ScorecardEntry::NotFound { generated_at } => *generated_at = value,
+        }
+    }
+
+    /// [CachedData::set_stale]
+    fn set_stale(&mut self, stale: bool) {
+        if let ScorecardEntry::Found(scorecard) = self {
+            scorecard.stale = stale.then_some(true);
+        }
+    }
+}
+
+/// Collect the OpenSSF Scorecard results of the items' primary repositories
+/// hosted on GitHub, reusing cached data whenever possible.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_scorecard_data(
+    cache: &Cache,
+    landscape_data: &LandscapeData,
+    issues: &Issues,
+    options: &ScorecardOptions,
+    repo_url_pattern: &Regex,
+    ttl: i64,
+    refresh_percentage: Option<u8>,
+    offline: bool,
+) -> Result<ScorecardData> {
+    debug!("collecting repositories openssf scorecard results");
+
+    // Setup Scorecard API client
+    let sc: DynSC = Arc::new(SCApi::new(&options.api_url)?);
+
+    // Collect items primary repositories urls hosted on GitHub
+    let mut urls = vec![];
+    for item in &landscape_data.items {
+        if let Some(repo) = item.primary_repository() {
+            if repo_url_pattern.is_match(&repo.url) {
+                urls.push(&repo.url);
+            }
+        }
+    }
+    urls.sort();
+    urls.dedup();
+
+    // Read cached data (if available) and select the expired entries that
+    // will be refreshed in this build
+    let source = CachedSource {
+        name: SCORECARD_CACHE_SOURCE,
+        namespace: SCORECARD_CACHE_NAMESPACE,
+        issue_kind: IssueKind::Scorecard,
+        ttl,
+        refresh_percentage,
+        offline,
+    };
+    let collector = CachedCollector::new(cache, issues, source, urls.iter().copied(), |_, _| true);
+
+    // Collect Scorecard results, reusing cached data when available
+    let sc = &sc;
+    let urls = urls.into_iter().map(|url| (url.clone(), ())).collect();
+    let scorecard_data: ScorecardData = collector
+        .collect(urls, SCORECARD_MAX_CONCURRENCY, |url, ()| async move {
+            collect_repository_scorecard(sc.clone(), repo_url_pattern, &url).await
+        })
+        .await
+        .into_iter()
+        .filter_map(|(url, entry)| match entry {
+            ScorecardEntry::Found(scorecard) => Some((url, scorecard)),
+            ScorecardEntry::NotFound { .. } => None,
+        })
+        .collect();
+
+    debug!("done!");
+    Ok(scorecard_data)
+}
+
+/// Collect the Scorecard results of the repository provided.
+#[instrument(skip(sc, repo_url_pattern), err)]
+async fn collect_repository_scorecard(
+    sc: DynSC,
+    repo_url_pattern: &Regex,
+    repo_url: &str,
+) -> Result<ScorecardEntry> {
+    let project = get_project(repo_url_pattern, repo_url)?;
+    let Some(sc_result) = sc.get_result(&project).await? else {
+        return Ok(ScorecardEntry::NotFound {
+            generated_at: Utc::now(),
+        });
+    };
+
+    Ok(ScorecardEntry::Found(Scorecard {
+        generated_at: Utc::now(),
+        score: sc_result.score,
+        checks: sc_result
+            .checks
+            .into_iter()
+            .map(|check| ScorecardCheck {
+                name: check.name,
+                score: check.score,
+                documentation_url: check.documentation.and_then(|d| d.url),
+                reason: check.reason,
+            })
+            .collect(),
+        date: sc_result.date,
+        stale: None,
+        url: Some(format!("{SCORECARD_VIEWER_URL}{project}")),
+    }))
+}
+
+/// Get the Scorecard project identifier (i.e. github.com/owner/repo) of the
+/// repository url provided.
+fn get_project(repo_url_pattern: &Regex, repo_url: &str) -> Result<String> {
+    let (owner, repo) = get_owner_and_repo(repo_url_pattern, repo_url)?;
+    let url = Url::parse(repo_url)?;
+    let host = url.host_str().ok_or_else(|| format_err!("invalid repository url"))?;
+    Ok(format!("{host}/{owner}/{repo}"))
+}
+
+/// Type alias to represent a SC trait object.
+type DynSC = Arc<dyn SC + Send + Sync>;
+
+/// Trait that defines some operations a SC implementation must support.
+#[cfg_attr(test, automock)]
+#[async_trait]
+trait SC {
+    /// Get the Scorecard result of the project provided.
+    async fn get_result(&self, project: &str) -> Result<Option<SCResult>>;
+}
+
+/// SC implementation backed by the Scorecard API.
+struct SCApi {
+    api_url: String,
+    http_client: reqwest::Client,
+}
+
+impl SCApi {
+    /// Create a new SCApi instance.
+    fn new(api_url: &str) -> Result<Self> {
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let http_client = reqwest::Client::builder().user_agent(user_agent).build()?;
+
+        Ok(Self {
+            api_url: api_url.to_string(),
+            http_client,
+        })
+    }
+}
+
+#[async_trait]
+impl SC for SCApi {
+    /// [SC::get_result]
+    #[instrument(skip(self), err)]
+    async fn get_result(&self, project: &str) -> Result<Option<SCResult>> {
+        let url = format!("{}/projects/{project}", self.api_url);
+        let response = self.http_client.get(url).send().await?;
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.json().await?)),
+            StatusCode::NOT_FOUND => Ok(None),
+            _ => bail!("unexpected status code: {:?}", response.status()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SCResult {
+    #[serde(default)]
+    checks: Vec<SCCheck>,
+    date: Option<String>,
+    score: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SCCheck {
+    documentation: Option<SCCheckDocumentation>,
+    name: String,
+    reason: Option<String>,
+    score: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SCCheckDocumentation {
+    url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::github::GITHUB_REPO_URL_PATTERN;
+    use mockall::predicate::eq;
+
+    const PROJECT: &str = "github.com/owner/repo";
+    const REPO_URL: &str = "https://github.com/owner/repo";
+
+    #[tokio::test]
+    async fn collect_repository_scorecard_found() {
+        let mut sc = MockSC::new();
+        sc.expect_get_result().with(eq(PROJECT)).times(1).returning(|_| {
+            Ok(Some(
+                serde_json::from_str(
+                    r#"{
+                        "date": "2024-01-02",
+                        "repo": { "name": "github.com/owner/repo" },
+                        "score": 7.3,
+                        "checks": [
+                            {
+                                "name": "Binary-Artifacts",
+                                "score": 10,
+                                "reason": "no binaries found in the repo",
+                                "details": null,
+                                "documentation": {
+                                    "short": "Determines if the project has generated executable artifacts.",
+                                    "url": "https://github.com/ossf/scorecard/blob/main/docs/checks.md#binary-artifacts"
+                                }
+                            },
+                            {
+                                "name": "Packaging",
+                                "score": -1,
+                                "reason": "packaging workflow not detected",
+                                "details": null,
+                                "documentation": null
+                            }
+                        ]
+                    }"#,
+                )
+                .unwrap(),
+            ))
+        });
+
+        let entry = collect_repository_scorecard(Arc::new(sc), &repo_url_pattern(), REPO_URL).await.unwrap();
+        assert_eq!(
+            entry,
+            ScorecardEntry::Found(Scorecard {
+                generated_at: entry.generated_at(),
+                score: 7.3,
+                checks: vec![
+                    ScorecardCheck {
+                        name: "Binary-Artifacts".to_string(),
+                        score: 10,
+                        documentation_url: Some(
+                            "https://github.com/ossf/scorecard/blob/main/docs/checks.md#binary-artifacts"
+                                .to_string()
+                        ),
+                        reason: Some("no binaries found in the repo".to_string()),
+                    },
+                    ScorecardCheck {
+                        name: "Packaging".to_string(),
+                        score: -1,
+                        documentation_url: None,
+                        reason: Some("packaging workflow not detected".to_string()),
+                    },
+                ],
+                date: Some("2024-01-02".to_string()),
+                stale: None,
+                url: Some("https://scorecard.dev/viewer/?uri=github.com/owner/repo".to_string()),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_repository_scorecard_not_found() {
+        let mut sc = MockSC::new();
+        sc.expect_get_result().with(eq(PROJECT)).times(1).returning(|_| Ok(None));
+
+        let entry = collect_repository_scorecard(Arc::new(sc), &repo_url_pattern(), REPO_URL).await.unwrap();
+        assert_eq!(
+            entry,
+            ScorecardEntry::NotFound {
+                generated_at: entry.generated_at()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_repository_scorecard_invalid_url() {
+        let sc = MockSC::new();
+
+        let result =
+            collect_repository_scorecard(Arc::new(sc), &repo_url_pattern(), "https://gitlab.com/owner/repo")
+                .await;
+        assert_eq!(result.unwrap_err().to_string(), "invalid repository url");
+    }
+
+    #[test]
+    fn get_project_works() {
+        assert_eq!(
+            get_project(&repo_url_pattern(), "https://github.com/owner/repo/").unwrap(),
+            PROJECT
+        );
+        assert!(get_project(&repo_url_pattern(), "https://githubxcom/owner/repo").is_err());
+    }
+
+    #[test]
+    fn get_project_custom_pattern() {
+        let repo_url_pattern =
+            Regex::new(r"^https://github\.example\.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/?$").unwrap();
+        assert_eq!(
+            get_project(&repo_url_pattern, "https://github.example.com/owner/repo").unwrap(),
+            "github.example.com/owner/repo"
+        );
+    }
+
+    #[test]
+    fn scorecard_entry_deserialize() {
+        let generated_at = DateTime::parse_from_rfc3339("2024-01-02T01:04:05Z").unwrap().into();
+        let found = ScorecardEntry::Found(Scorecard {
+            generated_at,
+            score: 7.3,
+            ..Default::default()
+        });
+        let not_found = ScorecardEntry::NotFound { generated_at };
+        for entry in [found, not_found] {
+            let json = serde_json::to_string(&entry).unwrap();
+            assert_eq!(serde_json::from_str::<ScorecardEntry>(&json).unwrap(), entry);
+        }
+    }
+
+    fn repo_url_pattern() -> Regex {
+        Regex::new(GITHUB_REPO_URL_PATTERN).unwrap()
+    }
+}
@@ -0,0 +1,137 @@
+//! This module implements the build watch mode, which rebuilds the landscape
+//! when any of the local data sources files or logos change, and reloads it
+//! in the browsers connected to the web server.
+//!
+//! Any change triggers a rebuild of the whole landscape. The build steps whose
+//! inputs haven't changed are skipped using the build manifest, so only the
+//! parts affected by the change are actually regenerated.
+
+use super::{build_landscape, organizations::get_organizations_overrides_path, BuildArgs};
+use crate::serve::{serve, ServeArgs};
+use anyhow::{format_err, Result};
+use landscape2_core::settings::LandscapeSettings;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tokio::{sync::broadcast, time::sleep};
+use tracing::{debug, error, info, instrument, warn};
+use walkdir::WalkDir;
+
+/// How often the sources will be checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the files watched, keyed by their path.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Build the landscape, serve it and rebuild it every time the sources change.
+#[instrument(skip_all, err)]
+pub(super) async fn watch(args: &BuildArgs) -> Result<()> {
+    // Initial build (errors are not fatal, the sources may be fixed later)
+    if let Err(err) = build_landscape(args).await {
+        error!(?err, "error building landscape");
+    }
+
+    // Prepare the list of paths to watch
+    let mut paths = get_watched_paths(args).await;
+    if paths.is_empty() {
+        warn!("no local sources to watch provided (urls are not watched)");
+    }
+
+    // Launch server with live reload enabled
+    let (reload_tx, _) = broadcast::channel(16);
+    let serve_args = ServeArgs {
+        addr: args.watch_addr.clone(),
+        graceful_shutdown: false,
+        landscape_dir: Some(args.output_dir.clone()),
+        silent: false,
+        live_reload: Some(reload_tx.clone()),
+    };
+    let server = tokio::spawn(async move { serve(&serve_args).await });
+
+    // Rebuild the landscape when any of the sources change
+    info!("watching sources for changes..");
+    let mut snapshot = take_snapshot(&paths);
+    loop {
+        sleep(POLL_INTERVAL).await;
+
+        // Stop watching if the server is not running anymore
+        if server.is_finished() {
+            server.await??;
+            return Err(format_err!("web server stopped unexpectedly"));
+        }
+
+        // Check if any of the sources has changed
+        let new_snapshot = take_snapshot(&paths);
+        if new_snapshot == snapshot {
+            continue;
+        }
+        snapshot = new_snapshot;
+
+        // Rebuild landscape and notify browsers
+        info!("changes detected, rebuilding landscape..");
+        match build_landscape(args).await {
+            Ok(()) => {
+                _ = reload_tx.send(());
+            }
+            Err(err) => error!(?err, "error rebuilding landscape"),
+        }
+
+        // The settings may reference a different organizations overrides file
+        // now, so the paths to watch are prepared again
+        let new_paths = get_watched_paths(args).await;
+        if new_paths != paths {
+            paths = new_paths;
+            snapshot = take_snapshot(&paths);
+        }
+    }
+}
+
+/// Get the paths of the local sources provided that should be watched. This
+/// includes the organizations file and the organizations overrides file
+/// referenced in the settings (when they are local).
+async fn get_watched_paths(args: &BuildArgs) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = [
+        &args.data_source.data_file,
+        &args.settings_source.settings_file,
+        &args.guide_source.guide_file,
+        &args.games_source.games_file,
+        &args.logos_source.logos_path,
+        &args.organizations_options.organizations_file,
+    ]
+    .into_iter()
+    .flatten()
+    .cloned()
+    .collect();
+
+    match LandscapeSettings::new(&args.settings_source).await {
+        Ok(settings) => paths.extend(get_organizations_overrides_path(&settings, &args.settings_source)),
+        Err(err) => debug!(
+            ?err,
+            "error getting settings, organizations overrides file not watched"
+        ),
+    }
+
+    paths
+}
+
+/// Take a snapshot of the modification times of the files in the paths
+/// provided (directories are walked).
+fn take_snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        for entry in WalkDir::new(path).into_iter().filter_map(Result::ok) {
+            if entry.file_type().is_file() {
+                snapshot.insert(entry.path().to_path_buf(), modified_at(entry.path()));
+            }
+        }
+    }
+    snapshot
+}
+
+/// Get the last modification time of the file provided (if available).
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|md| md.modified()).ok()
+}
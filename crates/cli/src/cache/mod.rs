@@ -0,0 +1,775 @@
+//! This module defines the functionality of the cache CLI subcommand.
+
+use crate::build::{
+    cache::{Cache, CacheTtls, CachedData},
+    clomonitor::CLOMONITOR_CACHE_FILE_PREFIX,
+    crunchbase::CRUNCHBASE_CACHE_NAMESPACE,
+    forges::{
+        bitbucket::BITBUCKET_CACHE_NAMESPACE, gitea::GITEA_CACHE_NAMESPACE, gitlab::GITLAB_CACHE_NAMESPACE,
+    },
+    github::GITHUB_CACHE_NAMESPACE,
+    history::HISTORY_CACHE_NAMESPACE,
+    organizations::{get_parent, ULTIMATE_PARENT_MAX_DEPTH},
+    scorecard::{ScorecardEntry, SCORECARD_CACHE_NAMESPACE},
+    settings_image_cache_file, settings_images_urls, SETTINGS_IMAGE_CACHE_FILE_PREFIX,
+};
+use anyhow::{bail, format_err, Context, Result};
+use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use landscape2_core::{
+    data::{DataSource, LandscapeData, Organization, RepositoryGithubData},
+    history::RepositoryHistory,
+    settings::{LandscapeSettings, SettingsSource},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt, fs,
+    path::PathBuf,
+    time::SystemTime,
+};
+use tracing::instrument;
+
+/// Version of the cache archive format.
+const ARCHIVE_VERSION: u32 = 1;
+
+/// Cache command arguments.
+#[derive(clap::Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct CacheArgs {
+    /// Cache operation to perform.
+    #[command(subcommand)]
+    pub operation: Operation,
+}
+
+/// Cache operation to perform.
+#[derive(Subcommand)]
+pub enum Operation {
+    /// Force the expiration of some cache entries, so that they are refreshed
+    /// in the next build.
+    Expire(ExpireArgs),
+
+    /// Export the cache to a single portable archive file.
+    Export(ExportArgs),
+
+    /// Import the cache from an archive file previously exported.
+    Import(ImportArgs),
+
+    /// List the cache entries, including their age and TTL status.
+    List(ListArgs),
+
+    /// Remove the cache entries not referenced by the landscape data and
+    /// settings files.
+    Prune(PruneArgs),
+}
+
+/// Cache directory argument (shared by all operations).
+#[derive(Args)]
+pub struct CacheDir {
+    /// Cache directory.
+    #[arg(long)]
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// Expire operation arguments.
+#[derive(Args)]
+pub struct ExpireArgs {
+    #[command(flatten)]
+    pub cache_dir: CacheDir,
+
+    /// Source the entries to expire belong to.
+    #[arg(long)]
+    pub source: Source,
+
+    /// Keys of the entries to expire (repositories urls for GitHub, other
+    /// forges and Scorecard, organizations urls for Crunchbase and
+    /// foundation/project for CLOMonitor).
+    #[arg(required = true)]
+    pub keys: Vec<String>,
+}
+
+/// Export operation arguments.
+#[derive(Args)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub cache_dir: CacheDir,
+
+    /// Archive file the cache will be exported to.
+    #[arg(long)]
+    pub archive_file: PathBuf,
+}
+
+/// Import operation arguments.
+#[derive(Args)]
+pub struct ImportArgs {
+    #[command(flatten)]
+    pub cache_dir: CacheDir,
+
+    /// Archive file the cache will be imported from.
+    #[arg(long)]
+    pub archive_file: PathBuf,
+}
+
+/// List operation arguments.
+#[derive(Args)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub cache_dir: CacheDir,
+
+    /// Cache TTLs used to check if the entries have expired.
+    #[command(flatten)]
+    pub cache_ttls: CacheTtls,
+
+    /// Only list the entries of this source.
+    #[arg(long)]
+    pub source: Option<Source>,
+}
+
+/// Prune operation arguments.
+#[derive(Args)]
+pub struct PruneArgs {
+    #[command(flatten)]
+    pub cache_dir: CacheDir,
+
+    #[command(flatten)]
+    pub data_source: DataSource,
+
+    #[command(flatten)]
+    pub settings_source: SettingsSource,
+}
+
+/// Source of the data in the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Source {
+    Bitbucket,
+    Clomonitor,
+    Crunchbase,
+    Gitea,
+    Github,
+    Gitlab,
+    Scorecard,
+}
+
+impl Source {
+    /// How long the data of this source in the cache is valid (in days).
+    fn ttl(self, cache_ttls: &CacheTtls) -> i64 {
+        match self {
+            Source::Bitbucket => cache_ttls.bitbucket,
+            Source::Clomonitor => cache_ttls.clomonitor,
+            Source::Crunchbase => cache_ttls.crunchbase,
+            Source::Gitea => cache_ttls.gitea,
+            Source::Github => cache_ttls.github,
+            Source::Gitlab => cache_ttls.gitlab,
+            Source::Scorecard => cache_ttls.scorecard,
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Bitbucket => write!(f, "bitbucket"),
+            Source::Clomonitor => write!(f, "clomonitor"),
+            Source::Crunchbase => write!(f, "crunchbase"),
+            Source::Gitea => write!(f, "gitea"),
+            Source::Github => write!(f, "github"),
+            Source::Gitlab => write!(f, "gitlab"),
+            Source::Scorecard => write!(f, "scorecard"),
+        }
+    }
+}
+
+/// Cache entry.
+struct Entry {
+    source: Source,
+    key: String,
+    generated_at: DateTime<Utc>,
+}
+
+impl Entry {
+    /// Get the TTL status of the entry.
+    fn status(&self, cache_ttls: &CacheTtls) -> EntryStatus {
+        if self.generated_at + chrono::Duration::days(self.source.ttl(cache_ttls)) <= Utc::now() {
+            EntryStatus::Expired
+        } else {
+            EntryStatus::Fresh
+        }
+    }
+}
+
+/// TTL status of a cache entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryStatus {
+    Expired,
+    Fresh,
+}
+
+impl fmt::Display for EntryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryStatus::Expired => f.pad("expired"),
+            EntryStatus::Fresh => f.pad("fresh"),
+        }
+    }
+}
+
+/// Portable cache archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Archive {
+    version: u32,
+    files: BTreeMap<String, ArchiveFile>,
+}
+
+/// File in the cache archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchiveFile {
+    /// File content (base64 encoded).
+    data: String,
+
+    /// Last modification time of the file (used to track the age of some
+    /// entries, like the CLOMonitor reports summaries).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified_at: Option<DateTime<Utc>>,
+}
+
+/// Force the expiration of the cache entries provided.
+#[instrument(skip_all)]
+pub fn expire(args: &ExpireArgs) -> Result<()> {
+    let cache = Cache::new(&args.cache_dir.cache_dir)?;
+    let _lock = cache.lock()?;
+
+    let mut not_found = vec![];
+    match args.source {
+        Source::Clomonitor => {
+            for key in &args.keys {
+                let mut found = false;
+                for file_name in clomonitor_files_names(key)? {
+                    if cache.read(&file_name)?.is_some() {
+                        cache.set_modified(&file_name, SystemTime::UNIX_EPOCH)?;
+                        found = true;
+                    }
+                }
+                if !found {
+                    not_found.push(key);
+                }
+            }
+        }
+        Source::Crunchbase => {
+            not_found.extend(expire_entries::<Organization>(
+                &cache,
+                CRUNCHBASE_CACHE_NAMESPACE,
+                &args.keys,
+            )?);
+        }
+        Source::Bitbucket => {
+            not_found.extend(expire_entries::<RepositoryGithubData>(
+                &cache,
+                BITBUCKET_CACHE_NAMESPACE,
+                &args.keys,
+            )?);
+        }
+        Source::Gitea => {
+            not_found.extend(expire_entries::<RepositoryGithubData>(
+                &cache,
+                GITEA_CACHE_NAMESPACE,
+                &args.keys,
+            )?);
+        }
+        Source::Github => {
+            not_found.extend(expire_entries::<RepositoryGithubData>(
+                &cache,
+                GITHUB_CACHE_NAMESPACE,
+                &args.keys,
+            )?);
+        }
+        Source::Gitlab => {
+            not_found.extend(expire_entries::<RepositoryGithubData>(
+                &cache,
+                GITLAB_CACHE_NAMESPACE,
+                &args.keys,
+            )?);
+        }
+        Source::Scorecard => {
+            not_found.extend(expire_entries::<ScorecardEntry>(
+                &cache,
+                SCORECARD_CACHE_NAMESPACE,
+                &args.keys,
+            )?);
+        }
+    }
+
+    println!(
+        "Expired {} {} entries",
+        args.keys.len() - not_found.len(),
+        args.source
+    );
+    for key in not_found {
+        println!("Entry not found in cache: {key}");
+    }
+    Ok(())
+}
+
+/// Force the expiration of the entries provided in the namespace provided,
+/// returning the keys not found in the cache.
+fn expire_entries<'a, T: CachedData>(
+    cache: &Cache,
+    namespace: &str,
+    keys: &'a [String],
+) -> Result<Vec<&'a String>> {
+    let mut not_found = vec![];
+    for key in keys {
+        match cache.read_entry::<T>(namespace, key)? {
+            Some(mut value) => {
+                value.set_generated_at(DateTime::UNIX_EPOCH);
+                cache.write_entry(namespace, key, &value)?;
+            }
+            None => not_found.push(key),
+        }
+    }
+    Ok(not_found)
+}
+
+/// Export the cache to an archive file.
+#[instrument(skip_all)]
+pub fn export(args: &ExportArgs) -> Result<()> {
+    let cache = Cache::new(&args.cache_dir.cache_dir)?;
+
+    let mut archive = Archive {
+        version: ARCHIVE_VERSION,
+        files: BTreeMap::new(),
+    };
+    for file_name in cache.list()? {
+        let Some((modified_at, data)) = cache.read(&file_name)? else {
+            continue;
+        };
+        let file = ArchiveFile {
+            data: b64.encode(data),
+            modified_at: modified_at.map(Into::into),
+        };
+        archive.files.insert(file_name, file);
+    }
+    fs::write(&args.archive_file, serde_json::to_vec(&archive)?)?;
+
+    println!(
+        "Exported {} cache files to {}",
+        archive.files.len(),
+        args.archive_file.display()
+    );
+    Ok(())
+}
+
+/// Import the cache from an archive file.
+#[instrument(skip_all)]
+pub fn import(args: &ImportArgs) -> Result<()> {
+    let cache = Cache::new(&args.cache_dir.cache_dir)?;
+    let _lock = cache.lock()?;
+
+    let archive: Archive = serde_json::from_slice(&fs::read(&args.archive_file)?)
+        .context("the archive file provided is not valid")?;
+    if archive.version != ARCHIVE_VERSION {
+        bail!("unsupported archive version: {}", archive.version);
+    }
+    for file_name in archive.files.keys() {
+        validate_archive_file_name(file_name)?;
+    }
+    for (file_name, file) in &archive.files {
+        cache.write(file_name, &b64.decode(&file.data)?)?;
+        if let Some(modified_at) = file.modified_at {
+            cache.set_modified(file_name, modified_at.into())?;
+        }
+    }
+
+    println!(
+        "Imported {} cache files from {}",
+        archive.files.len(),
+        args.archive_file.display()
+    );
+    Ok(())
+}
+
+/// List the cache entries.
+#[instrument(skip_all)]
+pub fn list(args: &ListArgs) -> Result<()> {
+    let cache = Cache::new(&args.cache_dir.cache_dir)?;
+
+    let sources = match args.source {
+        Some(source) => vec![source],
+        None => vec![
+            Source::Clomonitor,
+            Source::Crunchbase,
+            Source::Github,
+            Source::Gitlab,
+            Source::Gitea,
+            Source::Bitbucket,
+            Source::Scorecard,
+        ],
+    };
+    let now = Utc::now();
+    for source in sources {
+        let entries = get_entries(&cache, source)?;
+        let expired = entries.iter().filter(|e| e.status(&args.cache_ttls) == EntryStatus::Expired).count();
+        println!(
+            "{source}: {} entries ({expired} expired, ttl: {} days)",
+            entries.len(),
+            source.ttl(&args.cache_ttls)
+        );
+        for entry in entries {
+            let age = now - entry.generated_at;
+            println!(
+                "  {:<7}  {:>4}d {:>2}h  {}",
+                entry.status(&args.cache_ttls),
+                age.num_days(),
+                age.num_hours() % 24,
+                entry.key
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the cache entries not referenced by the landscape data and
+/// settings files.
+#[instrument(skip_all)]
+pub async fn prune(args: &PruneArgs) -> Result<()> {
+    let cache = Cache::new(&args.cache_dir.cache_dir)?;
+    let landscape_data = LandscapeData::new(&args.data_source).await?;
+    let settings = LandscapeSettings::new(&args.settings_source).await?;
+    let _lock = cache.lock()?;
+
+    // Collect keys referenced by the landscape data
+    let mut clomonitor_names = HashSet::new();
+    let mut crunchbase_urls = HashSet::new();
+    let mut repositories_urls = HashSet::new();
+    for item in &landscape_data.items {
+        if let Some(name) = &item.clomonitor_name {
+            clomonitor_names.insert(name.as_str());
+        }
+        if let Some(url) = &item.crunchbase_url {
+            crunchbase_urls.insert(url.as_str());
+        }
+        for repo in item.repositories.iter().flatten() {
+            repositories_urls.insert(repo.url.as_str());
+        }
+    }
+
+    // CLOMonitor (entries of a foundation other than the one currently set
+    // in the settings are removed as well)
+    let foundation = settings.clomonitor.as_ref().map(|clomonitor| clomonitor.foundation.to_lowercase());
+    let mut removed_clomonitor = 0;
+    for entry in get_entries(&cache, Source::Clomonitor)? {
+        let Some((entry_foundation, project_name)) = entry.key.split_once('/') else {
+            continue;
+        };
+        if foundation.as_deref() != Some(entry_foundation.to_lowercase().as_str())
+            || !clomonitor_names.contains(project_name)
+        {
+            for file_name in clomonitor_files_names(&entry.key)? {
+                if cache.read(&file_name)?.is_some() {
+                    cache.remove(&file_name)?;
+                }
+            }
+            removed_clomonitor += 1;
+        }
+    }
+
+    // Crunchbase (the parents of the organizations referenced are kept as
+    // well, as they are used to resolve their ultimate parents)
+    let crunchbase_entries = cache.read_entries::<Organization>(CRUNCHBASE_CACHE_NAMESPACE)?;
+    add_parents_urls(&mut crunchbase_urls, &crunchbase_entries);
+    let mut removed_crunchbase = 0;
+    for url in crunchbase_entries.keys() {
+        if !crunchbase_urls.contains(url.as_str()) {
+            cache.remove_entry(CRUNCHBASE_CACHE_NAMESPACE, url)?;
+            removed_crunchbase += 1;
+        }
+    }
+
+    // Repositories (GitHub and other forges)
+    let mut removed_repositories = vec![];
+    for (source, namespace) in [
+        (Source::Github, GITHUB_CACHE_NAMESPACE),
+        (Source::Gitlab, GITLAB_CACHE_NAMESPACE),
+        (Source::Gitea, GITEA_CACHE_NAMESPACE),
+        (Source::Bitbucket, BITBUCKET_CACHE_NAMESPACE),
+    ] {
+        let mut removed = 0;
+        for url in cache.read_entries::<RepositoryGithubData>(namespace)?.keys() {
+            if !repositories_urls.contains(url.as_str()) {
+                cache.remove_entry(namespace, url)?;
+                removed += 1;
+            }
+        }
+        removed_repositories.push((source, removed));
+    }
+
+    // Scorecard
+    let mut removed_scorecard = 0;
+    for url in cache.read_entries::<ScorecardEntry>(SCORECARD_CACHE_NAMESPACE)?.keys() {
+        if !repositories_urls.contains(url.as_str()) {
+            cache.remove_entry(SCORECARD_CACHE_NAMESPACE, url)?;
+            removed_scorecard += 1;
+        }
+    }
+
+    // Repositories history
+    let mut removed_history = 0;
+    for url in cache.read_entries::<RepositoryHistory>(HISTORY_CACHE_NAMESPACE)?.keys() {
+        if !repositories_urls.contains(url.as_str()) {
+            cache.remove_entry(HISTORY_CACHE_NAMESPACE, url)?;
+            removed_history += 1;
+        }
+    }
+
+    // Settings images
+    let removed_settings_images = prune_settings_images(&cache, &settings)?;
+
+    println!("Removed {removed_clomonitor} clomonitor entries");
+    println!("Removed {removed_crunchbase} crunchbase entries");
+    for (source, removed) in removed_repositories {
+        println!("Removed {removed} {source} entries");
+    }
+    println!("Removed {removed_scorecard} scorecard entries");
+    println!("Removed {removed_history} history entries");
+    println!("Removed {removed_settings_images} settings images");
+    Ok(())
+}
+
+/// Add the urls of the parents (or acquirers) of the Crunchbase entries
+/// referenced to the urls provided, following their chain of parents.
+fn add_parents_urls<'a>(
+    crunchbase_urls: &mut HashSet<&'a str>,
+    crunchbase_entries: &'a BTreeMap<String, Organization>,
+) {
+    let mut parents_urls: Vec<&String> = crunchbase_urls
+        .iter()
+        .filter_map(|url| crunchbase_entries.get(*url))
+        .filter_map(|org| get_parent(org)?.crunchbase_url.as_ref())
+        .collect();
+    for _ in 1..ULTIMATE_PARENT_MAX_DEPTH {
+        let mut next_parents_urls = vec![];
+        for url in parents_urls {
+            if crunchbase_urls.insert(url.as_str()) {
+                next_parents_urls.extend(
+                    crunchbase_entries.get(url).and_then(|org| get_parent(org)?.crunchbase_url.as_ref()),
+                );
+            }
+        }
+        parents_urls = next_parents_urls;
+    }
+}
+
+/// Remove the settings images no longer referenced by the settings provided,
+/// returning the number of images removed.
+fn prune_settings_images(cache: &Cache, settings: &LandscapeSettings) -> Result<usize> {
+    let settings_images_files: HashSet<String> = settings_images_urls(settings)
+        .into_iter()
+        .map(|url| settings_image_cache_file(url))
+        .collect();
+    let mut removed = 0;
+    for file_name in cache.list()? {
+        if file_name.starts_with(SETTINGS_IMAGE_CACHE_FILE_PREFIX)
+            && !settings_images_files.contains(&file_name)
+        {
+            cache.remove(&file_name)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Get the cache entries of the source provided, sorted by key.
+fn get_entries(cache: &Cache, source: Source) -> Result<Vec<Entry>> {
+    let mut entries = vec![];
+    match source {
+        Source::Clomonitor => {
+            for file_name in cache.list()? {
+                let Some(key) = file_name
+                    .strip_prefix(CLOMONITOR_CACHE_FILE_PREFIX)
+                    .and_then(|name| name.strip_suffix(".svg"))
+                    .and_then(|name| name.split_once('_'))
+                    .map(|(foundation, project_name)| format!("{foundation}/{project_name}"))
+                else {
+                    continue;
+                };
+                let Some((modified_at, _)) = cache.read(&file_name)? else {
+                    continue;
+                };
+                entries.push(Entry {
+                    source,
+                    key,
+                    generated_at: modified_at.map_or(DateTime::UNIX_EPOCH, Into::into),
+                });
+            }
+        }
+        Source::Crunchbase => {
+            entries.extend(namespace_entries::<Organization>(
+                cache,
+                source,
+                CRUNCHBASE_CACHE_NAMESPACE,
+            )?);
+        }
+        Source::Bitbucket => {
+            entries.extend(namespace_entries::<RepositoryGithubData>(
+                cache,
+                source,
+                BITBUCKET_CACHE_NAMESPACE,
+            )?);
+        }
+        Source::Gitea => {
+            entries.extend(namespace_entries::<RepositoryGithubData>(
+                cache,
+                source,
+                GITEA_CACHE_NAMESPACE,
+            )?);
+        }
+        Source::Github => {
+            entries.extend(namespace_entries::<RepositoryGithubData>(
+                cache,
+                source,
+                GITHUB_CACHE_NAMESPACE,
+            )?);
+        }
+        Source::Gitlab => {
+            entries.extend(namespace_entries::<RepositoryGithubData>(
+                cache,
+                source,
+                GITLAB_CACHE_NAMESPACE,
+            )?);
+        }
+        Source::Scorecard => {
+            entries.extend(namespace_entries::<ScorecardEntry>(
+                cache,
+                source,
+                SCORECARD_CACHE_NAMESPACE,
+            )?);
+        }
+    }
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    Ok(entries)
+}
+
+/// Get the cache entries of the source provided, stored in the namespace
+/// provided.
+fn namespace_entries<T: CachedData>(cache: &Cache, source: Source, namespace: &str) -> Result<Vec<Entry>> {
+    let entries = cache
+        .read_entries::<T>(namespace)?
+        .into_iter()
+        .map(|(key, value)| Entry {
+            source,
+            key,
+            generated_at: value.generated_at(),
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Check that the name of a file in the archive is valid. Internal files, like
+/// temporary ones, are never exported, so names starting with a dot are not
+/// accepted (in any of the path components).
+fn validate_archive_file_name(file_name: &str) -> Result<()> {
+    if file_name
+        .split('/')
+        .any(|component| component.is_empty() || component.starts_with('.') || component.contains('\\'))
+    {
+        bail!("invalid file name in archive: {file_name}");
+    }
+    Ok(())
+}
+
+/// Get the names of the cache files (report summary and report) of the
+/// CLOMonitor entry provided (the key is expected to be in the format
+/// foundation/project).
+fn clomonitor_files_names(key: &str) -> Result<[String; 2]> {
+    let (foundation, project_name) = key
+        .split_once('/')
+        .ok_or_else(|| format_err!("invalid clomonitor key (expected foundation/project): {key}"))?;
+    let foundation = foundation.to_lowercase();
+    Ok([
+        format!("{CLOMONITOR_CACHE_FILE_PREFIX}{foundation}_{project_name}.svg"),
+        format!("{CLOMONITOR_CACHE_FILE_PREFIX}{foundation}_{project_name}.json"),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn entry_status() {
+        let entry = |days_ago: i64| Entry {
+            source: Source::Github,
+            key: "key".to_string(),
+            generated_at: Utc::now() - chrono::Duration::days(days_ago),
+        };
+        let cache_ttls = CacheTtls {
+            bitbucket: 7,
+            clomonitor: 7,
+            crunchbase: 7,
+            gitea: 7,
+            github: 7,
+            gitlab: 7,
+            scorecard: 7,
+        };
+
+        assert_eq!(entry(8).status(&cache_ttls), EntryStatus::Expired);
+        assert_eq!(entry(1).status(&cache_ttls), EntryStatus::Fresh);
+        assert_eq!(format!("{:<7}|", EntryStatus::Fresh), "fresh  |");
+    }
+
+    #[test]
+    fn validate_archive_file_name_valid() {
+        assert!(validate_archive_file_name("github/0a1b2c.json").is_ok());
+        assert!(validate_archive_file_name("clomonitor_cncf_artifacthub.svg").is_ok());
+    }
+
+    #[test]
+    fn validate_archive_file_name_invalid() {
+        for file_name in [
+            ".lock",
+            "github/.lock",
+            "github/.0a1b2c.json.1.0.tmp",
+            "../github/0a1b2c.json",
+            "/github/0a1b2c.json",
+            "github//0a1b2c.json",
+            "github\\0a1b2c.json",
+        ] {
+            assert!(validate_archive_file_name(file_name).is_err(), "{file_name}");
+        }
+    }
+
+    #[test]
+    fn import_rejects_invalid_file_names() {
+        let tmp_dir = TempDir::new().unwrap();
+        let archive_file = tmp_dir.path().join("archive.json");
+        let cache_dir = tmp_dir.path().join("cache");
+        let archive = Archive {
+            version: ARCHIVE_VERSION,
+            files: [("github/0a1b2c.json", "{}"), (".lock", "")]
+                .into_iter()
+                .map(|(file_name, data)| {
+                    let file = ArchiveFile {
+                        data: b64.encode(data),
+                        modified_at: None,
+                    };
+                    (file_name.to_string(), file)
+                })
+                .collect(),
+        };
+        fs::write(&archive_file, serde_json::to_vec(&archive).unwrap()).unwrap();
+
+        let result = import(&ImportArgs {
+            cache_dir: CacheDir {
+                cache_dir: Some(cache_dir.clone()),
+            },
+            archive_file,
+        });
+        let files = Cache::new(&Some(cache_dir)).unwrap().list().unwrap();
+
+        assert!(result.is_err());
+        assert!(files.is_empty());
+    }
+}
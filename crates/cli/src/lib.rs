@@ -6,6 +6,7 @@
 )]
 
 pub mod build;
+pub mod cache;
 pub mod deploy;
 pub mod new;
 pub mod serve;
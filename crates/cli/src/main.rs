@@ -8,6 +8,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use landscape2::build::{build, BuildArgs};
+use landscape2::cache::{self, CacheArgs, Operation};
 use landscape2::deploy::s3::{self};
 use landscape2::deploy::{DeployArgs, Provider};
 use landscape2::new::{new, NewArgs};
@@ -36,6 +37,9 @@ enum Command {
     /// Build landscape website.
     Build(BuildArgs),
 
+    /// Manage the cache used when building landscapes.
+    Cache(CacheArgs),
+
     /// Deploy landscape website (experimental).
     Deploy(DeployArgs),
 
@@ -61,12 +65,19 @@ async fn main() -> Result<()> {
             }
             tracing_subscriber::fmt::init();
         }
-        Command::Validate(_) => {}
+        Command::Cache(_) | Command::Validate(_) => {}
     }
 
     // Run command
     match &cli.command {
         Command::Build(args) => build(args).await?,
+        Command::Cache(args) => match &args.operation {
+            Operation::Expire(args) => cache::expire(args)?,
+            Operation::Export(args) => cache::export(args)?,
+            Operation::Import(args) => cache::import(args)?,
+            Operation::List(args) => cache::list(args)?,
+            Operation::Prune(args) => cache::prune(args).await?,
+        },
         Command::Deploy(args) => {
             match &args.provider {
                 Provider::S3(args) => s3::deploy(args).await?,
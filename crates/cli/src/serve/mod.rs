@@ -2,16 +2,36 @@
 
 use anyhow::Result;
 use axum::{
+    body::{self, Body},
     extract::Request,
-    http::{header::CACHE_CONTROL, HeaderValue},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE},
+        HeaderValue, StatusCode,
+    },
     middleware::{self, Next},
-    response::IntoResponse,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
     Router,
 };
-use std::{env, net::SocketAddr, path::PathBuf};
-use tokio::{net::TcpListener, signal};
+use futures::stream::{self, Stream};
+use std::{convert::Infallible, env, net::SocketAddr, path::PathBuf};
+use tokio::{
+    net::TcpListener,
+    signal,
+    sync::broadcast::{self, error::RecvError},
+};
 use tower_http::services::{ServeDir, ServeFile};
-use tracing::{info, instrument};
+use tracing::{error, info, instrument};
+
+/// Path of the endpoint used to notify browsers that they should reload.
+const LIVE_RELOAD_PATH: &str = "/_live-reload";
+
+/// Script injected in html documents when live reload is enabled.
+const LIVE_RELOAD_SCRIPT: &str =
+    r#"<script>new EventSource("/_live-reload").onmessage = () => window.location.reload();</script>"#;
 
 /// Serve arguments.
 #[derive(clap::Args)]
@@ -32,6 +52,11 @@ pub struct ServeArgs {
     /// Enable silent mode.
     #[arg(long, default_value_t = false)]
     pub silent: bool,
+
+    /// Channel used to notify connected browsers that they should reload. When
+    /// set, live reload is enabled (used by the build subcommand watch mode).
+    #[arg(skip)]
+    pub live_reload: Option<broadcast::Sender<()>>,
 }
 
 /// Serve landscape website.
@@ -40,13 +65,22 @@ pub async fn serve(args: &ServeArgs) -> Result<()> {
     // Setup router
     let landscape_dir = args.landscape_dir.clone().unwrap_or(env::current_dir()?);
     let index_path = landscape_dir.join("index.html");
-    let router: Router<()> = Router::new()
+    let mut router: Router<()> = Router::new()
         .nest_service(
             "/",
             ServeDir::new(&landscape_dir).not_found_service(ServeFile::new(&index_path)),
         )
         .fallback_service(ServeFile::new(index_path))
         .route_layer(middleware::from_fn(set_cache_control_header));
+    if let Some(live_reload) = &args.live_reload {
+        let live_reload = live_reload.clone();
+        router = router
+            .route(
+                LIVE_RELOAD_PATH,
+                get(move || async move { live_reload_events(live_reload.subscribe()) }),
+            )
+            .layer(middleware::from_fn(inject_live_reload_script));
+    }
 
     // Setup and launch HTTP server
     let addr: SocketAddr = args.addr.parse()?;
@@ -64,6 +98,51 @@ pub async fn serve(args: &ServeArgs) -> Result<()> {
     Ok(())
 }
 
+/// Middleware that injects the live reload script in html documents.
+async fn inject_live_reload_script(req: Request, next: Next) -> Response {
+    // Execute next handler
+    let resp = next.run(req).await;
+
+    // Only html documents are modified
+    let is_html = resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if !is_html {
+        return resp;
+    }
+
+    // Inject script just before the end of the body
+    let (mut parts, body) = resp.into_parts();
+    let html = match body::to_bytes(body, usize::MAX).await {
+        Ok(html) => String::from_utf8_lossy(&html).into_owned(),
+        Err(err) => {
+            error!(?err, "error reading html document");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let html = html.replacen("</body>", &format!("{LIVE_RELOAD_SCRIPT}</body>"), 1);
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Response::from_parts(parts, Body::from(html))
+}
+
+/// Stream of server-sent events used to notify a browser that it should
+/// reload when the landscape has been rebuilt.
+fn live_reload_events(rx: broadcast::Receiver<()>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = stream::unfold(rx, |mut rx| async move {
+        match rx.recv().await {
+            // A lagged receiver has missed at least one rebuild, so it should
+            // reload as well
+            Ok(()) | Err(RecvError::Lagged(_)) => Some((Ok(Event::default().data("reload")), rx)),
+            Err(RecvError::Closed) => None,
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
 /// Middleware that sets the cache control header in the response.
 async fn set_cache_control_header(req: Request, next: Next) -> impl IntoResponse {
     // Prepare header value (based on the request uri)
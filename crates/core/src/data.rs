@@ -39,9 +39,16 @@ pub type CrunchbaseUrl = String;
 /// Type alias to represent some repositories' GitHub data.
 pub type GithubData = BTreeMap<RepositoryUrl, RepositoryGithubData>;
 
+/// Type alias to represent some organizations' overrides, keyed by the
+/// organization key (usually the Crunchbase url).
+pub type OrganizationsOverrides = BTreeMap<CrunchbaseUrl, OrganizationOverrides>;
+
 /// Type alias to represent a GitHub repository url.
 pub type RepositoryUrl = String;
 
+/// Type alias to represent some repositories' OpenSSF Scorecard data.
+pub type ScorecardData = BTreeMap<RepositoryUrl, Scorecard>;
+
 /// Type alias to represent a subcategory name.
 pub type SubcategoryName = String;
 
@@ -132,8 +139,8 @@ impl LandscapeData {
     #[instrument(skip_all)]
     pub fn add_crunchbase_data(&mut self, crunchbase_data: &CrunchbaseData) {
         for item in &mut self.items {
-            if let Some(crunchbase_url) = item.crunchbase_url.as_ref() {
-                if let Some(org_crunchbase_data) = crunchbase_data.get(crunchbase_url) {
+            if let Some(organization_key) = item.organization_key() {
+                if let Some(org_crunchbase_data) = crunchbase_data.get(organization_key) {
                     item.crunchbase_data = Some(org_crunchbase_data.clone());
                 }
             }
@@ -206,6 +213,17 @@ impl LandscapeData {
         }
     }
 
+    /// Add items primary repository OpenSSF Scorecard data.
+    #[instrument(skip_all)]
+    pub fn add_scorecard_data(&mut self, scorecard_data: &ScorecardData) {
+        for item in &mut self.items {
+            if let Some(scorecard) = item.primary_repository().and_then(|repo| scorecard_data.get(&repo.url))
+            {
+                item.openssf_scorecard = Some(scorecard.clone());
+            }
+        }
+    }
+
     /// Add items member subcategory.
     #[instrument(skip_all)]
     pub fn add_member_subcategory(&mut self, members_category: &Option<String>) {
@@ -213,18 +231,18 @@ impl LandscapeData {
             return;
         };
 
-        // Create a map with the member subcategory for each Crunchbase url
+        // Create a map with the member subcategory for each organization
         let mut members_subcategories: HashMap<String, String> = HashMap::new();
         for item in self.items.iter().filter(|i| &i.category == members_category) {
-            if let Some(crunchbase_url) = &item.crunchbase_url {
-                members_subcategories.insert(crunchbase_url.clone(), item.subcategory.clone());
+            if let Some(organization_key) = item.organization_key() {
+                members_subcategories.insert(organization_key.clone(), item.subcategory.clone());
             }
         }
 
-        // Set item's member subcategory using the item's Crunchbase url to match
+        // Set item's member subcategory using the item's organization to match
         for item in &mut self.items {
-            if let Some(crunchbase_url) = &item.crunchbase_url {
-                if let Some(member_subcategory) = members_subcategories.get(crunchbase_url) {
+            if let Some(organization_key) = item.organization_key() {
+                if let Some(member_subcategory) = members_subcategories.get(organization_key) {
                     item.member_subcategory = Some(member_subcategory.clone());
                 }
             }
@@ -433,6 +451,7 @@ impl From<legacy::LandscapeData> for LandscapeData {
                         item.latest_annual_review_url = extra.annual_review_url;
                         item.linkedin_url = extra.linkedin_url;
                         item.mailing_list_url = extra.mailing_list_url;
+                        item.organization_id = extra.organization_id;
                         item.other_links = extra.other_links;
                         item.package_manager_url = extra.package_manager_url;
                         item.parent_project = extra.parent_project;
@@ -552,6 +571,9 @@ pub struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clomonitor_name: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clomonitor_report: Option<ClomonitorReport>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clomonitor_report_summary: Option<String>,
 
@@ -618,9 +640,15 @@ pub struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub openssf_best_practices_url: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openssf_scorecard: Option<Scorecard>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oss: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub other_links: Option<Vec<ItemLink>>,
 
@@ -686,6 +714,13 @@ impl Item {
         description
     }
 
+    /// Get the key used to identify the item's organization: the Crunchbase
+    /// url when available, or the organization id otherwise.
+    #[must_use]
+    pub fn organization_key(&self) -> Option<&String> {
+        self.crunchbase_url.as_ref().or(self.organization_id.as_ref())
+    }
+
     /// Get primary repository if available.
     #[must_use]
     pub fn primary_repository(&self) -> Option<&Repository> {
@@ -694,6 +729,15 @@ impl Item {
             .and_then(|repos| repos.iter().find(|r| r.primary.unwrap_or_default()))
     }
 
+    /// Check if the item's primary repository has been archived or disabled
+    /// upstream.
+    #[must_use]
+    pub fn primary_repository_archived(&self) -> bool {
+        self.primary_repository()
+            .and_then(|r| r.github_data.as_ref())
+            .is_some_and(|gh| gh.archived.unwrap_or_default() || gh.disabled.unwrap_or_default())
+    }
+
     /// Generate and set the item's id.
     fn set_id(&mut self) {
         self.id = format!(
@@ -748,6 +792,67 @@ pub struct AdditionalCategory {
     pub subcategory: SubcategoryName,
 }
 
+/// CLOMonitor report of a project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClomonitorReport {
+    pub generated_at: DateTime<Utc>,
+    pub score: ClomonitorScore,
+
+    /// Checks that did not pass in any of the project's repositories (in the
+    /// format section/check).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_checks: Vec<String>,
+
+    /// Checks passed (or exempted) in any of the project's repositories (in
+    /// the format section/check).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub passed_checks: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// CLOMonitor project score (global and per section, 0-100).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClomonitorScore {
+    pub global: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_practices: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legal: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<f64>,
+}
+
+impl ClomonitorScore {
+    /// Get the sections scores available, keyed by the section name.
+    #[must_use]
+    pub fn sections(&self) -> Vec<(&'static str, f64)> {
+        [
+            ("best_practices", self.best_practices),
+            ("documentation", self.documentation),
+            ("legal", self.legal),
+            ("license", self.license),
+            ("security", self.security),
+        ]
+        .into_iter()
+        .filter_map(|(section, score)| score.map(|score| (section, score)))
+        .collect()
+    }
+}
+
 /// Commit information.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Commit {
@@ -830,11 +935,19 @@ pub struct ItemSummary {
     pub use_case: Option<String>,
 }
 
-/// Organization information collected from Crunchbase.
+/// Organization information collected from Crunchbase (or provided in a local
+/// organizations file).
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Organization {
+    /// Date the data was collected. When it is not provided (i.e. in a local
+    /// organizations file), the Unix epoch is used, so that the data stays
+    /// the same across builds.
+    #[serde(default)]
     pub generated_at: DateTime<Utc>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acquired_by: Option<OrganizationRef>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub acquisitions: Option<Vec<Acquisition>>,
 
@@ -877,6 +990,136 @@ pub struct Organization {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_employees_min: Option<i64>,
 
+    /// Names of the fields overridden using the organizations overrides.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overridden_fields: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<OrganizationRef>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// Set when the data has expired and could not be refreshed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_exchange: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticker: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub twitter_url: Option<String>,
+
+    /// Top organization in the chain of parents (or acquirers) of this
+    /// organization, when it belongs to another one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ultimate_parent: Option<OrganizationRef>,
+}
+
+impl Organization {
+    /// Apply the overrides provided to the organization, keeping track of the
+    /// fields overridden.
+    pub fn apply_overrides(&mut self, overrides: &OrganizationOverrides) {
+        let mut overridden_fields = self.overridden_fields.take().unwrap_or_default();
+
+        // Override the fields provided (when set), keeping track of them
+        macro_rules! override_fields {
+            ($($field:ident),+ $(,)?) => {
+                $(
+                    if let Some(value) = &overrides.$field {
+                        self.$field = Some(value.clone());
+                        overridden_fields.push(stringify!($field).to_string());
+                    }
+                )+
+            };
+        }
+        override_fields!(
+            acquired_by,
+            acquisitions,
+            categories,
+            city,
+            company_type,
+            country,
+            description,
+            funding,
+            funding_rounds,
+            homepage_url,
+            kind,
+            linkedin_url,
+            name,
+            num_employees_max,
+            num_employees_min,
+            parent,
+            region,
+            stock_exchange,
+            ticker,
+            twitter_url,
+        );
+
+        if !overridden_fields.is_empty() {
+            overridden_fields.sort();
+            overridden_fields.dedup();
+            self.overridden_fields = Some(overridden_fields);
+        }
+    }
+}
+
+/// Organization fields overrides. Only the fields provided will be
+/// overridden in the organization data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrganizationOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acquired_by: Option<OrganizationRef>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acquisitions: Option<Vec<Acquisition>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_rounds: Option<Vec<FundingRound>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linkedin_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_employees_max: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_employees_min: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<OrganizationRef>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub region: Option<String>,
 
@@ -890,6 +1133,35 @@ pub struct Organization {
     pub twitter_url: Option<String>,
 }
 
+/// Reference to an organization related to another one (i.e. its parent or
+/// the organization that acquired it).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationRef {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crunchbase_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+}
+
+impl OrganizationRef {
+    /// Get the key used to identify the organization referenced. It uses
+    /// the same key space as [`Item::organization_key`] (Crunchbase url or
+    /// organization id), falling back to the organization name.
+    #[must_use]
+    pub fn key(&self) -> &String {
+        self.crunchbase_url.as_ref().or(self.organization_id.as_ref()).unwrap_or(&self.name)
+    }
+
+    /// Get the keys that can be used to look up the organization referenced
+    /// (its Crunchbase url and organization id, when available).
+    pub fn lookup_keys(&self) -> impl Iterator<Item = &String> {
+        self.crunchbase_url.iter().chain(self.organization_id.iter())
+    }
+}
+
 /// Release information.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Release {
@@ -912,6 +1184,45 @@ pub struct Repository {
     pub primary: Option<bool>,
 }
 
+/// OpenSSF Scorecard results of a repository.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scorecard {
+    pub generated_at: DateTime<Utc>,
+
+    /// Aggregate score (0-10).
+    pub score: f64,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<ScorecardCheck>,
+
+    /// Date of the Scorecard analysis.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    /// Set when the data has expired and could not be refreshed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// OpenSSF Scorecard check result.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScorecardCheck {
+    pub name: String,
+
+    /// Check score (0-10, -1 when the check is not applicable or could not
+    /// be run).
+    pub score: i64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
 /// Repository information collected from GitHub.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct RepositoryGithubData {
@@ -925,9 +1236,24 @@ pub struct RepositoryGithubData {
     pub topics: Vec<String>,
     pub url: String,
 
+    /// Set when the repository has been archived upstream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+
+    /// Branch used to collect the commits data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// Set when the repository has been disabled upstream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub first_commit: Option<Commit>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forks: Option<i64>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub languages: Option<BTreeMap<String, i64>>,
 
@@ -936,6 +1262,29 @@ pub struct RepositoryGithubData {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+
+    /// Number of open issues (pull requests not included).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_issues: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_pull_requests: Option<i64>,
+
+    /// Median time (in seconds) it took to merge the pull requests merged
+    /// during the last 90 days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_requests_median_merge_time: Option<i64>,
+
+    /// Number of releases published during the last year.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub releases_last_year: Option<usize>,
+
+    /// Set when the data has expired and could not be refreshed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watchers: Option<i64>,
 }
 
 #[cfg(test)]
@@ -1141,6 +1490,39 @@ mod tests {
         assert_eq!(landscape_data.items[0].oss, Some(true));
     }
 
+    #[test]
+    fn landscape_data_add_scorecard_data() {
+        let mut landscape_data = LandscapeData::default();
+        let repository_url = "https://repo.url/test".to_string();
+        landscape_data.items.push(Item {
+            repositories: Some(vec![Repository {
+                url: repository_url.clone(),
+                primary: Some(true),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        landscape_data.items.push(Item {
+            repositories: Some(vec![Repository {
+                url: repository_url.clone(),
+                primary: Some(false),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+
+        let mut scorecard_data = ScorecardData::default();
+        let scorecard = Scorecard {
+            score: 7.5,
+            ..Default::default()
+        };
+        scorecard_data.insert(repository_url, scorecard.clone());
+
+        landscape_data.add_scorecard_data(&scorecard_data);
+        assert_eq!(landscape_data.items[0].openssf_scorecard, Some(scorecard));
+        assert_eq!(landscape_data.items[1].openssf_scorecard, None);
+    }
+
     #[test]
     fn landscape_data_add_member_subcategory() {
         let mut landscape_data = LandscapeData::default();
@@ -1382,6 +1764,7 @@ mod tests {
                             incubating: Some(date),
                             linkedin_url: Some("linkedin_url".to_string()),
                             mailing_list_url: Some("mailing_list_url".to_string()),
+                            organization_id: Some("organization_id".to_string()),
                             other_links: Some(vec![ItemLink {
                                 name: "name".to_string(),
                                 url: "https://link.url".to_string(),
@@ -1448,6 +1831,7 @@ mod tests {
                 blog_url: Some("blog_url".to_string()),
                 chat_channel: Some("chat_channel".to_string()),
                 clomonitor_name: Some("clomonitor_name".to_string()),
+                clomonitor_report: None,
                 clomonitor_report_summary: None,
                 crunchbase_data: None,
                 crunchbase_url: Some("crunchbase_url".to_string()),
@@ -1470,6 +1854,8 @@ mod tests {
                 latest_annual_review_at: Some(date),
                 latest_annual_review_url: Some("annual_review_url".to_string()),
                 openssf_best_practices_url: Some("url_for_bestpractices".to_string()),
+                openssf_scorecard: None,
+                organization_id: Some("organization_id".to_string()),
                 oss: None,
                 other_links: Some(vec![ItemLink {
                     name: "name".to_string(),
@@ -1595,6 +1981,38 @@ mod tests {
         assert_eq!(item.description(), Some(&"crunchbase description".to_string()));
     }
 
+    #[test]
+    fn item_primary_repository_archived() {
+        let repository = |primary: bool, archived: Option<bool>, disabled: Option<bool>| Repository {
+            github_data: Some(RepositoryGithubData {
+                archived,
+                disabled,
+                ..Default::default()
+            }),
+            primary: Some(primary),
+            ..Default::default()
+        };
+        for (repositories, expected) in [
+            (None, false),
+            (Some(vec![repository(true, None, None)]), false),
+            (Some(vec![repository(true, Some(true), None)]), true),
+            (Some(vec![repository(true, None, Some(true))]), true),
+            (
+                Some(vec![
+                    repository(true, None, None),
+                    repository(false, Some(true), None),
+                ]),
+                false,
+            ),
+        ] {
+            let item = Item {
+                repositories,
+                ..Default::default()
+            };
+            assert_eq!(item.primary_repository_archived(), expected);
+        }
+    }
+
     #[test]
     fn item_primary_repository_found() {
         let item = Item {
@@ -1673,4 +2091,44 @@ mod tests {
         item.set_website();
         assert_eq!(item.website, "homepage_url".to_string());
     }
+
+    #[test]
+    fn organization_apply_overrides() {
+        let mut org = Organization {
+            city: Some("city".to_string()),
+            country: Some("country".to_string()),
+            name: Some("name".to_string()),
+            ..Default::default()
+        };
+        let overrides = OrganizationOverrides {
+            city: Some("overridden_city".to_string()),
+            ticker: Some("TICK".to_string()),
+            ..Default::default()
+        };
+
+        org.apply_overrides(&overrides);
+        assert_eq!(
+            org,
+            Organization {
+                city: Some("overridden_city".to_string()),
+                country: Some("country".to_string()),
+                name: Some("name".to_string()),
+                overridden_fields: Some(vec!["city".to_string(), "ticker".to_string()]),
+                ticker: Some("TICK".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn organization_apply_empty_overrides() {
+        let mut org = Organization {
+            name: Some("name".to_string()),
+            ..Default::default()
+        };
+
+        org.apply_overrides(&OrganizationOverrides::default());
+        assert_eq!(org.overridden_fields, None);
+        assert_eq!(org.name, Some("name".to_string()));
+    }
 }
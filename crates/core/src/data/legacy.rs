@@ -165,6 +165,7 @@ pub(super) struct ItemExtra {
     pub incubating: Option<NaiveDate>,
     pub linkedin_url: Option<String>,
     pub mailing_list_url: Option<String>,
+    pub organization_id: Option<String>,
     pub other_links: Option<Vec<ItemLink>>,
     pub package_manager_url: Option<String>,
     pub parent_project: Option<String>,
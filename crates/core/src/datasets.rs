@@ -6,13 +6,14 @@
 //! that they can be fetched when needed. These datasets are not meant to be
 //! consumed by other applications, as they can change at any time.
 
-use self::{base::Base, embed::Embed, full::Full};
+use self::{base::Base, embed::Embed, full::Full, trends::Trends};
 use crate::{
     data::{CrunchbaseData, GithubData, LandscapeData},
     games::LandscapeGames,
     guide::LandscapeGuide,
+    history::RepositoriesHistory,
     settings::LandscapeSettings,
-    stats::Stats,
+    stats::{Stats, TrendsStats},
 };
 
 /// Input used to create a new Datasets instance.
@@ -22,6 +23,7 @@ pub struct NewDatasetsInput<'a> {
     pub games: &'a Option<LandscapeGames>,
     pub github_data: &'a GithubData,
     pub guide: &'a Option<LandscapeGuide>,
+    pub history: &'a RepositoriesHistory,
     pub landscape_data: &'a LandscapeData,
     pub qr_code: &'a String,
     pub settings: &'a LandscapeSettings,
@@ -41,17 +43,24 @@ pub struct Datasets {
 
     /// #[crate::stats]
     pub stats: Stats,
+
+    /// #[trends]
+    pub trends: Trends,
 }
 
 impl Datasets {
     /// Create a new datasets instance.
     #[must_use]
     pub fn new(i: &NewDatasetsInput) -> Self {
+        let mut stats = Stats::new(i.landscape_data, i.settings);
+        stats.trends = TrendsStats::new(i.landscape_data, i.history);
+
         Datasets {
             base: Base::new(i.landscape_data, i.settings, i.guide, i.games, i.qr_code),
             embed: Embed::new(i.landscape_data, i.settings),
             full: Full::new(i.landscape_data, i.crunchbase_data, i.github_data),
-            stats: Stats::new(i.landscape_data, i.settings),
+            stats,
+            trends: Trends::new(i.landscape_data, i.history),
         }
     }
 }
@@ -441,6 +450,46 @@ pub mod full {
     }
 }
 
+/// Trends dataset.
+///
+/// This dataset contains the history of some metrics of the items' primary
+/// repositories (stars, contributors and commits activity). It is used by the
+/// web application to display how projects have evolved over time.
+pub mod trends {
+    use crate::{
+        data::LandscapeData,
+        history::{RepositoriesHistory, RepositoryHistory},
+    };
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    /// Trends dataset information.
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct Trends {
+        /// Primary repository history, keyed by item id.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub items: BTreeMap<String, RepositoryHistory>,
+    }
+
+    impl Trends {
+        /// Create a new Trends instance from the landscape data and
+        /// repositories history provided.
+        #[must_use]
+        pub fn new(landscape_data: &LandscapeData, history: &RepositoriesHistory) -> Self {
+            let items = landscape_data
+                .items
+                .iter()
+                .filter_map(|item| {
+                    let repo_history = history.get(&item.primary_repository()?.url)?;
+                    Some((item.id.clone(), repo_history.clone()))
+                })
+                .collect();
+
+            Trends { items }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,6 +498,7 @@ mod tests {
         datasets::base,
         games::Quiz,
         guide::{self, LandscapeGuide},
+        history::HistoryPoint,
         settings::{self, *},
     };
     use chrono::{NaiveDate, Utc};
@@ -461,6 +511,7 @@ mod tests {
             games: &None,
             github_data: &GithubData::default(),
             guide: &None,
+            history: &RepositoriesHistory::default(),
             landscape_data: &LandscapeData::default(),
             qr_code: &String::default(),
             settings: &LandscapeSettings::default(),
@@ -811,4 +862,44 @@ mod tests {
         };
         pretty_assertions::assert_eq!(full, expected_full);
     }
+
+    #[test]
+    fn trends_new() {
+        let item = data::Item {
+            id: "item1".to_string(),
+            repositories: Some(vec![
+                Repository {
+                    url: "https://repository1.url".to_string(),
+                    primary: Some(true),
+                    ..Default::default()
+                },
+                Repository {
+                    url: "https://repository2.url".to_string(),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        let landscape_data = LandscapeData {
+            items: vec![item],
+            ..Default::default()
+        };
+        let point = HistoryPoint {
+            date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            stars: 10,
+            ..Default::default()
+        };
+        let history: RepositoriesHistory = vec![
+            ("https://repository1.url".to_string(), vec![point.clone()]),
+            ("https://repository2.url".to_string(), vec![point.clone()]),
+        ]
+        .into_iter()
+        .collect();
+
+        let trends = Trends::new(&landscape_data, &history);
+        let expected_trends = Trends {
+            items: vec![("item1".to_string(), vec![point])].into_iter().collect(),
+        };
+        pretty_assertions::assert_eq!(trends, expected_trends);
+    }
 }
@@ -0,0 +1,139 @@
+//! This module defines some types used to represent the history of some of
+//! the repositories metrics across builds, as well as the functionality used
+//! to maintain it.
+
+use crate::data::RepositoryGithubData;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Number of days of history kept for each repository.
+pub const HISTORY_MAX_DAYS: i64 = 730;
+
+/// Type alias to represent the history of the repositories metrics, keyed by
+/// the repository url.
+pub type RepositoriesHistory = BTreeMap<String, RepositoryHistory>;
+
+/// Type alias to represent the history of a repository metrics (one point per
+/// day, sorted by date).
+pub type RepositoryHistory = Vec<HistoryPoint>;
+
+/// Repository metrics on a given day.
+///
+/// Points are dated on the day of the build that recorded them, so the history
+/// gets one point per build day even when the data collected from GitHub has
+/// been reused from the cache (in which case the metrics are the same as the
+/// ones in the previous point).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    pub date: NaiveDate,
+
+    /// Number of commits during the last week.
+    pub commits: i64,
+
+    pub contributors: usize,
+    pub stars: i64,
+}
+
+impl HistoryPoint {
+    /// Create a new history point for the date provided from the repository
+    /// data given.
+    #[must_use]
+    pub fn new(gh_data: &RepositoryGithubData, date: NaiveDate) -> Self {
+        Self {
+            date,
+            commits: gh_data.participation_stats.last().copied().unwrap_or_default(),
+            contributors: gh_data.contributors.count,
+            stars: gh_data.stars,
+        }
+    }
+}
+
+/// Add the point provided to the repository history, replacing the existing
+/// point for the same day (if any) and removing the points older than
+/// HISTORY_MAX_DAYS. Returns true if the history was modified.
+pub fn add_point(history: &mut RepositoryHistory, point: HistoryPoint) -> bool {
+    // Points dated before the latest one available are ignored
+    if history.last().is_some_and(|last| last.date > point.date) {
+        return false;
+    }
+    if history.last() == Some(&point) {
+        return false;
+    }
+
+    // Add new point (or replace the existing one for the same day)
+    if history.last().is_some_and(|last| last.date == point.date) {
+        history.pop();
+    }
+    let oldest_date = point.date - Duration::days(HISTORY_MAX_DAYS);
+    history.push(point);
+    history.retain(|p| p.date > oldest_date);
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_point_appends_new_day() {
+        let mut history = vec![point("2024-01-01", 10)];
+        assert!(add_point(&mut history, point("2024-01-02", 12)));
+        assert_eq!(history, vec![point("2024-01-01", 10), point("2024-01-02", 12)]);
+    }
+
+    #[test]
+    fn add_point_replaces_same_day() {
+        let mut history = vec![point("2024-01-01", 10)];
+        assert!(add_point(&mut history, point("2024-01-01", 11)));
+        assert_eq!(history, vec![point("2024-01-01", 11)]);
+    }
+
+    #[test]
+    fn add_point_ignores_same_or_older_points() {
+        let mut history = vec![point("2024-01-02", 10)];
+        assert!(!add_point(&mut history, point("2024-01-02", 10)));
+        assert!(!add_point(&mut history, point("2024-01-01", 8)));
+        assert_eq!(history, vec![point("2024-01-02", 10)]);
+    }
+
+    #[test]
+    fn add_point_removes_old_points() {
+        let mut history = vec![point("2022-01-01", 1), point("2023-06-01", 5)];
+        assert!(add_point(&mut history, point("2024-01-02", 10)));
+        assert_eq!(history, vec![point("2023-06-01", 5), point("2024-01-02", 10)]);
+    }
+
+    #[test]
+    fn history_point_new() {
+        let gh_data = RepositoryGithubData {
+            contributors: crate::data::Contributors {
+                count: 5,
+                ..Default::default()
+            },
+            generated_at: "2024-01-02T03:04:05Z".parse().unwrap(),
+            participation_stats: vec![1, 2, 3],
+            stars: 10,
+            ..Default::default()
+        };
+        let build_date = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+        assert_eq!(
+            HistoryPoint::new(&gh_data, build_date),
+            HistoryPoint {
+                date: build_date,
+                commits: 3,
+                contributors: 5,
+                stars: 10,
+            }
+        );
+    }
+
+    fn point(date: &str, stars: i64) -> HistoryPoint {
+        HistoryPoint {
+            date: date.parse().unwrap(),
+            stars,
+            ..Default::default()
+        }
+    }
+}
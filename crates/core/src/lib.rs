@@ -9,6 +9,7 @@ pub mod data;
 pub mod datasets;
 pub mod games;
 pub mod guide;
+pub mod history;
 pub mod settings;
 pub mod stats;
 mod util;
@@ -62,6 +62,9 @@ pub struct LandscapeSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub categories: Option<Vec<Category>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clomonitor: Option<Clomonitor>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub colors: Option<Colors>,
 
@@ -95,6 +98,9 @@ pub struct LandscapeSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub members_category: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organizations_overrides: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub screenshot_width: Option<u32>,
 
@@ -204,6 +210,7 @@ impl LandscapeSettings {
 
         self.validate_base_path()?;
         self.validate_categories()?;
+        self.validate_clomonitor()?;
         self.validate_colors()?;
         self.validate_featured_items()?;
         self.validate_footer()?;
@@ -211,6 +218,7 @@ impl LandscapeSettings {
         self.validate_header()?;
         self.validate_images()?;
         self.validate_members_category()?;
+        self.validate_organizations_overrides()?;
         self.validate_osano()?;
         self.validate_screenshot_width()?;
         self.validate_tags()?;
@@ -264,6 +272,23 @@ impl LandscapeSettings {
         Ok(())
     }
 
+    /// Check CLOMonitor configuration is valid.
+    fn validate_clomonitor(&self) -> Result<()> {
+        let Some(clomonitor) = &self.clomonitor else {
+            return Ok(());
+        };
+
+        // Check foundation is not empty
+        if clomonitor.foundation.is_empty() {
+            bail!("clomonitor foundation cannot be empty");
+        }
+
+        // Check url is valid
+        validate_url("clomonitor", &clomonitor.url)?;
+
+        Ok(())
+    }
+
     /// Check colors format.
     fn validate_colors(&self) -> Result<()> {
         if let Some(colors) = &self.colors {
@@ -434,6 +459,25 @@ impl LandscapeSettings {
         Ok(())
     }
 
+    /// Check organizations overrides file location is valid.
+    fn validate_organizations_overrides(&self) -> Result<()> {
+        let Some(organizations_overrides) = &self.organizations_overrides else {
+            return Ok(());
+        };
+
+        // Check organizations overrides is not empty
+        if organizations_overrides.is_empty() {
+            bail!("organizations overrides cannot be empty");
+        }
+
+        // Check url is valid (when a url is provided instead of a local path)
+        if organizations_overrides.starts_with("http://") || organizations_overrides.starts_with("https://") {
+            validate_url("organizations_overrides", &self.organizations_overrides)?;
+        }
+
+        Ok(())
+    }
+
     /// Check Osano configuration is valid.
     fn validate_osano(&self) -> Result<()> {
         let Some(osano) = &self.osano else { return Ok(()) };
@@ -507,6 +551,15 @@ lazy_static! {
             .expect("exprs in RGBA to be valid");
 }
 
+/// CLOMonitor configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Clomonitor {
+    pub foundation: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
 /// Colors used across the landscape UI.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Colors {
@@ -980,6 +1033,53 @@ mod tests {
         settings.validate().unwrap();
     }
 
+    #[test]
+    fn settings_validate_clomonitor_succeeds() {
+        let settings = LandscapeSettings {
+            foundation: "Foundation".to_string(),
+            url: "https://example.url".to_string(),
+            clomonitor: Some(Clomonitor {
+                foundation: "foundation".to_string(),
+                url: Some("https://clomonitor.example.url".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        settings.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "clomonitor foundation cannot be empty")]
+    fn settings_validate_clomonitor_empty_foundation() {
+        let settings = LandscapeSettings {
+            foundation: "Foundation".to_string(),
+            url: "https://example.url".to_string(),
+            clomonitor: Some(Clomonitor {
+                foundation: String::new(),
+                url: None,
+            }),
+            ..Default::default()
+        };
+
+        settings.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid clomonitor url")]
+    fn settings_validate_clomonitor_invalid_url() {
+        let settings = LandscapeSettings {
+            foundation: "Foundation".to_string(),
+            url: "https://example.url".to_string(),
+            clomonitor: Some(Clomonitor {
+                foundation: "foundation".to_string(),
+                url: Some("invalid-url".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        settings.validate().unwrap();
+    }
+
     #[test]
     #[should_panic(expected = "color1 is not valid (expected format: \"rgba(0, 107, 204, 1)\")")]
     fn settings_validate_colors_invalid_format() {
@@ -1318,6 +1418,44 @@ mod tests {
         settings.validate().unwrap();
     }
 
+    #[test]
+    fn settings_validate_organizations_overrides_succeeds() {
+        let settings = LandscapeSettings {
+            foundation: "Foundation".to_string(),
+            url: "https://example.url".to_string(),
+            organizations_overrides: Some("organizations-overrides.yml".to_string()),
+            ..Default::default()
+        };
+
+        settings.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "organizations overrides cannot be empty")]
+    fn settings_validate_organizations_overrides_empty() {
+        let settings = LandscapeSettings {
+            foundation: "Foundation".to_string(),
+            url: "https://example.url".to_string(),
+            organizations_overrides: Some(String::new()),
+            ..Default::default()
+        };
+
+        settings.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid organizations_overrides url")]
+    fn settings_validate_organizations_overrides_invalid_url() {
+        let settings = LandscapeSettings {
+            foundation: "Foundation".to_string(),
+            url: "https://example.url".to_string(),
+            organizations_overrides: Some("https://".to_string()),
+            ..Default::default()
+        };
+
+        settings.validate().unwrap();
+    }
+
     #[test]
     fn settings_validate_osano_succeeds() {
         let settings = LandscapeSettings {
@@ -5,11 +5,14 @@ use super::{
     data::{CategoryName, SubcategoryName},
     settings::{LandscapeSettings, TagName},
 };
-use crate::data::LandscapeData;
-use chrono::{Datelike, Utc};
+use crate::{
+    data::{ClomonitorScore, Item, LandscapeData},
+    history::{RepositoriesHistory, RepositoryHistory},
+};
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Format used to represent a date as year-month.
 pub const YEAR_MONTH_FORMAT: &str = "%Y-%m";
@@ -38,6 +41,15 @@ pub struct Stats {
     /// Repositories stats.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repositories: Option<RepositoriesStats>,
+
+    /// OpenSSF Scorecard stats.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scorecard: Option<ScorecardStats>,
+
+    /// Repositories trends stats (only available when the repositories
+    /// history is provided, see TrendsStats::new).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trends: Option<TrendsStats>,
 }
 
 impl Stats {
@@ -50,6 +62,8 @@ impl Stats {
             organizations: OrganizationsStats::new(landscape_data),
             projects: ProjectsStats::new(landscape_data),
             repositories: RepositoriesStats::new(landscape_data),
+            scorecard: ScorecardStats::new(landscape_data),
+            trends: None,
         }
     }
 }
@@ -71,6 +85,17 @@ pub struct MembersStats {
     /// Number of members per subcategory.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub subcategories: BTreeMap<String, u64>,
+
+    /// Number of members per ultimate parent organization (only members that
+    /// belong to a group of organizations are included), keyed by the parent
+    /// key (see `ultimate_parents_names`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ultimate_parents: BTreeMap<String, u64>,
+
+    /// Names of the ultimate parent organizations, keyed by their Crunchbase
+    /// url (or by their name when the url is not available).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ultimate_parents_names: BTreeMap<String, String>,
 }
 
 impl MembersStats {
@@ -78,6 +103,7 @@ impl MembersStats {
     /// the landscape and the settings.
     fn new(landscape_data: &LandscapeData, settings: &LandscapeSettings) -> Option<Self> {
         let mut stats = MembersStats::default();
+        let ultimate_parents = collect_ultimate_parents(&landscape_data.items);
 
         // Collect stats from landscape items
         for item in &landscape_data.items {
@@ -94,6 +120,12 @@ impl MembersStats {
 
                     // Number of members per subcategory
                     increment(&mut stats.subcategories, &item.subcategory, 1);
+
+                    // Number of members per ultimate parent
+                    if let Some((key, name)) = get_ultimate_parent(item, &ultimate_parents) {
+                        increment(&mut stats.ultimate_parents, key, 1);
+                        stats.ultimate_parents_names.insert(key.clone(), name.clone());
+                    }
                 }
             }
         }
@@ -111,6 +143,8 @@ impl MembersStats {
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct OrganizationsStats {
     /// Total number of acquisitions per year across all organizations.
+    /// Acquisitions reported by several organizations (i.e. a parent and its
+    /// subsidiaries) are only counted once.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub acquisitions: BTreeMap<Year, u64>,
 
@@ -125,6 +159,17 @@ pub struct OrganizationsStats {
     /// Total money raised on funding rounds per year across all organizations.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub funding_rounds_money_raised: BTreeMap<Year, u64>,
+
+    /// Total funding per ultimate parent organization (only organizations
+    /// that belong to a group of organizations are included), keyed by the
+    /// parent key (see `ultimate_parents_names`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ultimate_parents_funding: BTreeMap<String, u64>,
+
+    /// Names of the ultimate parent organizations, keyed by their Crunchbase
+    /// url (or by their name when the url is not available).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ultimate_parents_names: BTreeMap<String, String>,
 }
 
 impl OrganizationsStats {
@@ -133,20 +178,30 @@ impl OrganizationsStats {
     fn new(landscape_data: &LandscapeData) -> Option<Self> {
         let mut stats = OrganizationsStats::default();
         let mut crunchbase_data_processed = HashSet::new();
+        let mut acquisitions_processed = HashSet::new();
+        let ultimate_parents = collect_ultimate_parents(&landscape_data.items);
 
         // Collect stats from landscape items
         for item in &landscape_data.items {
-            // Check if this crunchbase data has already been processed
-            if let Some(url) = item.crunchbase_url.as_ref() {
-                if crunchbase_data_processed.contains(url) {
+            // Check if this organization data has already been processed
+            if let Some(key) = item.organization_key() {
+                if crunchbase_data_processed.contains(key) {
                     continue;
                 }
-                crunchbase_data_processed.insert(url);
+                crunchbase_data_processed.insert(key);
             }
 
             // Acquisitions
             if let Some(acquisitions) = item.crunchbase_data.as_ref().and_then(|d| d.acquisitions.as_ref()) {
                 for acq in acquisitions {
+                    // Skip acquisitions already processed from another organization
+                    if let Some(acquiree) = acq.acquiree_cb_permalink.as_ref().or(acq.acquiree_name.as_ref())
+                    {
+                        if !acquisitions_processed.insert((acquiree, acq.announced_on)) {
+                            continue;
+                        }
+                    }
+
                     if let Some(announced_on) = acq.announced_on {
                         let year = announced_on.format("%Y").to_string();
                         increment(&mut stats.acquisitions, &year, 1);
@@ -180,6 +235,18 @@ impl OrganizationsStats {
                     }
                 }
             }
+
+            // Funding per ultimate parent
+            if let Some((key, name)) = get_ultimate_parent(item, &ultimate_parents) {
+                if let Some(funding) = item.crunchbase_data.as_ref().and_then(|d| d.funding) {
+                    increment(
+                        &mut stats.ultimate_parents_funding,
+                        key,
+                        u64::try_from(funding).unwrap_or_default(),
+                    );
+                    stats.ultimate_parents_names.insert(key.clone(), name.clone());
+                }
+            }
         }
 
         // Return stats collected
@@ -213,6 +280,10 @@ pub struct ProjectsStats {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub category: BTreeMap<CategoryName, CategoryProjectsStats>,
 
+    /// CLOMonitor scores stats.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clomonitor: Option<ClomonitorStats>,
+
     /// Promotions from incubating to graduated per year-month.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub incubating_to_graduated: BTreeMap<YearMonth, u64>,
@@ -240,6 +311,7 @@ impl ProjectsStats {
         let mut stats = ProjectsStats::default();
 
         // Collect stats from landscape items
+        let mut clomonitor_scores = ClomonitorScores::default();
         let mut projects_seen = vec![];
         for item in &landscape_data.items {
             if let Some(maturity) = &item.maturity {
@@ -281,6 +353,11 @@ impl ProjectsStats {
                     );
                 }
 
+                // CLOMonitor scores
+                if let Some(report) = &item.clomonitor_report {
+                    clomonitor_scores.add(maturity, &report.score);
+                }
+
                 // Number of projects per maturity
                 increment(&mut stats.maturity, maturity, 1);
 
@@ -308,6 +385,7 @@ impl ProjectsStats {
         }
         stats.accepted_at_rt = calculate_running_total(&stats.accepted_at);
         stats.audits_rt = calculate_running_total(&stats.audits);
+        stats.clomonitor = clomonitor_scores.stats();
 
         // Return stats collected
         if stats != ProjectsStats::default() {
@@ -317,6 +395,54 @@ impl ProjectsStats {
     }
 }
 
+/// Some stats about the projects' CLOMonitor scores.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClomonitorStats {
+    /// Average global score.
+    pub average_score: f64,
+
+    /// Average global score per maturity.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub average_score_by_maturity: BTreeMap<String, f64>,
+
+    /// Average score per section.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub average_sections_scores: BTreeMap<String, f64>,
+
+    /// Number of projects with a CLOMonitor report.
+    pub projects: u64,
+}
+
+/// Helper used to collect the projects' CLOMonitor scores.
+#[derive(Debug, Default)]
+struct ClomonitorScores {
+    global: Vec<f64>,
+    maturity: BTreeMap<String, Vec<f64>>,
+    sections: BTreeMap<String, Vec<f64>>,
+}
+
+impl ClomonitorScores {
+    /// Add the score of a project with the maturity provided.
+    fn add(&mut self, maturity: &str, score: &ClomonitorScore) {
+        self.global.push(score.global);
+        self.maturity.entry(maturity.to_string()).or_default().push(score.global);
+        for (section, section_score) in score.sections() {
+            self.sections.entry(section.to_string()).or_default().push(section_score);
+        }
+    }
+
+    /// Prepare the stats from the scores collected.
+    fn stats(self) -> Option<ClomonitorStats> {
+        let average_score = average(&self.global)?;
+        Some(ClomonitorStats {
+            average_score,
+            average_score_by_maturity: averages(self.maturity),
+            average_sections_scores: averages(self.sections),
+            projects: self.global.len() as u64,
+        })
+    }
+}
+
 /// Some stats about the projects in a category and its subcategories.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CategoryProjectsStats {
@@ -337,6 +463,9 @@ pub struct RepositoriesStats {
     /// Number of contributors.
     pub contributors: u64,
 
+    /// Number of forks.
+    pub forks: u64,
+
     /// Number of repositories where each language is used.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub languages: BTreeMap<String, u64>,
@@ -349,15 +478,34 @@ pub struct RepositoriesStats {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub licenses: BTreeMap<String, u64>,
 
+    /// Number of open issues (pull requests not included).
+    pub open_issues: u64,
+
+    /// Number of open pull requests.
+    pub open_pull_requests: u64,
+
     /// Number of commits per week over the last year.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub participation_stats: Vec<i64>,
 
+    /// Median of the repositories median time (in seconds) to merge the pull
+    /// requests merged during the last 90 days. Please note that this is a
+    /// median of per-repository medians, not the median merge time of all the
+    /// pull requests merged across the repositories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_requests_median_merge_time: Option<i64>,
+
+    /// Number of releases published during the last year.
+    pub releases_last_year: u64,
+
     /// Number of repositories.
     pub repositories: u64,
 
     /// Number of stars.
     pub stars: u64,
+
+    /// Number of watchers.
+    pub watchers: u64,
 }
 
 impl RepositoriesStats {
@@ -366,6 +514,7 @@ impl RepositoriesStats {
     fn new(landscape_data: &LandscapeData) -> Option<Self> {
         let mut stats = RepositoriesStats::default();
         let mut repositories_processed = HashSet::new();
+        let mut pull_requests_merge_times = vec![];
 
         // Collect stats from landscape items
         for item in &landscape_data.items {
@@ -384,6 +533,9 @@ impl RepositoriesStats {
                         // Contributors
                         stats.contributors += gh_data.contributors.count as u64;
 
+                        // Forks
+                        stats.forks += gh_data.forks.unwrap_or_default().unsigned_abs();
+
                         // Languages
                         if let Some(languages) = &gh_data.languages {
                             for (language, value) in languages {
@@ -403,6 +555,11 @@ impl RepositoriesStats {
                             increment(&mut stats.licenses, license, 1);
                         }
 
+                        // Open issues and pull requests
+                        stats.open_issues += gh_data.open_issues.unwrap_or_default().unsigned_abs();
+                        stats.open_pull_requests +=
+                            gh_data.open_pull_requests.unwrap_or_default().unsigned_abs();
+
                         // Participation stats
                         if stats.participation_stats.is_empty() {
                             stats.participation_stats.clone_from(&gh_data.participation_stats);
@@ -415,13 +572,27 @@ impl RepositoriesStats {
                                 .collect();
                         }
 
+                        // Pull requests merge time
+                        if let Some(merge_time) = gh_data.pull_requests_median_merge_time {
+                            pull_requests_merge_times.push(merge_time);
+                        }
+
+                        // Releases
+                        stats.releases_last_year += gh_data.releases_last_year.unwrap_or_default() as u64;
+
                         // Stars
                         stats.stars += gh_data.stars.unsigned_abs();
+
+                        // Watchers
+                        stats.watchers += gh_data.watchers.unwrap_or_default().unsigned_abs();
                     }
                 }
             }
         }
 
+        // Median of the repositories pull requests merge time
+        stats.pull_requests_median_merge_time = median(pull_requests_merge_times);
+
         // Keep only top languages
         stats.languages = stats
             .languages
@@ -446,6 +617,205 @@ impl RepositoriesStats {
     }
 }
 
+/// Some stats about the OpenSSF Scorecard results of the items' primary
+/// repositories.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScorecardStats {
+    /// Average aggregate score.
+    pub average_score: f64,
+
+    /// Average score per check (checks not applicable are not considered).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<String, f64>,
+
+    /// Number of items with Scorecard data.
+    pub items: u64,
+
+    /// Number of items per aggregate score (rounded down).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scores: BTreeMap<u8, u64>,
+}
+
+impl ScorecardStats {
+    /// Create a new ScorecardStats instance from the information available in
+    /// the landscape.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn new(landscape_data: &LandscapeData) -> Option<Self> {
+        let mut stats = ScorecardStats::default();
+        let mut total_score = 0.0;
+        let mut checks_scores: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+
+        // Collect stats from landscape items
+        for item in &landscape_data.items {
+            let Some(scorecard) = &item.openssf_scorecard else {
+                continue;
+            };
+            stats.items += 1;
+            total_score += scorecard.score;
+            increment(&mut stats.scores, &(scorecard.score.clamp(0.0, 10.0) as u8), 1);
+            for check in scorecard.checks.iter().filter(|check| check.score >= 0) {
+                checks_scores.entry(check.name.clone()).or_default().push(check.score);
+            }
+        }
+        if stats.items == 0 {
+            return None;
+        }
+
+        // Calculate averages
+        stats.average_score = round(total_score / stats.items as f64);
+        stats.checks = checks_scores
+            .into_iter()
+            .map(|(name, scores)| {
+                let average = scores.iter().sum::<i64>() as f64 / scores.len() as f64;
+                (name, round(average))
+            })
+            .collect();
+
+        Some(stats)
+    }
+}
+
+/// Number of days considered when calculating the items growth.
+const TRENDS_GROWTH_DAYS: i64 = 90;
+
+/// Maximum number of items included in the fastest growing list.
+const TRENDS_MAX_ITEMS: usize = 10;
+
+/// Some stats about the repositories trends, built from the history of the
+/// repositories metrics collected across builds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrendsStats {
+    /// Number of contributors per year-month (last value collected in the
+    /// month for each repository, or the last known one when no value was
+    /// collected in that month).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contributors: BTreeMap<YearMonth, u64>,
+
+    /// Items whose primary repository stars grew the most during the last
+    /// quarter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fastest_growing: Vec<ItemGrowth>,
+
+    /// Number of stars per year-month (last value collected in the month for
+    /// each repository, or the last known one when no value was collected in
+    /// that month).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stars: BTreeMap<YearMonth, u64>,
+}
+
+impl TrendsStats {
+    /// Create a new TrendsStats instance from the repositories history
+    /// provided (only the repositories in the landscape are considered). The
+    /// stats are built up to the date of the latest point in the history, so
+    /// that they only change when the history does.
+    #[must_use]
+    pub fn new(landscape_data: &LandscapeData, history: &RepositoriesHistory) -> Option<Self> {
+        let latest_date =
+            history.values().filter_map(|repo_history| repo_history.last()).map(|p| p.date).max()?;
+        let mut stats = TrendsStats::default();
+        let mut repositories_processed = HashSet::new();
+        let since = latest_date - Duration::days(TRENDS_GROWTH_DAYS);
+
+        for item in &landscape_data.items {
+            // Contributors and stars per year-month
+            for repo in item.repositories.iter().flatten() {
+                if !repositories_processed.insert(&repo.url) {
+                    continue;
+                }
+                let Some(repo_history) = history.get(&repo.url) else {
+                    continue;
+                };
+                let Some(first_point) = repo_history.first() else {
+                    continue;
+                };
+                let last_points_per_month: BTreeMap<YearMonth, _> = repo_history
+                    .iter()
+                    .map(|point| (point.date.format(YEAR_MONTH_FORMAT).to_string(), point))
+                    .collect();
+
+                // Data is not collected every month for all repositories, so
+                // the last known value is carried forward until a new one is
+                // available (otherwise the totals would drop on those months)
+                let mut month = first_point.date.with_day(1).unwrap_or(first_point.date);
+                let mut last_known_point = None;
+                while month <= latest_date {
+                    let year_month = month.format(YEAR_MONTH_FORMAT).to_string();
+                    if let Some(point) = last_points_per_month.get(&year_month) {
+                        last_known_point = Some(*point);
+                    }
+                    if let Some(point) = last_known_point {
+                        increment(&mut stats.contributors, &year_month, point.contributors as u64);
+                        increment(&mut stats.stars, &year_month, point.stars.unsigned_abs());
+                    }
+                    let Some(next_month) = month.checked_add_months(Months::new(1)) else {
+                        break;
+                    };
+                    month = next_month;
+                }
+            }
+
+            // Item growth (primary repository)
+            let Some(repo_history) = item.primary_repository().and_then(|repo| history.get(&repo.url)) else {
+                continue;
+            };
+            if let Some(growth) = ItemGrowth::new(item, repo_history, since) {
+                stats.fastest_growing.push(growth);
+            }
+        }
+
+        // Keep only the fastest growing items
+        stats.fastest_growing = stats
+            .fastest_growing
+            .into_iter()
+            .filter(|growth| growth.stars_growth > 0)
+            .sorted_by(|a, b| b.stars_growth.cmp(&a.stars_growth).then_with(|| a.name.cmp(&b.name)))
+            .take(TRENDS_MAX_ITEMS)
+            .collect();
+
+        // Return stats collected
+        if stats != TrendsStats::default() {
+            return Some(stats);
+        }
+        None
+    }
+}
+
+/// Growth of an item's primary repository metrics over a period of time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemGrowth {
+    pub id: String,
+    pub name: String,
+    pub contributors_growth: i64,
+    pub stars: i64,
+    pub stars_growth: i64,
+}
+
+impl ItemGrowth {
+    /// Create a new ItemGrowth instance from the repository history provided,
+    /// comparing the latest point available with the last one on or before
+    /// the date given (or the first one available, when the history does not
+    /// go back that far). At least two points are required.
+    #[allow(clippy::cast_possible_wrap)]
+    fn new(item: &crate::data::Item, repo_history: &RepositoryHistory, since: NaiveDate) -> Option<Self> {
+        let first = repo_history.iter().rev().find(|point| point.date <= since).or(repo_history.first())?;
+        let last = repo_history.last()?;
+        if first.date == last.date {
+            return None;
+        }
+        Some(Self {
+            id: item.id.clone(),
+            name: item.name.clone(),
+            contributors_growth: last.contributors as i64 - first.contributors as i64,
+            stars: last.stars,
+            stars_growth: last.stars - first.stars,
+        })
+    }
+}
+
 /// Languages to exclude from top lists.
 const EXCLUDED_LANGUAGES: [&str; 7] = [
     "Batchfile",
@@ -471,6 +841,73 @@ where
     }
 }
 
+/// Collect the ultimate parents of the organizations of the items provided,
+/// returning a map with the key and the name of each parent keyed by each of
+/// the keys that can be used to look it up (see [`crate::data::OrganizationRef::key`]).
+fn collect_ultimate_parents(items: &[Item]) -> HashMap<&String, (&String, &String)> {
+    items
+        .iter()
+        .filter_map(|item| item.crunchbase_data.as_ref().and_then(|d| d.ultimate_parent.as_ref()))
+        .flat_map(|parent| parent.lookup_keys().map(move |key| (key, (parent.key(), &parent.name))))
+        .collect()
+}
+
+/// Get the key and the name of the ultimate parent of the item's
+/// organization. When the organization is itself the ultimate parent of
+/// others, its own parent key is used. Parents are identified by their
+/// Crunchbase url or organization id (or by their name when none of them is
+/// available), so that different organizations sharing the same name are not
+/// merged.
+fn get_ultimate_parent<'a>(
+    item: &'a Item,
+    ultimate_parents: &HashMap<&String, (&'a String, &'a String)>,
+) -> Option<(&'a String, &'a String)> {
+    if let Some(parent) = item.crunchbase_data.as_ref().and_then(|d| d.ultimate_parent.as_ref()) {
+        return Some((parent.key(), &parent.name));
+    }
+    item.crunchbase_url
+        .iter()
+        .chain(item.organization_id.iter())
+        .find_map(|key| ultimate_parents.get(key).copied())
+}
+
+/// Calculate the average of the values provided (rounded to two decimal
+/// places).
+#[allow(clippy::cast_precision_loss)]
+fn average(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(round(values.iter().sum::<f64>() / values.len() as f64))
+}
+
+/// Calculate the average of each of the entries values provided.
+fn averages(map: BTreeMap<String, Vec<f64>>) -> BTreeMap<String, f64> {
+    map.into_iter()
+        .filter_map(|(key, values)| average(&values).map(|average| (key, average)))
+        .collect()
+}
+
+/// Round the value provided to two decimal places.
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Calculate the median of the values provided.
+#[must_use]
+pub fn median(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2)
+    } else {
+        Some(values[mid])
+    }
+}
+
 /// Calculate the running total of the values provided.
 fn calculate_running_total(map: &BTreeMap<YearMonth, u64>) -> BTreeMap<YearMonth, u64> {
     let mut rt = BTreeMap::new();
@@ -488,8 +925,8 @@ fn calculate_running_total(map: &BTreeMap<YearMonth, u64>) -> BTreeMap<YearMonth
 mod tests {
     use super::*;
     use crate::data::{
-        Acquisition, Contributors, FundingRound, Item, ItemAudit, Organization, Repository,
-        RepositoryGithubData,
+        Acquisition, ClomonitorReport, Contributors, FundingRound, Item, ItemAudit, Organization,
+        OrganizationRef, Repository, RepositoryGithubData, Scorecard, ScorecardCheck,
     };
     use chrono::NaiveDate;
 
@@ -534,10 +971,96 @@ mod tests {
             joined_at_rt: vec![("2024-04".to_string(), 1), ("2024-05".to_string(), 2)].into_iter().collect(),
             members: 2,
             subcategories: vec![("Subcategory".to_string(), 2)].into_iter().collect(),
+            ultimate_parents: BTreeMap::new(),
+            ultimate_parents_names: BTreeMap::new(),
         });
         pretty_assertions::assert_eq!(members_stats, expected_members_stats);
     }
 
+    #[test]
+    fn members_stats_new_with_ultimate_parents() {
+        let vendor = OrganizationRef {
+            name: "Vendor".to_string(),
+            crunchbase_url: None,
+            organization_id: Some("vendor".to_string()),
+        };
+        let other_vendor = OrganizationRef {
+            name: "Vendor".to_string(),
+            crunchbase_url: Some("https://crunchbase.com/other-vendor".to_string()),
+            organization_id: None,
+        };
+        let landscape_data = LandscapeData {
+            categories: vec![],
+            items: vec![
+                Item {
+                    // Organization identified by its id (not in Crunchbase)
+                    name: "Vendor".to_string(),
+                    category: "Members".to_string(),
+                    subcategory: "Subcategory".to_string(),
+                    organization_id: vendor.organization_id.clone(),
+                    ..Default::default()
+                },
+                Item {
+                    name: "Product".to_string(),
+                    category: "Members".to_string(),
+                    subcategory: "Subcategory".to_string(),
+                    crunchbase_data: Some(Organization {
+                        ultimate_parent: Some(vendor.clone()),
+                        ..Default::default()
+                    }),
+                    crunchbase_url: Some("https://crunchbase.com/product".to_string()),
+                    ..Default::default()
+                },
+                Item {
+                    // Different parent organization with the same name
+                    name: "Other product".to_string(),
+                    category: "Members".to_string(),
+                    subcategory: "Subcategory".to_string(),
+                    crunchbase_data: Some(Organization {
+                        ultimate_parent: Some(other_vendor.clone()),
+                        ..Default::default()
+                    }),
+                    crunchbase_url: Some("https://crunchbase.com/other-product".to_string()),
+                    ..Default::default()
+                },
+                Item {
+                    name: "Independent".to_string(),
+                    category: "Members".to_string(),
+                    subcategory: "Subcategory".to_string(),
+                    crunchbase_url: Some("https://crunchbase.com/independent".to_string()),
+                    ..Default::default()
+                },
+            ],
+        };
+        let settings = LandscapeSettings {
+            members_category: Some("Members".to_string()),
+            ..Default::default()
+        };
+
+        let members_stats = MembersStats::new(&landscape_data, &settings).unwrap();
+        pretty_assertions::assert_eq!(
+            members_stats.ultimate_parents,
+            vec![
+                ("vendor".to_string(), 2),
+                ("https://crunchbase.com/other-vendor".to_string(), 1)
+            ]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+        );
+        pretty_assertions::assert_eq!(
+            members_stats.ultimate_parents_names,
+            vec![
+                ("vendor".to_string(), "Vendor".to_string()),
+                (
+                    "https://crunchbase.com/other-vendor".to_string(),
+                    "Vendor".to_string()
+                )
+            ]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+        );
+    }
+
     #[test]
     fn organization_stats_new() {
         let landscape_data = LandscapeData {
@@ -628,11 +1151,71 @@ mod tests {
             funding_rounds_money_raised: vec![("2023".to_string(), 100), ("2024".to_string(), 500)]
                 .into_iter()
                 .collect(),
+            ultimate_parents_funding: BTreeMap::new(),
+            ultimate_parents_names: BTreeMap::new(),
+        });
+        assert_eq!(orgs_stats, expected_orgs_stats);
+    }
+
+    #[test]
+    fn organization_stats_new_with_subsidiaries() {
+        let vendor = OrganizationRef {
+            name: "Vendor".to_string(),
+            crunchbase_url: Some("https://crunchbase.com/vendor".to_string()),
+            organization_id: None,
+        };
+        let acquisition = Acquisition {
+            acquiree_cb_permalink: Some("acquiree".to_string()),
+            announced_on: NaiveDate::from_ymd_opt(2024, 5, 1),
+            price: Some(100),
+            ..Default::default()
+        };
+        let landscape_data = LandscapeData {
+            categories: vec![],
+            items: vec![
+                Item {
+                    name: "Vendor".to_string(),
+                    crunchbase_data: Some(Organization {
+                        acquisitions: Some(vec![acquisition.clone()]),
+                        funding: Some(1000),
+                        ..Default::default()
+                    }),
+                    crunchbase_url: vendor.crunchbase_url.clone(),
+                    ..Default::default()
+                },
+                Item {
+                    // The acquisition is also reported by the subsidiary, so
+                    // it should only be counted once
+                    name: "Product".to_string(),
+                    crunchbase_data: Some(Organization {
+                        acquisitions: Some(vec![acquisition]),
+                        funding: Some(500),
+                        ultimate_parent: Some(vendor),
+                        ..Default::default()
+                    }),
+                    crunchbase_url: Some("https://crunchbase.com/product".to_string()),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let orgs_stats = OrganizationsStats::new(&landscape_data);
+        let expected_orgs_stats = Some(OrganizationsStats {
+            acquisitions: vec![("2024".to_string(), 1)].into_iter().collect(),
+            acquisitions_price: vec![("2024".to_string(), 100)].into_iter().collect(),
+            ultimate_parents_funding: vec![("https://crunchbase.com/vendor".to_string(), 1500)]
+                .into_iter()
+                .collect(),
+            ultimate_parents_names: vec![("https://crunchbase.com/vendor".to_string(), "Vendor".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
         });
         assert_eq!(orgs_stats, expected_orgs_stats);
     }
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn projects_stats_new() {
         let landscape_data = LandscapeData {
             categories: vec![],
@@ -651,6 +1234,15 @@ mod tests {
                         date: NaiveDate::from_ymd_opt(2024, 4, 2).unwrap(),
                         ..Default::default()
                     }]),
+                    clomonitor_report: Some(ClomonitorReport {
+                        score: ClomonitorScore {
+                            global: 90.0,
+                            documentation: Some(80.0),
+                            security: Some(100.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
                     ..Default::default()
                 },
                 Item {
@@ -666,6 +1258,14 @@ mod tests {
                         date: NaiveDate::from_ymd_opt(2024, 5, 2).unwrap(),
                         ..Default::default()
                     }]),
+                    clomonitor_report: Some(ClomonitorReport {
+                        score: ClomonitorScore {
+                            global: 75.5,
+                            documentation: Some(60.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
                     ..Default::default()
                 },
                 Item {
@@ -700,6 +1300,22 @@ mod tests {
             )]
             .into_iter()
             .collect(),
+            clomonitor: Some(ClomonitorStats {
+                average_score: 82.75,
+                average_score_by_maturity: vec![
+                    ("graduated".to_string(), 90.0),
+                    ("incubating".to_string(), 75.5),
+                ]
+                .into_iter()
+                .collect(),
+                average_sections_scores: vec![
+                    ("documentation".to_string(), 70.0),
+                    ("security".to_string(), 100.0),
+                ]
+                .into_iter()
+                .collect(),
+                projects: 2,
+            }),
             incubating_to_graduated: vec![("2024-04".to_string(), 1)].into_iter().collect(),
             maturity: vec![("graduated".to_string(), 1), ("incubating".to_string(), 1)].into_iter().collect(),
             projects: 2,
@@ -742,9 +1358,15 @@ mod tests {
                                 .into_iter()
                                 .collect(),
                             ),
+                            forks: Some(1),
                             license: Some("Apache-2.0".to_string()),
+                            open_issues: Some(3),
+                            open_pull_requests: Some(1),
                             participation_stats: vec![1, 2, 3],
+                            pull_requests_median_merge_time: Some(100),
+                            releases_last_year: Some(2),
                             stars: 10,
+                            watchers: Some(5),
                             ..Default::default()
                         }),
                         ..Default::default()
@@ -777,9 +1399,15 @@ mod tests {
                                     .into_iter()
                                     .collect(),
                                 ),
+                                forks: Some(2),
                                 license: Some("MIT".to_string()),
+                                open_issues: Some(4),
+                                open_pull_requests: Some(2),
                                 participation_stats: vec![4, 5, 6],
+                                pull_requests_median_merge_time: Some(300),
+                                releases_last_year: Some(3),
                                 stars: 20,
+                                watchers: Some(10),
                                 ..Default::default()
                             }),
                             ..Default::default()
@@ -803,6 +1431,7 @@ mod tests {
         let expected_repositories_stats = Some(RepositoriesStats {
             bytes: 685,
             contributors: 3,
+            forks: 3,
             languages: vec![
                 ("Rust".to_string(), 2),
                 ("Python".to_string(), 2),
@@ -832,13 +1461,201 @@ mod tests {
             .into_iter()
             .collect(),
             licenses: vec![("Apache-2.0".to_string(), 1), ("MIT".to_string(), 1)].into_iter().collect(),
+            open_issues: 7,
+            open_pull_requests: 3,
             participation_stats: vec![5, 7, 9],
+            pull_requests_median_merge_time: Some(200),
+            releases_last_year: 5,
             repositories: 2,
             stars: 30,
+            watchers: 15,
         });
         pretty_assertions::assert_eq!(repositories_stats, expected_repositories_stats);
     }
 
+    #[test]
+    fn scorecard_stats_new() {
+        let scorecard = |score: f64, checks: Vec<(&str, i64)>| Scorecard {
+            score,
+            checks: checks
+                .into_iter()
+                .map(|(name, score)| ScorecardCheck {
+                    name: name.to_string(),
+                    score,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let landscape_data = LandscapeData {
+            categories: vec![],
+            items: vec![
+                Item {
+                    openssf_scorecard: Some(scorecard(7.5, vec![("Check-1", 10), ("Check-2", 5)])),
+                    ..Default::default()
+                },
+                Item {
+                    openssf_scorecard: Some(scorecard(4.2, vec![("Check-1", 4), ("Check-2", -1)])),
+                    ..Default::default()
+                },
+                Item::default(),
+            ],
+        };
+
+        let scorecard_stats = ScorecardStats::new(&landscape_data);
+        let expected_scorecard_stats = Some(ScorecardStats {
+            average_score: 5.85,
+            checks: vec![("Check-1".to_string(), 7.0), ("Check-2".to_string(), 5.0)].into_iter().collect(),
+            items: 2,
+            scores: vec![(4, 1), (7, 1)].into_iter().collect(),
+        });
+        pretty_assertions::assert_eq!(scorecard_stats, expected_scorecard_stats);
+    }
+
+    #[test]
+    fn trends_stats_new() {
+        let landscape_data = LandscapeData {
+            categories: vec![],
+            items: vec![
+                Item {
+                    id: "item1".to_string(),
+                    name: "Project 1".to_string(),
+                    repositories: Some(vec![Repository {
+                        url: "https://repository1.url".to_string(),
+                        primary: Some(true),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                Item {
+                    id: "item2".to_string(),
+                    name: "Project 2".to_string(),
+                    repositories: Some(vec![Repository {
+                        url: "https://repository2.url".to_string(),
+                        primary: Some(true),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+            ],
+        };
+        let history: RepositoriesHistory = vec![
+            (
+                "https://repository1.url".to_string(),
+                vec![
+                    history_point("2023-01-01", 1, 5),   // Last point before the growth period
+                    history_point("2023-12-01", 2, 10),  // First point in the growth period
+                    history_point("2023-12-15", 3, 20),  // Overridden by the next one
+                    history_point("2023-12-20", 4, 30),  // Last point in the month
+                    history_point("2024-01-01", 5, 100), // Last point
+                ],
+            ),
+            (
+                "https://repository2.url".to_string(),
+                vec![
+                    history_point("2023-12-01", 1, 10),
+                    history_point("2024-01-01", 1, 5),
+                ],
+            ),
+            (
+                "https://not-in-landscape.url".to_string(),
+                vec![history_point("2024-01-01", 1, 1000)],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let trends_stats = TrendsStats::new(&landscape_data, &history);
+        let months_without_points = (1..=11).map(|month| format!("2023-{month:02}"));
+        let expected_trends_stats = Some(TrendsStats {
+            contributors: months_without_points
+                .clone()
+                .map(|year_month| (year_month, 1))
+                .chain([("2023-12".to_string(), 5), ("2024-01".to_string(), 6)])
+                .collect(),
+            fastest_growing: vec![ItemGrowth {
+                id: "item1".to_string(),
+                name: "Project 1".to_string(),
+                contributors_growth: 4,
+                stars: 100,
+                stars_growth: 95,
+            }],
+            stars: months_without_points
+                .map(|year_month| (year_month, 5))
+                .chain([("2023-12".to_string(), 40), ("2024-01".to_string(), 105)])
+                .collect(),
+        });
+        pretty_assertions::assert_eq!(trends_stats, expected_trends_stats);
+    }
+
+    #[test]
+    fn trends_stats_new_carries_last_known_values_forward() {
+        let landscape_data = LandscapeData {
+            categories: vec![],
+            items: vec![Item {
+                id: "item1".to_string(),
+                name: "Project 1".to_string(),
+                repositories: Some(vec![
+                    Repository {
+                        url: "https://repository1.url".to_string(),
+                        primary: Some(true),
+                        ..Default::default()
+                    },
+                    Repository {
+                        url: "https://repository2.url".to_string(),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }],
+        };
+        // Repositories refreshed in different months
+        let history: RepositoriesHistory = vec![
+            (
+                "https://repository1.url".to_string(),
+                vec![
+                    history_point("2024-01-10", 1, 10),
+                    history_point("2024-03-10", 2, 30),
+                ],
+            ),
+            (
+                "https://repository2.url".to_string(),
+                vec![history_point("2024-02-10", 3, 100)],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let trends_stats = TrendsStats::new(&landscape_data, &history).unwrap();
+        pretty_assertions::assert_eq!(
+            trends_stats.contributors,
+            vec![
+                ("2024-01".to_string(), 1),
+                ("2024-02".to_string(), 4),
+                ("2024-03".to_string(), 5),
+            ]
+            .into_iter()
+            .collect()
+        );
+        pretty_assertions::assert_eq!(
+            trends_stats.stars,
+            vec![
+                ("2024-01".to_string(), 10),
+                ("2024-02".to_string(), 110),
+                ("2024-03".to_string(), 130),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn trends_stats_new_no_history() {
+        let landscape_data = LandscapeData::default();
+        let history = RepositoriesHistory::default();
+        assert_eq!(TrendsStats::new(&landscape_data, &history), None);
+    }
+
     #[test]
     fn increment_works() {
         let mut map = std::collections::BTreeMap::new();
@@ -850,6 +1667,14 @@ mod tests {
         assert_eq!(map.get(&"key2"), Some(&1));
     }
 
+    #[test]
+    fn median_works() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![3]), Some(3));
+        assert_eq!(median(vec![5, 1, 3]), Some(3));
+        assert_eq!(median(vec![4, 1, 2, 3]), Some(2));
+    }
+
     #[test]
     fn calculate_running_total_works() {
         let mut map = std::collections::BTreeMap::new();
@@ -862,4 +1687,13 @@ mod tests {
         assert_eq!(rt.get("2024-02"), Some(&3));
         assert_eq!(rt.get("2024-03"), Some(&6));
     }
+
+    fn history_point(date: &str, contributors: usize, stars: i64) -> crate::history::HistoryPoint {
+        crate::history::HistoryPoint {
+            date: date.parse().unwrap(),
+            contributors,
+            stars,
+            ..Default::default()
+        }
+    }
 }
@@ -55,6 +55,7 @@ export interface Item extends BaseItem {
   linkedin_url?: string;
   audits?: SecurityAudit[];
   parent_project?: string;
+  organization_id?: string;
   other_links?: OtherLink[];
 }
 
@@ -87,6 +88,7 @@ export interface AdditionalCategory {
 }
 
 export interface Organization {
+  acquired_by?: OrganizationRef;
   city?: string;
   company_type?: string;
   country?: string;
@@ -100,14 +102,23 @@ export interface Organization {
   name?: string;
   num_employees_max?: number;
   num_employees_min?: number;
+  overridden_fields?: string[];
+  parent?: OrganizationRef;
   region?: string;
   stock_exchange?: string;
   ticker?: string;
   twitter_url?: string;
+  ultimate_parent?: OrganizationRef;
   acquisitions?: Acquisition[];
   funding_rounds?: FundingRound[];
 }
 
+export interface OrganizationRef {
+  name: string;
+  crunchbase_url?: string;
+  organization_id?: string;
+}
+
 export interface Repository {
   url: string;
   branch?: string;
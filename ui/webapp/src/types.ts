@@ -93,6 +93,7 @@ export interface Item extends BaseItem {
   blog_url?: string;
   chat_channel?: string;
   clomonitor_name?: string;
+  clomonitor_report?: ClomonitorReport;
   clomonitor_report_summary?: string;
   member_subcategory?: string;
   crunchbase_data?: Organization;
@@ -112,6 +113,7 @@ export interface Item extends BaseItem {
   latest_annual_review_at?: string;
   latest_annual_review_url?: string;
   openssf_best_practices_url?: string;
+  openssf_scorecard?: Scorecard;
   repositories?: Repository[];
   slack_url?: string;
   specification?: boolean;
@@ -123,6 +125,7 @@ export interface Item extends BaseItem {
   linkedin_url?: string;
   audits?: SecurityAudit[];
   parent_project?: string;
+  organization_id?: string;
   other_links?: OtherLink[];
 }
 
@@ -144,6 +147,7 @@ export interface AdditionalCategory {
 }
 
 export interface Organization {
+  acquired_by?: OrganizationRef;
   city?: string;
   company_type?: string;
   country?: string;
@@ -157,14 +161,24 @@ export interface Organization {
   name?: string;
   num_employees_max?: number;
   num_employees_min?: number;
+  overridden_fields?: string[];
+  parent?: OrganizationRef;
   region?: string;
+  stale?: boolean;
   stock_exchange?: string;
   ticker?: string;
   twitter_url?: string;
+  ultimate_parent?: OrganizationRef;
   acquisitions?: Acquisition[];
   funding_rounds?: FundingRound[];
 }
 
+export interface OrganizationRef {
+  name: string;
+  crunchbase_url?: string;
+  organization_id?: string;
+}
+
 export interface Acquisition {
   announced_on?: string;
   acquiree_name?: string;
@@ -189,6 +203,38 @@ export interface ItemSummary {
   use_case?: string;
 }
 
+export interface ClomonitorReport {
+  generated_at: string;
+  score: {
+    global: number;
+    best_practices?: number;
+    documentation?: number;
+    legal?: number;
+    license?: number;
+    security?: number;
+  };
+  failed_checks?: string[];
+  passed_checks?: string[];
+  rating?: string;
+  url?: string;
+}
+
+export interface Scorecard {
+  generated_at: string;
+  score: number;
+  checks?: ScorecardCheck[];
+  date?: string;
+  stale?: boolean;
+  url?: string;
+}
+
+export interface ScorecardCheck {
+  name: string;
+  score: number;
+  documentation_url?: string;
+  reason?: string;
+}
+
 export interface Repository {
   url: string;
   branch?: string;
@@ -197,18 +243,28 @@ export interface Repository {
 }
 
 export interface GithubRepository {
+  archived?: boolean;
+  branch?: string;
   contributors: Contributors;
   description: string;
+  disabled?: boolean;
   first_commit: Commit;
+  forks?: number;
   generated_at: number;
   languages?: { [key: string]: number };
   latest_commit: Commit;
   latest_release?: Release;
   license: string;
+  open_issues?: number;
+  open_pull_requests?: number;
   participation_stats: number[];
+  pull_requests_median_merge_time?: number;
+  releases_last_year?: number;
+  stale?: boolean;
   stars: number;
   topics: string[];
   url: string;
+  watchers?: number;
 }
 
 export interface Contributors {
@@ -351,6 +407,8 @@ export interface Stats {
     joined_at_rt: { [key: string]: number };
     members: number;
     subcategories: { [key: string]: number };
+    ultimate_parents?: { [key: string]: number };
+    ultimate_parents_names?: { [key: string]: string };
   };
   projects?: {
     accepted_at: { [key: string]: number };
@@ -367,23 +425,67 @@ export interface Stats {
     tag?: {
       [key: string]: number;
     };
+    clomonitor?: {
+      average_score: number;
+      average_score_by_maturity?: { [key: string]: number };
+      average_sections_scores?: { [key: string]: number };
+      projects: number;
+    };
   };
   repositories?: {
     bytes: number;
     contributors: number;
+    forks: number;
     languages: { [key: string]: number };
     languages_bytes: { [key: string]: number };
     licenses: { [key: string]: number };
+    open_issues: number;
+    open_pull_requests: number;
     participation_stats: number[];
+    pull_requests_median_merge_time?: number;
+    releases_last_year: number;
     repositories: number;
     stars: number;
+    watchers: number;
   };
   organizations?: {
     acquisitions: { [key: string]: number };
     acquisitions_price: { [key: string]: number };
     funding_rounds: { [key: string]: number };
     funding_rounds_money_raised: { [key: string]: number };
+    ultimate_parents_funding?: { [key: string]: number };
+    ultimate_parents_names?: { [key: string]: string };
+  };
+  scorecard?: {
+    average_score: number;
+    checks?: { [key: string]: number };
+    items: number;
+    scores?: { [key: string]: number };
   };
+  trends?: {
+    contributors?: { [key: string]: number };
+    fastest_growing?: ItemGrowth[];
+    stars?: { [key: string]: number };
+  };
+}
+
+export interface ItemGrowth {
+  id: string;
+  name: string;
+  contributors_growth: number;
+  stars: number;
+  stars_growth: number;
+}
+
+export interface HistoryPoint {
+  date: string;
+  commits: number;
+  contributors: number;
+  stars: number;
+}
+
+export interface Trends {
+  items?: { [key: string]: HistoryPoint[] };
 }
 
 export interface CategoryValueStats {